@@ -138,6 +138,10 @@ impl Extension for Bootsier {
         Some(&Self)
     }
 
+    fn settings(&self) {
+        std::sync::LazyLock::force(&config::SETTINGS);
+    }
+
     fn configure_service(&self, scfg: &mut service::web::ServiceConfig) {
         static_files_service!(scfg, [bootsier_bs] => "/bootsier/bs");
         static_files_service!(scfg, [bootsier_js] => "/bootsier/js");
@@ -2,12 +2,17 @@
 
 mod figfont;
 
-use crate::core::{extension, extension::ExtensionRef};
+mod export;
+pub use export::SiteExport;
+
+pub mod cli;
+
+use crate::core::{extension, extension::ExtensionRef, job, schedule};
 use crate::html::Markup;
 use crate::locale::Locale;
 use crate::response::page::{ErrorPage, ResultPage};
 use crate::service::HttpRequest;
-use crate::{global, service, trace, PAGETOP_VERSION};
+use crate::{config, global, service, trace, PAGETOP_VERSION};
 
 use actix_session::config::{BrowserSession, PersistentSession, SessionLifecycle};
 use actix_session::storage::CookieSessionStore;
@@ -15,15 +20,22 @@ use actix_session::SessionMiddleware;
 
 use substring::Substring;
 
+use std::future::Future;
 use std::io::Error;
+use std::path::PathBuf;
+use std::pin::Pin;
 use std::sync::LazyLock;
+use std::task::{Context as TaskContext, Poll};
 
 /// Punto de entrada de una aplicación PageTop.
 ///
 /// No almacena datos, **encapsula** el inicio completo de configuración y puesta en marcha. Para
-/// instanciarla se puede usar [`new()`](Application::new) o [`prepare()`](Application::prepare).
+/// instanciarla se puede usar [`new()`](Application::new) o [`prepare()`](Application::prepare), o
+/// sus variantes [`try_new()`](Application::try_new) y [`try_prepare()`](Application::try_prepare)
+/// que devuelven los problemas encontrados en lugar de lanzar *panic*.
 /// Después sólo hay que llamar a [`run()`](Application::run) para ejecutar la aplicación (o a
-/// [`test()`](Application::test) si se está preparando un entorno de pruebas).
+/// [`test()`](Application::test) si se está preparando un entorno de pruebas). Con
+/// [`cli()`](Application::cli) se ejecuta en su lugar la línea de comandos de la aplicación.
 pub struct Application;
 
 impl Default for Application {
@@ -34,8 +46,19 @@ impl Default for Application {
 
 impl Application {
     /// Crea una instancia de la aplicación.
+    ///
+    /// # Panics
+    ///
+    /// Si la aplicación no se puede preparar, con el informe de [`try_new()`](Self::try_new).
     pub fn new() -> Self {
-        Self::internal_prepare(None)
+        Self::internal_prepare(None, true).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Crea una instancia de la aplicación, o devuelve los problemas que lo impiden.
+    ///
+    /// Ver [`PrepareError`].
+    pub fn try_new() -> Result<Self, PrepareError> {
+        Self::internal_prepare(None, true)
     }
 
     /// Prepara una instancia de la aplicación a partir de una extensión raíz.
@@ -46,31 +69,98 @@ impl Application {
     /// - Una lista de extensiones que deben deshabilitarse si estuvieran activadas.
     ///
     /// Esto simplifica el arranque en escenarios complejos.
+    ///
+    /// # Panics
+    ///
+    /// Si la aplicación no se puede preparar, con el informe de
+    /// [`try_prepare()`](Self::try_prepare).
     pub fn prepare(root_extension: ExtensionRef) -> Self {
-        Self::internal_prepare(Some(root_extension))
+        Self::internal_prepare(Some(root_extension), true).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Prepara una instancia de la aplicación a partir de una extensión raíz, o devuelve los
+    /// problemas que lo impiden.
+    ///
+    /// Ver [`prepare()`](Self::prepare) y [`PrepareError`].
+    pub fn try_prepare(root_extension: ExtensionRef) -> Result<Self, PrepareError> {
+        Self::internal_prepare(Some(root_extension), true)
+    }
+
+    /// Prepara una instancia de la aplicación, igual que [`try_prepare()`](Self::try_prepare),
+    /// para ejecutar la línea de comandos con [`cli()`](Self::cli).
+    ///
+    /// Además de los archivos y las variables de entorno, la configuración incluye los ajustes que
+    /// se pasan al programa con `--set clave=valor` (ver [`config`]).
+    ///
+    /// Con los subcomandos predefinidos que sólo consultan la aplicación (`routes`, `extensions`,
+    /// `config`, `check`, `reload` o la ayuda) no se instalan, actualizan ni desinstalan las
+    /// extensiones (ver [`Extension::install()`](crate::core::extension::Extension::install)).
+    pub fn prepare_cli(root_extension: ExtensionRef) -> Result<Self, PrepareError> {
+        config::enable_args_overrides();
+        let lifecycle = !cli::is_read_only(std::env::args_os());
+        Self::internal_prepare(Some(root_extension), lifecycle)
     }
 
-    /// Método interno para preparar la aplicación, opcionalmente con una extensión.
-    fn internal_prepare(root_extension: Option<ExtensionRef>) -> Self {
+    /// Método interno para preparar la aplicación, opcionalmente con una extensión. Con `lifecycle`
+    /// instala, actualiza o desinstala las extensiones que lo necesiten.
+    fn internal_prepare(
+        root_extension: Option<ExtensionRef>,
+        lifecycle: bool,
+    ) -> Result<Self, PrepareError> {
         // Al arrancar muestra una cabecera para la aplicación.
         Self::show_banner();
 
         // Inicia gestión de trazas y registro de eventos (logging).
         LazyLock::force(&trace::TRACING);
 
+        // Comprueba los ajustes globales antes de preparar las extensiones.
+        Self::check_config(false)?;
+
         // Inicializa el idioma predeterminado.
         Locale::init();
 
         // Registra las extensiones de la aplicación.
-        extension::all::register_extensions(root_extension);
+        let problems = extension::all::register_extensions(root_extension);
+        PrepareError::check("Invalid extensions", problems)?;
+
+        // Comprueba los ajustes de las extensiones antes de instalarlas e inicializarlas.
+        extension::all::load_settings();
+        Self::check_config(true)?;
 
         // Registra las acciones de las extensiones.
         extension::all::register_actions();
 
+        // Registra los suscriptores a eventos de las extensiones.
+        extension::all::register_subscribers();
+
+        // Instala, actualiza o desinstala las extensiones que lo necesiten.
+        if lifecycle {
+            let problems = extension::all::run_lifecycle();
+            PrepareError::check("Extension setup failed", problems)?;
+        }
+
         // Inicializa las extensiones.
         extension::all::initialize_extensions();
 
-        Self
+        Ok(Self)
+    }
+
+    /// Devuelve los errores de la configuración cargada hasta ahora. Con `warn`, informa además de
+    /// los avisos y de los errores de los ajustes que se carguen después.
+    fn check_config(warn: bool) -> Result<(), PrepareError> {
+        let mut errors = Vec::new();
+        for issue in config::issues() {
+            match issue.level() {
+                config::IssueLevel::Error => errors.push(issue.to_string()),
+                config::IssueLevel::Warning if warn => trace::warn!("Check settings, {issue}"),
+                config::IssueLevel::Warning => {}
+            }
+        }
+        PrepareError::check("Invalid configuration", errors)?;
+        if warn {
+            config::report_late_issues();
+        }
+        Ok(())
     }
 
     /// Muestra una cabecera para la aplicación basada en la configuración.
@@ -116,14 +206,26 @@ impl Application {
 
     /// Arranca el servidor web de la aplicación.
     ///
+    /// Devuelve un [`AppServer`] que se ejecuta al esperarlo con `.await`. Cuando el servidor se
+    /// detiene espera a los [trabajos en segundo plano](job) pendientes con [`job::drain()`].
+    ///
     /// Devuelve [`std::io::Error`] si el *socket* no puede enlazarse (por puerto en uso, permisos,
     /// etc.).
-    pub fn run(self) -> Result<service::Server, Error> {
+    pub fn run(self) -> Result<AppServer, Error> {
         // Genera clave secreta para firmar y verificar cookies.
         let secret_key = service::cookie::Key::generate();
 
+        // Recarga los ajustes dinámicos al recibir SIGHUP o al cambiar los archivos.
+        Self::watch_config();
+
+        // Reanuda los trabajos en segundo plano pendientes.
+        job::start();
+
+        // Programa las tareas periódicas de las extensiones.
+        schedule::start();
+
         // Prepara el servidor web.
-        Ok(service::HttpServer::new(move || {
+        let server = service::HttpServer::new(move || {
             Self::service_app()
                 .wrap(tracing_actix_web::TracingLogger::default())
                 .wrap(
@@ -146,7 +248,76 @@ impl Application {
             &global::SETTINGS.server.bind_address,
             &global::SETTINGS.server.bind_port
         ))?
-        .run())
+        .run();
+        Ok(AppServer::new(server))
+    }
+
+    /// Activa la recarga de la configuración mientras el servidor web está en marcha.
+    fn watch_config() {
+        #[cfg(unix)]
+        service::rt::spawn(async {
+            use service::rt::signal::unix::{signal, SignalKind};
+            match signal(SignalKind::hangup()) {
+                Ok(mut hangup) => {
+                    while hangup.recv().await.is_some() {
+                        config::reload_and_report("SIGHUP");
+                    }
+                }
+                Err(e) => trace::warn!("Unable to listen for SIGHUP: {e}"),
+            }
+        });
+        if global::SETTINGS.app.watch_config {
+            config::watch_config_dir();
+        }
+    }
+
+    /// Ejecuta la línea de comandos de la aplicación con los argumentos del programa.
+    ///
+    /// Sin subcomando, o con `serve`, arranca el servidor web igual que [`run()`](Self::run). Ver
+    /// [`cli`] para los subcomandos disponibles y cómo añadir otros desde las extensiones. Antes de
+    /// terminar espera a los [trabajos en segundo plano](job) pendientes con [`job::drain()`].
+    ///
+    /// Los ajustes `--set` sólo se admiten si la aplicación se ha preparado con
+    /// [`prepare_cli()`](Self::prepare_cli).
+    pub async fn cli(self) -> Result<(), Error> {
+        self.cli_from(std::env::args_os()).await
+    }
+
+    /// Ejecuta la línea de comandos de la aplicación con los argumentos indicados.
+    ///
+    /// El primer argumento es el nombre del programa, como en [`std::env::args_os()`].
+    pub async fn cli_from<I, T>(self, args: I) -> Result<(), Error>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<std::ffi::OsString> + Clone,
+    {
+        let result = self.dispatch_cli(args).await;
+        job::drain().await;
+        result
+    }
+
+    async fn dispatch_cli<I, T>(self, args: I) -> Result<(), Error>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<std::ffi::OsString> + Clone,
+    {
+        match cli::dispatch(args).await? {
+            Some(cli::Builtin::Serve) => self.run()?.await,
+            Some(cli::Builtin::Export(args)) => {
+                let output = args.get_one::<String>("output").expect("Required argument");
+                let mut export = SiteExport::new(output)
+                    .with_crawl(!args.get_flag("no-crawl"))
+                    .with_languages(!args.get_flag("no-languages"));
+                if let Some(base_url) = args.get_one::<String>("base-url") {
+                    export = export.with_base_url(base_url);
+                }
+                for route in args.get_many::<String>("route").unwrap_or_default() {
+                    export = export.with_route(route);
+                }
+                self.export(export).await.map(|_| ())
+            }
+            None => Ok(()),
+        }
     }
 
     /// Prepara el servidor web de la aplicación para pruebas.
@@ -164,6 +335,18 @@ impl Application {
         Self::service_app()
     }
 
+    /// Exporta la aplicación como sitio estático según las opciones indicadas.
+    ///
+    /// Usa el servicio web interno de la aplicación, sin arrancar ningún servidor. Devuelve la
+    /// lista de archivos guardados o el primer error de escritura. Ver [`SiteExport`] para más
+    /// detalles.
+    pub async fn export(self, export: SiteExport) -> Result<Vec<PathBuf>, Error> {
+        let app = service::test::init_service(Self::service_app()).await;
+        export
+            .run(&app, service::test::TestRequest::to_request)
+            .await
+    }
+
     /// Configura el servicio web de la aplicación.
     fn service_app() -> service::App<
         impl service::Factory<
@@ -183,3 +366,93 @@ impl Application {
 async fn service_not_found(request: HttpRequest) -> ResultPage<Markup, ErrorPage> {
     Err(ErrorPage::NotFound(request))
 }
+
+// **< PrepareError >*******************************************************************************
+
+/// Problemas que impiden preparar la aplicación.
+///
+/// Lo devuelven [`Application::try_new()`], [`Application::try_prepare()`] y
+/// [`Application::prepare_cli()`] cuando la configuración no es válida o las extensiones no se
+/// pueden habilitar o instalar. Muestra un informe con todos los problemas encontrados, y se puede
+/// convertir en [`std::io::Error`] para devolverlo con `?` desde la función `main()`.
+#[derive(Debug)]
+pub struct PrepareError {
+    title: &'static str,
+    problems: Vec<String>,
+}
+
+impl PrepareError {
+    /// Devuelve la descripción general de los problemas.
+    pub fn title(&self) -> &str {
+        self.title
+    }
+
+    /// Devuelve los problemas encontrados.
+    pub fn problems(&self) -> &[String] {
+        &self.problems
+    }
+
+    // Devuelve un error con los problemas indicados, si hay alguno.
+    fn check(title: &'static str, problems: Vec<String>) -> Result<(), PrepareError> {
+        match problems.is_empty() {
+            true => Ok(()),
+            false => Err(PrepareError { title, problems }),
+        }
+    }
+}
+
+impl std::fmt::Display for PrepareError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:", self.title)?;
+        for problem in &self.problems {
+            write!(f, "\n  - {problem}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for PrepareError {}
+
+impl From<PrepareError> for Error {
+    fn from(error: PrepareError) -> Self {
+        Error::other(error)
+    }
+}
+
+// **< AppServer >**********************************************************************************
+
+/// Servidor web en marcha devuelto por [`Application::run()`].
+///
+/// Se ejecuta al esperarlo con `.await` y termina cuando el servidor se detiene, después de esperar
+/// a los [trabajos en segundo plano](job) pendientes con [`job::drain()`]. Con
+/// [`handle()`](Self::handle) se puede detener el servidor desde otra tarea.
+pub struct AppServer {
+    handle: service::ServerHandle,
+    future: Pin<Box<dyn Future<Output = Result<(), Error>>>>,
+}
+
+impl AppServer {
+    fn new(server: service::Server) -> Self {
+        AppServer {
+            handle: server.handle(),
+            future: Box::pin(async move {
+                let result = server.await;
+                job::drain().await;
+                result
+            }),
+        }
+    }
+
+    /// Devuelve un manejador para controlar el servidor, por ejemplo para detenerlo.
+    pub fn handle(&self) -> service::ServerHandle {
+        self.handle.clone()
+    }
+}
+
+impl Future for AppServer {
+    type Output = Result<(), Error>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<Self::Output> {
+        self.future.as_mut().poll(cx)
+    }
+}
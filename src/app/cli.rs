@@ -0,0 +1,479 @@
+//! Interfaz de línea de comandos de la aplicación.
+//!
+//! [`Application::cli()`](crate::app::Application::cli) interpreta los argumentos del programa y
+//! ejecuta uno de los subcomandos predefinidos:
+//!
+//! - `serve`, arranca el servidor web (es el subcomando por defecto si no se indica ninguno).
+//! - `routes`, muestra el [inventario de rutas](crate::service::routes) del servicio web.
+//! - `extensions`, muestra las extensiones habilitadas, las descartadas, las deshabilitadas en la
+//!   configuración y los temas.
+//! - `config`, muestra los ajustes efectivos combinando los archivos de configuración con los
+//!   valores por defecto, ocultando los que pueden contener secretos (contraseñas, claves o los
+//!   leídos de archivos indicados en variables `*_FILE`).
+//! - `check`, comprueba la configuración y los idiomas, y termina con error si hay problemas.
+//! - `reload`, pide a la aplicación en marcha con el PID indicado que recargue los
+//!   [ajustes dinámicos](crate::config::DynamicSetting), enviándole la señal `SIGHUP` (sólo en
+//!   sistemas Unix).
+//! - `task`, lista las [tareas periódicas](crate::core::schedule) de las extensiones o, si se
+//!   indica su nombre, ejecuta una de ellas en el momento.
+//! - `export`, exporta la aplicación como sitio estático (ver
+//!   [`SiteExport`](crate::app::SiteExport)).
+//!
+//! Si la aplicación se prepara con
+//! [`Application::prepare_cli()`](crate::app::Application::prepare_cli), con cualquier subcomando
+//! se pueden sobrescribir ajustes de configuración usando `--set clave=valor` (ver [`config`]). En
+//! ese caso `routes`, `extensions`, `config`, `check` y `reload` no instalan, actualizan ni
+//! desinstalan las extensiones, para no modificar sus datos.
+//!
+//! Las extensiones pueden añadir sus propios subcomandos implementando
+//! [`Extension::commands()`](crate::core::extension::Extension::commands), que devuelve una lista
+//! de [`Command`]. Los argumentos se declaran con [`Arg`] y se leen con [`ArgMatches`] (de
+//! [clap](https://docs.rs/clap)).
+//!
+//! # Ejemplo
+//!
+//! ```rust,no_run
+//! # use pagetop::prelude::*;
+//! pub struct Blog;
+//!
+//! impl Extension for Blog {
+//!     fn commands(&self) -> Vec<cli::Command> {
+//!         vec![cli::Command::new("seed")
+//!             .with_about("Creates sample posts")
+//!             .with_arg(cli::Arg::new("count").long("count").default_value("10"))
+//!             .to(seed)]
+//!     }
+//! }
+//!
+//! async fn seed(args: cli::ArgMatches) -> std::io::Result<()> {
+//!     let count = args.get_one::<String>("count").unwrap();
+//!     println!("Creating {count} posts");
+//!     Ok(())
+//! }
+//!
+//! #[pagetop::main]
+//! async fn main() -> std::io::Result<()> {
+//!     Application::prepare_cli(&Blog)?.cli().await
+//! }
+//! ```
+
+pub use clap::{Arg, ArgAction, ArgMatches};
+
+use crate::core::extension::all as extensions;
+use crate::core::schedule;
+use crate::core::theme::all::{DEFAULT_THEME, THEMES};
+use crate::locale::{has_translations, L10n, Locale};
+use crate::{config, global, service, trace, util};
+
+use std::future::Future;
+use std::io::{Error, ErrorKind};
+use std::pin::Pin;
+use std::sync::LazyLock;
+
+type CommandHandler = Box<dyn Fn(ArgMatches) -> Pin<Box<dyn Future<Output = Result<(), Error>>>>>;
+
+// **< Command >************************************************************************************
+
+/// Subcomando de la línea de comandos que aporta una extensión.
+///
+/// Ver [`Extension::commands()`](crate::core::extension::Extension::commands).
+pub struct Command {
+    command: clap::Command,
+    handler: Option<CommandHandler>,
+}
+
+impl Command {
+    /// Crea un subcomando con el nombre indicado.
+    pub fn new(name: impl Into<String>) -> Self {
+        Command {
+            command: clap::Command::new(name.into()),
+            handler: None,
+        }
+    }
+
+    /// Establece la descripción corta del subcomando, que se muestra en la ayuda.
+    pub fn with_about(mut self, about: impl Into<String>) -> Self {
+        self.command = self.command.about(about.into());
+        self
+    }
+
+    /// Añade un argumento al subcomando.
+    pub fn with_arg(mut self, arg: Arg) -> Self {
+        self.command = self.command.arg(arg);
+        self
+    }
+
+    /// Asigna la función asíncrona que ejecuta el subcomando con los argumentos recibidos.
+    pub fn to<F, Fut>(mut self, handler: F) -> Self
+    where
+        F: Fn(ArgMatches) -> Fut + 'static,
+        Fut: Future<Output = Result<(), Error>> + 'static,
+    {
+        self.handler = Some(Box::new(move |args| Box::pin(handler(args))));
+        self
+    }
+
+    /// Devuelve el nombre del subcomando.
+    pub fn name(&self) -> &str {
+        self.command.get_name()
+    }
+}
+
+// **< Subcomandos predefinidos >*******************************************************************
+
+// Subcomandos que ejecuta la propia aplicación.
+pub(crate) enum Builtin {
+    Serve,
+    Export(ArgMatches),
+}
+
+// Subcomandos predefinidos que sólo consultan la aplicación, sin modificar sus datos.
+const READ_ONLY: [&str; 6] = ["routes", "extensions", "config", "check", "reload", "help"];
+
+// Indica si los argumentos del programa piden la ayuda o un subcomando predefinido que sólo consulta
+// la aplicación. Sin subcomando se arranca el servidor web.
+pub(crate) fn is_read_only<I, T>(args: I) -> bool
+where
+    I: IntoIterator<Item = T>,
+    T: Into<std::ffi::OsString>,
+{
+    let mut args = args
+        .into_iter()
+        .skip(1)
+        .map(|arg| arg.into().to_string_lossy().into_owned());
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--set" => {
+                args.next();
+            }
+            "-h" | "--help" => return true,
+            option if option.starts_with('-') => {}
+            command => return READ_ONLY.contains(&command),
+        }
+    }
+    false
+}
+
+// Interpreta los argumentos y ejecuta el subcomando correspondiente. Devuelve el subcomando
+// predefinido que debe ejecutar la aplicación, si es el caso.
+pub(crate) async fn dispatch<I, T>(args: I) -> Result<Option<Builtin>, Error>
+where
+    I: IntoIterator<Item = T>,
+    T: Into<std::ffi::OsString> + Clone,
+{
+    // Descarta los subcomandos con el nombre de otro ya declarado.
+    let mut cli = builtin_commands();
+    let mut commands: Vec<Command> = Vec::new();
+    for command in extensions::commands() {
+        if cli.find_subcommand(command.name()).is_some() {
+            trace::warn!(
+                "Command \"{}\" is already declared, ignoring",
+                command.name()
+            );
+            continue;
+        }
+        cli = cli.subcommand(command.command.clone());
+        commands.push(command);
+    }
+
+    let matches = match cli.try_get_matches_from(args) {
+        Ok(matches) => matches,
+        // La ayuda no es un error.
+        Err(e) if !e.use_stderr() => {
+            e.print()?;
+            return Ok(None);
+        }
+        Err(e) => {
+            e.print()?;
+            return Err(Error::new(ErrorKind::InvalidInput, e.kind().to_string()));
+        }
+    };
+
+    // Los ajustes `--set` ya se tienen que haber aplicado al cargar la configuración.
+    if matches.contains_id("set") && !config::args_overrides_applied() {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "Settings given with --set require Application::prepare_cli()",
+        ));
+    }
+
+    match matches.subcommand() {
+        None | Some(("serve", _)) => Ok(Some(Builtin::Serve)),
+        Some(("export", args)) => Ok(Some(Builtin::Export(args.clone()))),
+        Some(("routes", _)) => {
+            print_routes();
+            Ok(None)
+        }
+        Some(("extensions", _)) => {
+            print_extensions();
+            Ok(None)
+        }
+        Some(("config", _)) => {
+            print_config()?;
+            Ok(None)
+        }
+        Some(("check", _)) => {
+            check()?;
+            Ok(None)
+        }
+        Some(("reload", args)) => {
+            let pid = args.get_one::<u32>("pid").expect("Required argument");
+            send_reload(*pid)?;
+            Ok(None)
+        }
+        Some(("task", args)) => {
+            match args.get_one::<String>("name") {
+                Some(name) => schedule::run_now(name).await?,
+                None => print_tasks(),
+            }
+            Ok(None)
+        }
+        Some((name, args)) => {
+            let index = commands
+                .iter()
+                .position(|command| command.name() == name)
+                .expect("Subcommand declared by an extension");
+            match commands.swap_remove(index).handler {
+                Some(handler) => handler(args.clone()).await?,
+                None => {
+                    return Err(Error::new(
+                        ErrorKind::Unsupported,
+                        format!("Command \"{name}\" has no handler"),
+                    ))
+                }
+            }
+            Ok(None)
+        }
+    }
+}
+
+fn builtin_commands() -> clap::Command {
+    clap::Command::new(global::SETTINGS.app.name.clone())
+        .about(global::SETTINGS.app.description.clone())
+        // Los ajustes `--set` se aplican al cargar la configuración, ver `config`.
+        .arg(
+            Arg::new("set")
+                .long("set")
+                .global(true)
+                .action(ArgAction::Append)
+                .value_name("KEY=VALUE")
+                .help("Overrides a setting of the configuration files"),
+        )
+        .subcommand(clap::Command::new("serve").about("Starts the web server (default)"))
+        .subcommand(clap::Command::new("routes").about("Lists the routes of the web service"))
+        .subcommand(
+            clap::Command::new("extensions").about("Lists enabled, dropped and theme extensions"),
+        )
+        .subcommand(clap::Command::new("config").about("Shows the effective settings"))
+        .subcommand(clap::Command::new("check").about("Checks the settings and languages"))
+        .subcommand(
+            clap::Command::new("reload")
+                .about("Asks a running application to reload its dynamic settings")
+                .arg(
+                    Arg::new("pid")
+                        .required(true)
+                        .value_parser(clap::value_parser!(u32))
+                        .help("Process ID of the running application"),
+                ),
+        )
+        .subcommand(
+            clap::Command::new("task")
+                .about("Lists the scheduled tasks or runs one of them now")
+                .arg(Arg::new("name").help("Name of the task to run")),
+        )
+        .subcommand(
+            clap::Command::new("export")
+                .about("Exports the application as a static site")
+                .arg(Arg::new("output").required(true).help("Output directory"))
+                .arg(
+                    Arg::new("base-url")
+                        .long("base-url")
+                        .help("Scheme and host of the published site"),
+                )
+                .arg(
+                    Arg::new("route")
+                        .long("route")
+                        .action(ArgAction::Append)
+                        .help("Additional route to export"),
+                )
+                .arg(
+                    Arg::new("no-crawl")
+                        .long("no-crawl")
+                        .action(ArgAction::SetTrue)
+                        .help("Do not follow the links of exported pages"),
+                )
+                .arg(
+                    Arg::new("no-languages")
+                        .long("no-languages")
+                        .action(ArgAction::SetTrue)
+                        .help("Do not export pages in every supported language"),
+                ),
+        )
+}
+
+fn print_routes() {
+    // El inventario se completa al configurar el servicio web.
+    let _ = crate::app::Application::service_app();
+    print!(
+        "{}",
+        service::format_routes(&service::routes(), &service::unlisted_routes())
+    );
+}
+
+fn print_extensions() {
+    let themes = THEMES.read();
+    let describe = |name: L10n| name.get().unwrap_or_default();
+
+    println!("Enabled extensions:");
+    for extension in extensions::enabled_extensions() {
+        let mut notes = Vec::new();
+        if themes
+            .iter()
+            .any(|theme| theme.type_id() == extension.type_id())
+        {
+            notes.push("theme");
+            if DEFAULT_THEME.type_id() == extension.type_id() {
+                notes.push("default");
+            }
+        }
+        let notes = if notes.is_empty() {
+            String::new()
+        } else {
+            format!(" [{}]", notes.join(", "))
+        };
+        println!(
+            "  {:<24} {}{notes}",
+            extension.short_name(),
+            describe(extension.name())
+        );
+    }
+
+    for (title, list) in [
+        ("Dropped extensions:", extensions::dropped_extensions()),
+        ("Disabled extensions:", extensions::disabled_extensions()),
+    ] {
+        println!("{title}");
+        if list.is_empty() {
+            println!("  (none)");
+        }
+        for extension in list {
+            println!(
+                "  {:<24} {}",
+                extension.short_name(),
+                describe(extension.name())
+            );
+        }
+    }
+}
+
+fn print_tasks() {
+    if !global::SETTINGS.app.scheduled_tasks {
+        println!("Scheduling is disabled in this instance (app.scheduled_tasks)");
+    }
+    println!("Scheduled tasks:");
+    let tasks = schedule::tasks();
+    if tasks.is_empty() {
+        println!("  (none)");
+    }
+    for task in tasks {
+        println!(
+            "  {:<24} {:<20} {}",
+            task.name(),
+            task.schedule().to_string(),
+            task.owner()
+        );
+    }
+}
+
+fn print_config() -> Result<(), Error> {
+    // Sólo se conocen los valores por defecto de los ajustes ya cargados.
+    LazyLock::force(&global::SETTINGS);
+    extensions::load_settings();
+
+    let values = config::effective_values().map_err(Error::other)?;
+    for (key, value) in values {
+        if config::is_secret(&key) {
+            println!("{key} = \"********\"");
+        } else {
+            println!("{key} = {value}");
+        }
+    }
+    Ok(())
+}
+
+// Envía la señal SIGHUP al proceso indicado para que recargue la configuración.
+#[cfg(unix)]
+fn send_reload(pid: u32) -> Result<(), Error> {
+    let pid = libc::pid_t::try_from(pid)
+        .map_err(|_| Error::new(ErrorKind::InvalidInput, format!("Invalid process ID {pid}")))?;
+    // SAFETY: `kill()` sólo envía una señal al proceso indicado, no accede a la memoria.
+    if unsafe { libc::kill(pid, libc::SIGHUP) } != 0 {
+        let error = Error::last_os_error();
+        return Err(Error::new(
+            error.kind(),
+            format!("Unable to send SIGHUP to process {pid}: {error}"),
+        ));
+    }
+    println!("Reload requested to process {pid}");
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn send_reload(_pid: u32) -> Result<(), Error> {
+    Err(Error::new(
+        ErrorKind::Unsupported,
+        "Reloading a running application requires SIGHUP (Unix only)",
+    ))
+}
+
+// Comprueba la configuración y los idiomas, mostrando cada problema encontrado.
+fn check() -> Result<(), Error> {
+    let mut problems = Vec::new();
+
+    for issue in config::issues() {
+        match issue.level() {
+            config::IssueLevel::Error => problems.push(issue.to_string()),
+            config::IssueLevel::Warning => println!("  Warning: {issue}"),
+        }
+    }
+
+    let app = &global::SETTINGS.app;
+    if let Some(language) = app.language.as_deref() {
+        if !language.trim().is_empty() && Locale::configured_langid().is_none() {
+            problems.push(format!(
+                "Unsupported language \"{language}\" in app.language"
+            ));
+        }
+    }
+    if !THEMES
+        .read()
+        .iter()
+        .any(|theme| theme.short_name().eq_ignore_ascii_case(&app.theme))
+    {
+        problems.push(format!("Unknown theme \"{}\" in app.theme", app.theme));
+    }
+    let static_dir = &global::SETTINGS.dev.pagetop_static_dir;
+    if !static_dir.is_empty() && util::resolve_absolute_dir(static_dir).is_err() {
+        problems.push(format!(
+            "Directory \"{static_dir}\" in dev.pagetop_static_dir not found"
+        ));
+    }
+
+    for langid in Locale::supported_langids() {
+        if !has_translations(langid) {
+            problems.push(format!("Missing translations for language \"{langid}\""));
+        }
+    }
+
+    if problems.is_empty() {
+        println!("No problems found");
+        return Ok(());
+    }
+    for problem in &problems {
+        eprintln!("  {problem}");
+    }
+    Err(Error::new(
+        ErrorKind::InvalidData,
+        format!("{} problem(s) found", problems.len()),
+    ))
+}
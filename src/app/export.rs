@@ -0,0 +1,358 @@
+use crate::core::extension::all::sitemap_entries;
+use crate::locale::Locale;
+use crate::response::page::ErrorPage;
+use crate::response::ResponseError;
+use crate::service::http::{header, StatusCode};
+use crate::service::test::TestRequest;
+use crate::service::{self, static_files};
+use crate::{global, trace};
+
+use actix_web::body;
+use actix_web::dev::Service;
+
+use std::collections::{HashSet, VecDeque};
+use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+
+/// Opciones para exportar una aplicación como sitio estático.
+///
+/// Se usa con [`Application::export()`](crate::app::Application::export), que recorre las rutas de
+/// la aplicación con el servicio web interno, sin arrancar ningún servidor, y guarda las respuestas
+/// en un directorio listo para publicarse en cualquier alojamiento de archivos estáticos:
+///
+/// - Parte de la página de inicio (`"/"`), de las rutas añadidas con
+///   [`with_route()`](Self::with_route), de las rutas del [mapa del sitio](crate::response::sitemap)
+///   y de `/robots.txt`.
+/// - Si [`with_crawl()`](Self::with_crawl) está activado (por defecto), sigue también los enlaces
+///   locales (`href` y `src`) de cada página HTML exportada.
+/// - Si [`with_languages()`](Self::with_languages) está activado (por defecto) y la negociación de
+///   idioma lo permite, exporta cada página en todos los idiomas soportados bajo un directorio con
+///   el nombre del idioma (p. ej. `es-ES/about/index.html` para `/about?lang=es-ES`), reescribiendo
+///   los enlaces `?lang=` de las páginas exportadas.
+/// - Guarda la página de error 404 en `404.html` y copia todos los recursos embebidos que se
+///   publican con [`static_files_service!`](crate::static_files_service).
+///
+/// Las páginas HTML se guardan como `index.html` en un directorio con el nombre de la ruta (p. ej.
+/// `about/index.html` para `/about`). Los enlaces locales con otros parámetros de consulta no se
+/// pueden representar como archivos estáticos y se ignoran.
+///
+/// # Ejemplo
+///
+/// ```rust,no_run
+/// # use pagetop::prelude::*;
+/// # struct MySite;
+/// # impl Extension for MySite {}
+/// #[pagetop::main]
+/// async fn main() -> std::io::Result<()> {
+///     let files = Application::prepare(&MySite)
+///         .export(
+///             SiteExport::new("dist")
+///                 .with_base_url("https://example.com")
+///                 .with_route("/about"),
+///         )
+///         .await?;
+///     println!("Exported {} files", files.len());
+///     Ok(())
+/// }
+/// ```
+#[rustfmt::skip]
+pub struct SiteExport {
+    output   : PathBuf,
+    base_url : Option<String>,
+    routes   : Vec<String>,
+    crawl    : bool,
+    languages: bool,
+}
+
+impl SiteExport {
+    /// Prepara la exportación en el directorio indicado, que se creará si no existe.
+    pub fn new(output: impl Into<PathBuf>) -> Self {
+        SiteExport {
+            output: output.into(),
+            base_url: None,
+            routes: Vec::new(),
+            crawl: true,
+            languages: true,
+        }
+    }
+
+    // **< SiteExport BUILDER >*********************************************************************
+
+    /// Establece el esquema y el servidor del sitio publicado (p. ej. `"https://example.com"`).
+    ///
+    /// Se usa para generar las URL absolutas de documentos como el mapa del sitio o los canales de
+    /// sindicación. Por defecto se usan los de las peticiones de prueba (`http://localhost:8080`).
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = Some(base_url.into());
+        self
+    }
+
+    /// Añade una ruta a exportar.
+    pub fn with_route(mut self, route: impl Into<String>) -> Self {
+        self.routes.push(route.into());
+        self
+    }
+
+    /// Activa o desactiva el seguimiento de los enlaces locales de las páginas exportadas.
+    pub fn with_crawl(mut self, crawl: bool) -> Self {
+        self.crawl = crawl;
+        self
+    }
+
+    /// Activa o desactiva la exportación de las páginas en todos los idiomas soportados.
+    pub fn with_languages(mut self, languages: bool) -> Self {
+        self.languages = languages;
+        self
+    }
+
+    // **< SiteExport RUN >*************************************************************************
+
+    pub(crate) async fn run<S, R>(
+        self,
+        app: &S,
+        to_request: fn(TestRequest) -> R,
+    ) -> io::Result<Vec<PathBuf>>
+    where
+        S: Service<R, Response = service::Response<service::BoxBody>, Error = service::Error>,
+    {
+        fs::create_dir_all(&self.output)?;
+        let mut written = Vec::new();
+        let mut exported = HashSet::new();
+
+        // Recursos embebidos.
+        for (path, data) in static_files() {
+            written.push(self.write(&path, data)?);
+            exported.insert(path);
+        }
+
+        // Rutas iniciales.
+        let mut queue: VecDeque<(String, Option<String>)> = VecDeque::new();
+        queue.push_back(("/".to_owned(), None));
+        queue.push_back(("/robots.txt".to_owned(), None));
+        for route in &self.routes {
+            queue.push_back((route.clone(), None));
+        }
+        let entries = sitemap_entries();
+        if !entries.is_empty() {
+            queue.push_back(("/sitemap.xml".to_owned(), None));
+        }
+        for entry in entries {
+            if is_local(entry.loc()) {
+                queue.push_back((entry.loc().to_owned(), None));
+            }
+        }
+
+        let languages = self.languages
+            && matches!(
+                global::SETTINGS.app.lang_negotiation,
+                global::LangNegotiation::Full
+            );
+
+        while let Some((path, lang)) = queue.pop_front() {
+            let Some(path) = local_path(&path) else {
+                trace::debug!("Skipping route \"{path}\" (query parameters are not exportable)");
+                continue;
+            };
+            let target = match &lang {
+                Some(lang) => format!("/{lang}{path}"),
+                None => path.clone(),
+            };
+            if !exported.insert(target.clone()) {
+                continue;
+            }
+            if self.output_file(&target).is_none() {
+                trace::warn!("Skipping route \"{target}\" (outside the export directory)");
+                continue;
+            }
+
+            let uri = match &lang {
+                Some(lang) => format!("{path}?lang={lang}"),
+                None => path.clone(),
+            };
+            let (status, content_type, location, body) = self.fetch(app, to_request, &uri).await;
+
+            if status.is_redirection() {
+                if let Some(location) = location.filter(|l| is_local(l)) {
+                    queue.push_back((location, lang));
+                }
+                continue;
+            }
+            if !status.is_success() {
+                trace::warn!("Skipping route \"{uri}\" ({status})");
+                continue;
+            }
+
+            if !content_type.starts_with("text/html") {
+                written.push(self.write(&target, &body)?);
+                continue;
+            }
+
+            let mut html = String::from_utf8_lossy(&body).into_owned();
+            for link in local_links(&html) {
+                match split_lang(&link) {
+                    Some((link_path, link_lang)) => {
+                        html = html.replace(
+                            &format!("=\"{}\"", link.replace('&', "&amp;")),
+                            &format!("=\"/{link_lang}{link_path}\""),
+                        );
+                        if self.crawl && languages {
+                            queue.push_back((link_path, Some(link_lang)));
+                        }
+                    }
+                    None if self.crawl => queue.push_back((link, None)),
+                    None => {}
+                }
+            }
+            if languages && lang.is_none() {
+                for langid in Locale::supported_langids() {
+                    queue.push_back((path.clone(), Some(langid.to_string())));
+                }
+            }
+            written.push(self.write(&html_file(&target), html.as_bytes())?);
+        }
+
+        // Página de error 404.
+        let request = self.request("/404.html").to_http_request();
+        let body = body::to_bytes(ErrorPage::NotFound(request).error_response().into_body())
+            .await
+            .unwrap_or_default();
+        written.push(self.write("/404.html", &body)?);
+
+        trace::info!(
+            "Exported {} files to {}",
+            written.len(),
+            self.output.display()
+        );
+        Ok(written)
+    }
+
+    // Solicita una ruta al servicio y devuelve el estado, el tipo de contenido, la redirección y el
+    // cuerpo de la respuesta.
+    async fn fetch<S, R>(
+        &self,
+        app: &S,
+        to_request: fn(TestRequest) -> R,
+        uri: &str,
+    ) -> (StatusCode, String, Option<String>, Vec<u8>)
+    where
+        S: Service<R, Response = service::Response<service::BoxBody>, Error = service::Error>,
+    {
+        let response = service::test::call_service(app, to_request(self.request(uri))).await;
+        let status = response.status();
+        let header = |name| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|value: &header::HeaderValue| value.to_str().ok())
+                .map(str::to_owned)
+        };
+        let content_type = header(header::CONTENT_TYPE).unwrap_or_default();
+        let location = header(header::LOCATION);
+        let body = service::test::read_body(response).await.to_vec();
+        (status, content_type, location, body)
+    }
+
+    // Prepara una petición GET a la ruta indicada desde la URL base del sitio, si se ha definido.
+    fn request(&self, uri: &str) -> TestRequest {
+        let request = TestRequest::get().uri(uri);
+        match self
+            .base_url
+            .as_deref()
+            .and_then(|base_url| base_url.trim_end_matches('/').split_once("://"))
+        {
+            Some((scheme, host)) => {
+                request.insert_header((header::FORWARDED, format!("proto={scheme};host={host}")))
+            }
+            None => request,
+        }
+    }
+
+    // Archivo del directorio de exportación para la ruta URL indicada, o `None` si la ruta sale
+    // del directorio (por ejemplo, con segmentos `..`). La ruta raíz devuelve el propio directorio.
+    fn output_file(&self, path: &str) -> Option<PathBuf> {
+        let mut file = self.output.clone();
+        for component in Path::new(path.trim_start_matches('/')).components() {
+            match component {
+                Component::Normal(name) => file.push(name),
+                Component::CurDir => {}
+                _ => return None,
+            }
+        }
+        Some(file)
+    }
+
+    // Guarda el contenido en la ruta URL indicada, relativa al directorio de exportación.
+    fn write(&self, path: &str, data: &[u8]) -> io::Result<PathBuf> {
+        let file = self
+            .output_file(path)
+            .filter(|file| *file != self.output)
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("Route \"{path}\" is outside the export directory"),
+                )
+            })?;
+        if let Some(parent) = file.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&file, data)?;
+        Ok(file)
+    }
+}
+
+// **< Helpers >************************************************************************************
+
+// Indica si el enlace apunta a una ruta local de la aplicación.
+fn is_local(link: &str) -> bool {
+    link.starts_with('/') && !link.starts_with("//")
+}
+
+// Devuelve la ruta de un enlace local sin fragmento, o `None` si incluye parámetros de consulta.
+fn local_path(link: &str) -> Option<String> {
+    let link = link.split('#').next().unwrap_or_default();
+    (!link.contains('?')).then(|| link.to_owned())
+}
+
+// Separa un enlace local de la forma `/ruta?lang=xx` en su ruta y su idioma, si es uno de los
+// idiomas soportados.
+fn split_lang(link: &str) -> Option<(String, String)> {
+    let (path, query) = link.split('#').next()?.split_once('?')?;
+    let lang = query.strip_prefix("lang=")?;
+    Locale::supported_langids()
+        .iter()
+        .any(|langid| langid.to_string() == lang)
+        .then(|| (path.to_owned(), lang.to_owned()))
+}
+
+// Extrae los enlaces locales de los atributos `href` y `src` de un documento HTML.
+fn local_links(html: &str) -> Vec<String> {
+    let mut links = Vec::new();
+    for attr in [" href=\"", " src=\""] {
+        for (start, _) in html.match_indices(attr) {
+            let value = &html[start + attr.len()..];
+            if let Some(end) = value.find('"') {
+                let link = value[..end].replace("&amp;", "&");
+                if is_local(&link) {
+                    links.push(link);
+                }
+            }
+        }
+    }
+    links
+}
+
+// Nombre del archivo para guardar una página HTML.
+fn html_file(path: &str) -> String {
+    let name = Path::new(path)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or_default();
+    if path.ends_with('/') {
+        format!("{path}index.html")
+    } else if name.contains('.') {
+        path.to_owned()
+    } else {
+        format!("{path}/index.html")
+    }
+}
@@ -6,6 +6,9 @@ pub use html::Html;
 mod block;
 pub use block::Block;
 
+mod deferred;
+pub use deferred::Deferred;
+
 mod intro;
 pub use intro::{Intro, IntroOpening};
 
@@ -0,0 +1,94 @@
+use crate::prelude::*;
+
+/// Componente que aplaza el renderizado de contenidos lentos.
+///
+/// Cuando la página se envía por fragmentos ([`Page::stream()`]), `Deferred` renderiza de inmediato
+/// un marcador de posición con sus componentes *placeholder* y encola sus componentes hijo para
+/// renderizarlos al final del `<body>`, en la misma respuesta. Un pequeño *script* sustituye
+/// entonces el marcador por el contenido final, igual que haría [`ChildOp::ReplaceById`] con el
+/// componente del mismo identificador.
+///
+/// En un renderizado completo ([`Page::render()`]) los componentes hijo se renderizan directamente
+/// en su posición, sin marcador ni *script*.
+///
+/// # Ejemplo
+///
+/// ```rust
+/// # use pagetop::prelude::*;
+/// let deferred = Deferred::new()
+///     .with_id("latest-news")
+///     .with_placeholder(Html::with(|_| html! { p { "Cargando..." } }))
+///     .with_child(Html::with(|_| html! { ul { li { "Noticia lenta" } } }));
+/// ```
+#[derive(AutoDefault, Clone, Debug, Getters)]
+pub struct Deferred {
+    #[getters(skip)]
+    id: AttrId,
+    /// Devuelve los componentes que se muestran mientras llega el contenido final.
+    placeholder: Children,
+    /// Devuelve la lista de componentes hijo con el contenido aplazado.
+    children: Children,
+    #[getters(skip)]
+    resolved: bool,
+}
+
+impl Component for Deferred {
+    fn new() -> Self {
+        Self::default()
+    }
+
+    fn id(&self) -> Option<String> {
+        self.id.get()
+    }
+
+    fn prepare(&self, cx: &mut Context) -> Result<Markup, ComponentError> {
+        let id = cx.required_id::<Self>(self.id(), 1);
+
+        if cx.is_streaming() && !self.resolved {
+            let mut resolved = self.clone().with_id(&id);
+            resolved.resolved = true;
+            cx.defer(id.clone(), Child::with(resolved));
+
+            return Ok(html! {
+                div id=(id) class="deferred deferred--pending" aria-busy="true" {
+                    (self.placeholder().render(cx))
+                }
+            });
+        }
+
+        Ok(html! {
+            div id=(id) class="deferred" {
+                (self.children().render(cx))
+            }
+        })
+    }
+}
+
+impl Deferred {
+    // **< Deferred BUILDER >***********************************************************************
+
+    /// Establece el identificador único (`id`) del componente.
+    ///
+    /// Es el identificador del marcador que se sustituirá por el contenido final.
+    #[builder_fn]
+    pub fn with_id(mut self, id: impl AsRef<str>) -> Self {
+        self.id.alter_id(id);
+        self
+    }
+
+    /// Añade un componente al marcador de posición o modifica su lista con una operación
+    /// [`ChildOp`].
+    #[builder_fn]
+    pub fn with_placeholder(mut self, op: impl Into<ChildOp>) -> Self {
+        self.placeholder.alter_child(op.into());
+        self
+    }
+
+    /// Añade un nuevo componente al contenido aplazado o modifica la lista de componentes
+    /// (`children`) con una operación [`ChildOp`].
+    #[builder_fn]
+    pub fn with_child(mut self, op: impl Into<ChildOp>) -> Self {
+        self.children.alter_child(op.into());
+        self
+    }
+}
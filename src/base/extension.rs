@@ -2,3 +2,6 @@
 
 mod welcome;
 pub use welcome::Welcome;
+
+mod routes;
+pub use routes::RouteInventory;
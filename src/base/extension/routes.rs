@@ -0,0 +1,74 @@
+use crate::prelude::*;
+
+/// Página de desarrollo con el inventario de rutas del servicio web.
+///
+/// Esta extensión se instala si el ajuste de configuración [`global::Dev::routes_page`] es `true`
+/// y el modo de ejecución no es *"production"*. Muestra en `/dev/routes` el método, el patrón, el
+/// nombre y la extensión de cada ruta configurada, además de los archivos estáticos publicados y
+/// las extensiones con rutas fuera del inventario. Ver [`service::routes()`] para obtener el mismo
+/// inventario desde el código.
+pub struct RouteInventory;
+
+impl Extension for RouteInventory {
+    fn name(&self) -> L10n {
+        L10n::l("routes_extension_name")
+    }
+
+    fn description(&self) -> L10n {
+        L10n::l("routes_extension_description")
+    }
+
+    fn routes(&self) -> Vec<service::Route> {
+        vec![service::Route::get("dev.routes", "/dev/routes").to(route_inventory)]
+    }
+}
+
+async fn route_inventory(request: HttpRequest) -> ResultPage<Markup, ErrorPage> {
+    Page::new(request)
+        .with_title(L10n::l("routes_title"))
+        .with_child(Html::with(|cx| {
+            html! {
+                table class="route-inventory" {
+                    thead {
+                        tr {
+                            th { (L10n::l("routes_method").using(cx)) }
+                            th { (L10n::l("routes_pattern").using(cx)) }
+                            th { (L10n::l("routes_name").using(cx)) }
+                            th { (L10n::l("routes_owner").using(cx)) }
+                        }
+                    }
+                    tbody {
+                        @for route in service::routes() {
+                            tr {
+                                td { (route.method().as_str()) }
+                                td {
+                                    code { (route.pattern()) }
+                                    @match route.kind() {
+                                        service::RouteKind::Handler => {}
+                                        service::RouteKind::EmbeddedFiles => {
+                                            " " (L10n::l("routes_embedded_files").using(cx))
+                                        }
+                                        service::RouteKind::FileSystem => {
+                                            " " (L10n::l("routes_file_system").using(cx))
+                                        }
+                                    }
+                                }
+                                td { (route.name().unwrap_or_default()) }
+                                td { (route.owner()) }
+                            }
+                        }
+                    }
+                }
+                @let unlisted = service::unlisted_routes();
+                @if !unlisted.is_empty() {
+                    p { (L10n::l("routes_unlisted").using(cx)) ":" }
+                    ul class="route-inventory-unlisted" {
+                        @for owner in unlisted {
+                            li { (owner) }
+                        }
+                    }
+                }
+            }
+        }))
+        .render()
+}
@@ -20,8 +20,8 @@ impl Extension for Welcome {
         L10n::l("welcome_extension_description")
     }
 
-    fn configure_service(&self, scfg: &mut service::web::ServiceConfig) {
-        scfg.route("/", service::web::get().to(home));
+    fn routes(&self) -> Vec<service::Route> {
+        vec![service::Route::get("welcome", "/").to(home)]
     }
 }
 
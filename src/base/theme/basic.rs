@@ -11,6 +11,10 @@ impl Extension for Basic {
 }
 
 impl Theme for Basic {
+    fn stream_by_regions(&self) -> bool {
+        true
+    }
+
     fn before_render_page_body(&self, page: &mut Page) {
         page.alter_assets(AssetsOp::AddStyleSheet(
             StyleSheet::from("/css/normalize.css")
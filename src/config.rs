@@ -1,7 +1,8 @@
 //! Carga las opciones de configuración de la aplicación.
 //!
-//! Estos ajustes se obtienen de archivos [TOML](https://toml.io) como pares `clave = valor` que se
-//! mapean a estructuras **fuertemente tipadas** y valores predefinidos.
+//! Estos ajustes se obtienen de archivos [TOML](https://toml.io), [YAML](https://yaml.org) o
+//! [JSON](https://www.json.org) como pares `clave = valor` que se mapean a estructuras
+//! **fuertemente tipadas** y valores predefinidos.
 //!
 //! Siguiendo la metodología [Twelve-Factor App](https://12factor.net/config), PageTop separa el
 //! **código** de la **configuración**, lo que permite tener configuraciones diferentes para cada
@@ -13,7 +14,7 @@
 //! Si tu aplicación necesita archivos de configuración, crea un directorio `config` en la raíz del
 //! proyecto, al mismo nivel que el archivo *Cargo.toml* o que el binario de la aplicación.
 //!
-//! PageTop carga en este orden, y siempre de forma opcional, los siguientes archivos TOML:
+//! PageTop carga en este orden, y siempre de forma opcional, los siguientes archivos:
 //!
 //! 1. **config/common.toml**, para ajustes comunes a todos los entornos. Este enfoque simplifica el
 //!    mantenimiento al centralizar los valores de configuración comunes.
@@ -42,6 +43,37 @@
 //! Los archivos se combinan en el orden anterior, cada archivo sobrescribe a los anteriores en caso
 //! de conflicto.
 //!
+//! Cada archivo puede escribirse en TOML (`.toml`), YAML (`.yaml` o `.yml`) o JSON (`.json`), por
+//! ejemplo *config/common.yaml* o *config/production.json*, con el mismo orden de carga. Pero cada
+//! uno debe existir en un único formato: si hay, por ejemplo, *config/local.toml* y
+//! *config/local.yaml*, no se carga ninguno de los dos y la aplicación no arranca (ver
+//! [`issues()`]).
+//!
+//!
+//! # Variables de entorno y línea de comandos
+//!
+//! Después de los archivos se aplican, en este orden, los ajustes dados en:
+//!
+//! 5. **Variables de entorno** con el prefijo `PAGETOP__` y `__` como separador de secciones. Por
+//!    ejemplo, `PAGETOP__SERVER__BIND_PORT=8088` equivale a `bind_port = 8088` en la sección
+//!    `[server]`. Así se puede configurar un contenedor sin incluir archivos en la imagen.
+//!
+//!    * Si el nombre termina en `_FILE`, el valor es la ruta de un archivo cuyo contenido (sin el
+//!      salto de línea final) se asigna a la clave sin ese sufijo. Por ejemplo,
+//!      `PAGETOP__DATABASE__PASSWORD_FILE=/run/secrets/db` asigna `database.password`. Es la forma
+//!      habitual de pasar secretos a los contenedores. Por eso los nombres de los ajustes no
+//!      terminan en `_file` (p. ej. [`jobs.store_path`](crate::global::Jobs::store_path)), que no
+//!      se podrían asignar con variables de entorno.
+//!
+//! 6. **Argumentos `--set clave=valor`** del programa, como `--set server.bind_port=8088`, que se
+//!    pueden repetir y sobrescriben a todos los anteriores. Sólo se leen si la aplicación se
+//!    prepara para la [línea de comandos](crate::app::cli) con
+//!    [`Application::prepare_cli()`](crate::app::Application::prepare_cli), y entonces se admiten
+//!    con cualquier subcomando.
+//!
+//! Si no se puede leer el archivo de una variable `_FILE`, o algún argumento `--set` no es válido,
+//! el error se registra igual que los valores no válidos (ver [`issues()`]).
+//!
 //!
 //! # Cómo añadir opciones de configuración a tu código
 //!
@@ -86,12 +118,32 @@
 //! Se recomienda proporcionar siempre valores por defecto o usar `Option<T>` para los ajustes
 //! opcionales.
 //!
-//! Si la configuración no se inicializa correctamente, la aplicación lanzará *panic* y detendrá la
+//! Si algún valor no es válido, el ajuste toma su valor por defecto y el problema se registra para
+//! validar la configuración al preparar la [aplicación](crate::app::Application), que no arranca y
+//! devuelve un informe de **todos** los errores encontrados (ver [`issues()`]). Los archivos que no
+//! se pueden interpretar se descartan y se informa igualmente del error. Sólo si un ajuste
+//! obligatorio sin valor por defecto no es válido, la aplicación lanzará *panic* y detendrá la
 //! ejecución.
 //!
+//! Los ajustes de las extensiones se comprueban al prepararse la aplicación si se cargan en
+//! [`Extension::settings()`](crate::core::extension::Extension::settings). Los errores de los que se
+//! carguen más tarde, la primera vez que se usan, se informan en la traza de eventos.
+//!
 //! Las estructuras de configuración son de **sólo lectura** durante la ejecución.
 //!
 //!
+//! # Ajustes dinámicos
+//!
+//! Las claves declaradas explícitamente con [`DynamicSetting`] se pueden cambiar sin reiniciar la
+//! aplicación. Al [recargar la configuración](reload), por la señal `SIGHUP`, el subcomando
+//! `reload` de la [línea de comandos](crate::app::cli) o porque han cambiado los archivos (si está
+//! activado [`app.watch_config`](crate::global::App::watch_config)), se actualiza su valor y se
+//! notifica a las extensiones suscritas con [`DynamicSetting::on_change()`]. Es el caso, por
+//! ejemplo, de `log.tracing`.
+//!
+//! El resto de ajustes no cambian hasta reiniciar la aplicación.
+//!
+//!
 //! # Usando tus opciones de configuración
 //!
 //! ```rust,ignore
@@ -113,41 +165,300 @@
 use crate::util;
 
 use config::builder::DefaultState;
-use config::{Config, ConfigBuilder, File};
+use config::{Config, ConfigBuilder, File, FileFormat, FileSourceFile, Value};
+
+use parking_lot::RwLock;
 
 use std::env;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::LazyLock;
 
+mod validation;
+#[doc(hidden)]
+pub use validation::load_settings;
+pub(crate) use validation::report_late_issues;
+pub use validation::{issues, ConfigIssue, IssueLevel};
+
+mod dynamic;
+pub use dynamic::{reload, DynamicSetting};
+pub(crate) use dynamic::{reload_and_report, watch_config_dir};
+
 // Nombre del directorio de configuración por defecto.
 const DEFAULT_CONFIG_DIR: &str = "config";
 
 // Modo de ejecución por defecto.
 const DEFAULT_RUN_MODE: &str = "default";
 
+// Extensiones y formatos admitidos para los archivos de configuración.
+const CONFIG_FORMATS: [(&str, FileFormat); 4] = [
+    ("toml", FileFormat::Toml),
+    ("yaml", FileFormat::Yaml),
+    ("yml", FileFormat::Yaml),
+    ("json", FileFormat::Json),
+];
+
+// Prefijo y separador de las variables de entorno con ajustes de configuración.
+const ENV_PREFIX: &str = "PAGETOP__";
+const ENV_SEPARATOR: &str = "__";
+
+// Sufijo de las variables de entorno que apuntan a un archivo con el valor del ajuste.
+const ENV_FILE_SUFFIX: &str = "_FILE";
+
+// Palabras que identifican por su nombre los ajustes que pueden contener secretos. Se comparan con
+// las palabras completas del nombre separadas por `_`, así `api_key` es secreto pero `hotkeys` no.
+const SECRET_WORDS: [&str; 4] = ["password", "secret", "token", "key"];
+
+// Indica si se leen los argumentos `--set` del programa (ver `Application::prepare_cli()`).
+static ARGS_ENABLED: AtomicBool = AtomicBool::new(false);
+
+// Indica si la configuración cargada incluye los argumentos `--set` del programa.
+static ARGS_APPLIED: AtomicBool = AtomicBool::new(false);
+
 /// Valores originales de los archivos de configuración como pares `clave = valor`.
 pub static CONFIG_VALUES: LazyLock<ConfigBuilder<DefaultState>> = LazyLock::new(|| {
-    // CONFIG_DIR (si existe) o DEFAULT_CONFIG_DIR. Si no se puede resolver, se usa tal cual.
+    let (builder, errors) = load_values();
+    for error in errors {
+        validation::record_error(None, error);
+    }
+    builder
+});
+
+// Devuelve el directorio de configuración: CONFIG_DIR (si existe) o DEFAULT_CONFIG_DIR. Si no se
+// puede resolver, se usa tal cual.
+pub(crate) fn config_dir() -> PathBuf {
     let dir = env::var_os("CONFIG_DIR").unwrap_or_else(|| DEFAULT_CONFIG_DIR.into());
-    let config_dir = util::resolve_absolute_dir(&dir).unwrap_or_else(|_| PathBuf::from(&dir));
+    util::resolve_absolute_dir(&dir).unwrap_or_else(|_| PathBuf::from(&dir))
+}
+
+// Lee los argumentos `--set` del programa en las siguientes cargas de la configuración.
+pub(crate) fn enable_args_overrides() {
+    ARGS_ENABLED.store(true, Ordering::SeqCst);
+}
+
+// Indica si la configuración cargada incluye los argumentos `--set` del programa.
+pub(crate) fn args_overrides_applied() -> bool {
+    ARGS_APPLIED.load(Ordering::SeqCst)
+}
+
+// Combina los archivos de configuración, las variables de entorno y los argumentos `--set`, si se
+// leen. Devuelve también los errores de las capas y los ajustes que no se han podido cargar.
+fn load_values() -> (ConfigBuilder<DefaultState>, Vec<String>) {
+    let config_dir = config_dir();
 
     // Modo de ejecución según la variable de entorno PAGETOP_RUN_MODE. Si no está definida, se usa
     // por defecto DEFAULT_RUN_MODE (p. ej. PAGETOP_RUN_MODE=production).
     let rm = env::var("PAGETOP_RUN_MODE").unwrap_or_else(|_| DEFAULT_RUN_MODE.into());
 
-    Config::builder()
+    let mut builder = Config::builder();
+    let mut errors = Vec::new();
+    for layer in [
         // 1. Configuración común para todos los entornos (common.toml).
-        .add_source(File::from(config_dir.join("common.toml")).required(false))
+        "common".to_owned(),
         // 2. Configuración específica del entorno (p. ej. default.toml o production.toml).
-        .add_source(File::from(config_dir.join(format!("{rm}.toml"))).required(false))
+        rm.clone(),
         // 3. Configuración local reservada para cada entorno (p. ej. local.default.toml).
-        .add_source(File::from(config_dir.join(format!("local.{rm}.toml"))).required(false))
+        format!("local.{rm}"),
         // 4. Configuración local común (local.toml).
-        .add_source(File::from(config_dir.join("local.toml")).required(false))
-        // Guarda el modo de ejecución explícitamente.
+        "local".to_owned(),
+    ] {
+        match layer_file(&config_dir, &layer) {
+            // Un archivo que no se puede interpretar se descarta, conservando las demás capas.
+            Ok(Some(file)) => match Config::builder().add_source(file.clone()).build() {
+                Ok(_) => builder = builder.add_source(file),
+                Err(e) => errors.push(e.to_string()),
+            },
+            Ok(None) => {}
+            Err(error) => errors.push(error),
+        }
+    }
+    // Guarda el modo de ejecución explícitamente.
+    let mut builder = builder
         .set_override("app.run_mode", rm)
-        .expect("Failed to set application run mode")
-});
+        .expect("Failed to set application run mode");
+
+    // 5. Variables de entorno (p. ej. PAGETOP__SERVER__BIND_PORT=8088).
+    for (key, value) in env_overrides(&mut errors) {
+        match builder.clone().set_override(&key, value) {
+            Ok(overridden) => builder = overridden,
+            Err(e) => errors.push(format!("unable to set `{key}` from environment: {e}")),
+        }
+    }
+
+    // 6. Argumentos de la línea de comandos (p. ej. --set server.bind_port=8088).
+    if ARGS_ENABLED.load(Ordering::SeqCst) {
+        for (key, value) in args_overrides(env::args().skip(1), &mut errors) {
+            match builder.clone().set_override(&key, value) {
+                Ok(overridden) => builder = overridden,
+                Err(e) => errors.push(format!("unable to set `{key}` from --set: {e}")),
+            }
+        }
+        ARGS_APPLIED.store(true, Ordering::SeqCst);
+    }
+
+    (builder, errors)
+}
+
+// Devuelve el archivo de una capa de configuración en cualquiera de los formatos admitidos. Si la
+// misma capa existe en varios formatos devuelve un error, porque no se sabría cuál debe prevalecer.
+fn layer_file(
+    config_dir: &Path,
+    layer: &str,
+) -> Result<Option<File<FileSourceFile, FileFormat>>, String> {
+    let found: Vec<(PathBuf, FileFormat)> = CONFIG_FORMATS
+        .iter()
+        .map(|(extension, format)| (config_dir.join(format!("{layer}.{extension}")), *format))
+        .filter(|(path, _)| path.is_file())
+        .collect();
+    match found.as_slice() {
+        [] => Ok(None),
+        [(path, format)] => Ok(Some(
+            File::from(path.as_path()).format(*format).required(false),
+        )),
+        _ => {
+            let files: Vec<String> = found
+                .iter()
+                .map(|(path, _)| path.display().to_string())
+                .collect();
+            Err(format!(
+                "ambiguous configuration layer \"{layer}\" found in {}, keep only one",
+                files.join(" and ")
+            ))
+        }
+    }
+}
+
+// Devuelve los ajustes de las variables de entorno con el prefijo `PAGETOP__`, leyendo el valor de
+// los archivos indicados en las variables terminadas en `_FILE`. Añade a `errors` los archivos que
+// no se pueden leer.
+fn env_overrides(errors: &mut Vec<String>) -> Vec<(String, String)> {
+    let mut overrides: Vec<(String, String)> = env::vars()
+        .filter_map(|(name, value)| {
+            let name = name.strip_prefix(ENV_PREFIX)?;
+            match name.strip_suffix(ENV_FILE_SUFFIX) {
+                Some(name) => match std::fs::read_to_string(&value) {
+                    Ok(content) => {
+                        let content = content.trim_end_matches(['\r', '\n']).to_owned();
+                        Some((env_key(name), content))
+                    }
+                    Err(e) => {
+                        errors.push(format!(
+                            "unable to read {ENV_PREFIX}{name}{ENV_FILE_SUFFIX} ({value}): {e}"
+                        ));
+                        None
+                    }
+                },
+                None => Some((env_key(name), value)),
+            }
+        })
+        .collect();
+    // Orden estable para que el resultado no dependa del orden de las variables de entorno.
+    overrides.sort();
+    overrides
+}
+
+/// Indica si un ajuste puede contener un secreto, porque alguna palabra de su nombre lo sugiere o
+/// porque su valor se lee del archivo de una variable de entorno terminada en `_FILE`.
+#[doc(hidden)]
+pub fn is_secret(key: &str) -> bool {
+    let key = key.to_ascii_lowercase();
+    let name = key.rsplit('.').next().unwrap_or_default();
+    name.split('_').any(|word| SECRET_WORDS.contains(&word))
+        || env::vars().any(|(name, _)| {
+            name.strip_prefix(ENV_PREFIX)
+                .and_then(|name| name.strip_suffix(ENV_FILE_SUFFIX))
+                .is_some_and(|name| env_key(name) == key)
+        })
+}
+
+// Convierte el nombre de una variable de entorno sin prefijo en una clave de configuración.
+fn env_key(name: &str) -> String {
+    name.split(ENV_SEPARATOR)
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+// Devuelve los ajustes de los argumentos `--set clave=valor` o `--set=clave=valor`. Añade a
+// `errors` los argumentos que no tienen el formato esperado.
+fn args_overrides(
+    mut args: impl Iterator<Item = String>,
+    errors: &mut Vec<String>,
+) -> Vec<(String, String)> {
+    let mut overrides = Vec::new();
+    while let Some(arg) = args.next() {
+        let setting = match arg.strip_prefix("--set") {
+            Some("") => args.next(),
+            Some(setting) => setting.strip_prefix('=').map(str::to_owned),
+            None => None,
+        };
+        if let Some(setting) = setting {
+            match setting.split_once('=') {
+                Some((key, value)) if !key.trim().is_empty() => {
+                    overrides.push((key.trim().to_owned(), value.to_owned()));
+                }
+                _ => errors.push(format!(
+                    "invalid setting \"{setting}\" in --set, expected key=value"
+                )),
+            }
+        }
+    }
+    overrides
+}
+
+// Valores por defecto declarados con `include_config!` en los ajustes ya cargados.
+static CONFIG_DEFAULTS: LazyLock<RwLock<Vec<(&'static str, Value)>>> =
+    LazyLock::new(|| RwLock::new(Vec::new()));
+
+/// Aplica un valor por defecto a la configuración y lo registra para poder mostrar después los
+/// ajustes efectivos. Lo usa [`include_config!`](crate::include_config).
+#[doc(hidden)]
+pub fn with_default(
+    settings: ConfigBuilder<DefaultState>,
+    key: &'static str,
+    value: impl Into<Value>,
+) -> ConfigBuilder<DefaultState> {
+    let value = value.into();
+    CONFIG_DEFAULTS.write().push((key, value.clone()));
+    settings
+        .set_default(key, value)
+        .expect("Failed to set default config value")
+}
+
+// Devuelve los ajustes efectivos como pares `clave = valor` ordenados por clave, combinando los
+// archivos de configuración con los valores por defecto de los ajustes ya cargados (ver
+// `Extension::settings()`).
+pub(crate) fn effective_values() -> Result<Vec<(String, serde_json::Value)>, config::ConfigError> {
+    let mut settings = CONFIG_VALUES.clone();
+    for (key, value) in CONFIG_DEFAULTS.read().iter() {
+        settings = settings.set_default(*key, value.clone())?;
+    }
+    let tree = settings.build()?.try_deserialize::<serde_json::Value>()?;
+    let mut values = Vec::new();
+    flatten(String::new(), tree, &mut values);
+    values.sort_by(|(a, _), (b, _)| a.cmp(b));
+    Ok(values)
+}
+
+fn flatten(
+    prefix: String,
+    value: serde_json::Value,
+    values: &mut Vec<(String, serde_json::Value)>,
+) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, value) in map {
+                let key = if prefix.is_empty() {
+                    key
+                } else {
+                    format!("{prefix}.{key}")
+                };
+                flatten(key, value, values);
+            }
+        }
+        value => values.push((prefix, value)),
+    }
+}
 
 // **< include_config! >****************************************************************************
 
@@ -212,11 +523,14 @@ pub static CONFIG_VALUES: LazyLock<ConfigBuilder<DefaultState>> = LazyLock::new(
 ///   evitar colisiones con otras librerías.
 ///
 /// * **Solo lectura**. La variable generada es inmutable durante toda la vida del programa. Para
-///   configurar distintos entornos (*dev*, *staging*, *prod*) usa los archivos TOML descritos en la
-///   documentación de [`config`](crate::config).
+///   configurar distintos entornos (*dev*, *staging*, *prod*) usa los archivos de configuración
+///   descritos en la documentación de [`config`](crate::config). Los ajustes que deban cambiar sin
+///   reiniciar se leen con [`DynamicSetting`](crate::config::DynamicSetting).
 ///
-/// * **Errores explícitos**. Si la deserialización falla, la macro lanzará un `panic!` con un
-///   mensaje que indica la estructura problemática, facilitando la depuración.
+/// * **Errores explícitos**. Los valores no válidos se sustituyen por su valor por defecto y se
+///   informa de ellos, con la clave, el archivo y el tipo esperado, al preparar la aplicación (ver
+///   [`config::issues()`](crate::config::issues)). Si un ajuste sin valor por defecto no es válido,
+///   la macro lanzará un `panic!` con el mismo informe.
 ///
 /// # Requisitos
 ///
@@ -245,13 +559,12 @@ macro_rules! include_config {
             std::sync::LazyLock::new(|| {
                 let mut settings = $crate::config::CONFIG_VALUES.clone();
                 $(
-                    settings = settings.set_default($k, $v).unwrap();
+                    settings = $crate::config::with_default(settings, $k, $v);
                 )*
-                settings
-                    .build()
-                    .expect(concat!("Failed to build config for ", stringify!($Settings_Type)))
-                    .try_deserialize::<$Settings_Type>()
-                    .expect(concat!("Error parsing settings for ", stringify!($Settings_Type)))
+                $crate::config::load_settings::<$Settings_Type>(
+                    settings,
+                    stringify!($Settings_Type),
+                )
             });
     };
 }
@@ -0,0 +1,279 @@
+use crate::config::{config_dir, load_values, CONFIG_DEFAULTS, CONFIG_FORMATS, CONFIG_VALUES};
+use crate::trace;
+
+use config::{Config, ConfigError, Source, Value, ValueKind};
+use parking_lot::RwLock;
+use serde::de::DeserializeOwned;
+
+use std::collections::BTreeMap;
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock};
+use std::time::{Duration, SystemTime};
+
+type ChangeHandler = Arc<dyn Fn(&Value) + Send + Sync>;
+
+// Comprueba que el valor de un ajuste dinámico es del tipo esperado.
+type ValueCheck = fn(Value) -> Result<(), ConfigError>;
+
+// Valores vigentes de la configuración, actualizados en cada recarga.
+static CURRENT_VALUES: LazyLock<RwLock<Config>> =
+    LazyLock::new(|| RwLock::new(CONFIG_VALUES.clone().build().unwrap_or_default()));
+
+// Claves declaradas como dinámicas con la función que comprueba su tipo.
+static DYNAMIC_KEYS: LazyLock<RwLock<BTreeMap<&'static str, ValueCheck>>> =
+    LazyLock::new(|| RwLock::new(BTreeMap::new()));
+
+// Funciones suscritas a los cambios de cada clave dinámica.
+static CHANGE_HANDLERS: LazyLock<RwLock<Vec<(&'static str, ChangeHandler)>>> =
+    LazyLock::new(|| RwLock::new(Vec::new()));
+
+// Intervalo para comprobar si han cambiado los archivos de configuración.
+const WATCH_INTERVAL: Duration = Duration::from_secs(2);
+
+// **< DynamicSetting >*****************************************************************************
+
+/// Ajuste de configuración que se puede recargar sin reiniciar la aplicación.
+///
+/// Los ajustes de [`include_config!`](crate::include_config) son de sólo lectura durante toda la
+/// ejecución. Un `DynamicSetting` declara explícitamente una clave como **dinámica**, y su valor se
+/// actualiza cada vez que se recarga la configuración con [`reload()`]. La aplicación recarga la
+/// configuración al recibir la señal `SIGHUP`, con el subcomando `reload` de la
+/// [línea de comandos](crate::app::cli) o, si está activado
+/// [`app.watch_config`](crate::global::App::watch_config), al cambiar los archivos de
+/// configuración.
+///
+/// Si la clave no está en la configuración se usa su valor por defecto declarado con
+/// `include_config!`.
+///
+/// # Ejemplo
+///
+/// ```rust,no_run
+/// # use pagetop::prelude::*;
+/// use pagetop::config::DynamicSetting;
+///
+/// static BANNER: DynamicSetting<String> = DynamicSetting::new("myapp.banner");
+///
+/// pub struct MyApp;
+///
+/// impl Extension for MyApp {
+///     fn initialize(&self) {
+///         BANNER.on_change(|banner| trace::info!("New banner: {banner}"));
+///     }
+/// }
+///
+/// fn banner() -> String {
+///     BANNER.get()
+/// }
+/// ```
+pub struct DynamicSetting<T> {
+    key: &'static str,
+    value_type: PhantomData<fn() -> T>,
+}
+
+impl<T: DeserializeOwned + 'static> DynamicSetting<T> {
+    /// Declara la clave indicada (p. ej. `"log.tracing"`) como ajuste dinámico.
+    pub const fn new(key: &'static str) -> Self {
+        DynamicSetting {
+            key,
+            value_type: PhantomData,
+        }
+    }
+
+    /// Devuelve la clave del ajuste.
+    pub fn key(&self) -> &'static str {
+        self.key
+    }
+
+    /// Devuelve el valor vigente del ajuste.
+    ///
+    /// # Panics
+    ///
+    /// Si la clave no tiene valor ni valor por defecto, o si el valor cargado al arrancar no es del
+    /// tipo esperado. Las recargas con valores no válidos se descartan.
+    pub fn get(&self) -> T {
+        self.register();
+        let value = current_value(&CURRENT_VALUES.read(), self.key)
+            .unwrap_or_else(|| panic!("Dynamic setting \"{}\" has no value", self.key));
+        value
+            .try_deserialize()
+            .unwrap_or_else(|e| panic!("Invalid dynamic setting \"{}\": {e}", self.key))
+    }
+
+    /// Ejecuta la función indicada con el nuevo valor cada vez que cambia el ajuste en una recarga.
+    pub fn on_change(&self, handler: impl Fn(&T) + Send + Sync + 'static) {
+        self.register();
+        let key = self.key;
+        CHANGE_HANDLERS.write().push((
+            key,
+            Arc::new(
+                move |value: &Value| match value.clone().try_deserialize::<T>() {
+                    Ok(value) => handler(&value),
+                    Err(e) => trace::warn!("Invalid dynamic setting \"{key}\": {e}"),
+                },
+            ),
+        ));
+    }
+
+    fn register(&self) {
+        if !DYNAMIC_KEYS.read().contains_key(self.key) {
+            DYNAMIC_KEYS
+                .write()
+                .insert(self.key, |value| value.try_deserialize::<T>().map(|_| ()));
+        }
+    }
+}
+
+// Valor de una clave en la configuración o, si no está, su valor por defecto.
+fn current_value(config: &Config, key: &str) -> Option<Value> {
+    config.get::<Value>(key).ok().or_else(|| {
+        CONFIG_DEFAULTS
+            .read()
+            .iter()
+            .find(|(default, _)| default.eq_ignore_ascii_case(key))
+            .map(|(_, value)| value.clone())
+    })
+}
+
+// **< reload() >***********************************************************************************
+
+/// Recarga la configuración y actualiza los ajustes dinámicos.
+///
+/// Vuelve a leer los archivos, las variables de entorno y los argumentos `--set` con las mismas
+/// reglas de carga que al arrancar. Devuelve las claves dinámicas que han cambiado, después de
+/// notificar el nuevo valor a las funciones suscritas con
+/// [`DynamicSetting::on_change()`](DynamicSetting::on_change).
+///
+/// Los ajustes que no son dinámicos no cambian, y si se han modificado se avisa de que requieren
+/// reiniciar la aplicación. Si los archivos no son válidos, o algún ajuste dinámico no tiene el
+/// tipo esperado, devuelve el error y se mantiene la configuración vigente.
+pub fn reload() -> Result<Vec<&'static str>, ConfigError> {
+    let (builder, errors) = load_values();
+    if let Some(error) = errors.into_iter().next() {
+        return Err(ConfigError::Message(error));
+    }
+    let config = builder.build()?;
+
+    let dynamic_keys = DYNAMIC_KEYS.read().clone();
+    let mut changed = Vec::new();
+    {
+        let current = CURRENT_VALUES.read();
+        for (key, check) in &dynamic_keys {
+            let new_value = current_value(&config, key);
+            if new_value.as_ref().map(|value| &value.kind)
+                != current_value(&current, key)
+                    .as_ref()
+                    .map(|value| &value.kind)
+            {
+                if let Some(value) = &new_value {
+                    check(value.clone()).map_err(|e| {
+                        ConfigError::Message(format!("invalid value for key `{key}`: {e}"))
+                    })?;
+                }
+                changed.push((*key, new_value));
+            }
+        }
+
+        let old_values = flatten(&current);
+        for (key, value) in flatten(&config) {
+            if old_values.get(&key) != Some(&value)
+                && !dynamic_keys
+                    .keys()
+                    .any(|dynamic| dynamic.eq_ignore_ascii_case(&key))
+            {
+                trace::warn!("Setting \"{key}\" has changed but requires a restart");
+            }
+        }
+    }
+    *CURRENT_VALUES.write() = config;
+
+    // Las funciones suscritas se ejecutan sin bloquear los valores vigentes.
+    for (key, value) in &changed {
+        let handlers: Vec<ChangeHandler> = CHANGE_HANDLERS
+            .read()
+            .iter()
+            .filter(|(handler_key, _)| handler_key == key)
+            .map(|(_, handler)| Arc::clone(handler))
+            .collect();
+        if let Some(value) = value {
+            for handler in handlers {
+                handler(value);
+            }
+        }
+    }
+
+    Ok(changed.into_iter().map(|(key, _)| key).collect())
+}
+
+// Recarga la configuración e informa del resultado en las trazas.
+pub(crate) fn reload_and_report(trigger: &str) {
+    match reload() {
+        Ok(changed) if changed.is_empty() => {
+            trace::info!("Configuration reloaded ({trigger}), no dynamic settings changed");
+        }
+        Ok(changed) => trace::info!(
+            "Configuration reloaded ({trigger}), changed: {}",
+            changed.join(", ")
+        ),
+        Err(e) => trace::error!("Configuration not reloaded ({trigger}): {e}"),
+    }
+}
+
+// Claves con sus valores, sin tablas anidadas.
+fn flatten(config: &Config) -> BTreeMap<String, ValueKind> {
+    fn collect(prefix: String, value: Value, values: &mut BTreeMap<String, ValueKind>) {
+        match value.kind {
+            ValueKind::Table(table) => {
+                for (key, value) in table {
+                    collect(format!("{prefix}.{key}"), value, values);
+                }
+            }
+            kind => {
+                values.insert(prefix, kind);
+            }
+        }
+    }
+    let mut values = BTreeMap::new();
+    for (key, value) in config.collect().unwrap_or_default() {
+        collect(key, value, &mut values);
+    }
+    values
+}
+
+// **< Vigilancia de archivos >*********************************************************************
+
+// Vigila los archivos del directorio de configuración y recarga la configuración cuando cambian.
+pub(crate) fn watch_config_dir() {
+    let dir = config_dir();
+    trace::info!("Watching configuration directory {}", dir.display());
+    std::thread::spawn(move || {
+        let mut snapshot = config_files(&dir);
+        loop {
+            std::thread::sleep(WATCH_INTERVAL);
+            let files = config_files(&dir);
+            if files != snapshot {
+                snapshot = files;
+                reload_and_report("files changed");
+            }
+        }
+    });
+}
+
+// Archivos de configuración del directorio con su fecha de modificación.
+fn config_files(dir: &Path) -> BTreeMap<PathBuf, Option<SystemTime>> {
+    let mut files = BTreeMap::new();
+    if let Ok(entries) = std::fs::read_dir(dir) {
+        for path in entries.flatten().map(|entry| entry.path()) {
+            let supported = path.extension().is_some_and(|extension| {
+                CONFIG_FORMATS
+                    .iter()
+                    .any(|(supported, _)| extension.eq_ignore_ascii_case(supported))
+            });
+            if supported {
+                let modified = path.metadata().and_then(|meta| meta.modified()).ok();
+                files.insert(path, modified);
+            }
+        }
+    }
+    files
+}
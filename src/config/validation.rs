@@ -0,0 +1,298 @@
+use crate::config::{CONFIG_DEFAULTS, CONFIG_VALUES};
+use crate::trace;
+
+use config::builder::DefaultState;
+use config::{ConfigBuilder, ConfigError, Source, Value, ValueKind};
+use parking_lot::RwLock;
+use serde::de::DeserializeOwned;
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::LazyLock;
+
+// Errores encontrados al cargar los ajustes de configuración.
+static CONFIG_ERRORS: LazyLock<RwLock<Vec<ConfigIssue>>> =
+    LazyLock::new(|| RwLock::new(Vec::new()));
+
+// Indica si ya se ha comprobado la configuración al preparar la aplicación. Los errores de los
+// ajustes que se carguen después se informan en la traza de eventos.
+static CHECKED: AtomicBool = AtomicBool::new(false);
+
+// Distancia máxima de edición para considerar que una clave desconocida es una errata.
+const MAX_TYPO_DISTANCE: usize = 2;
+
+// **< ConfigIssue >********************************************************************************
+
+/// Gravedad de un problema de configuración.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum IssueLevel {
+    /// Valor no válido. El ajuste toma su valor por defecto y la aplicación no arranca.
+    Error,
+    /// Posible errata, como una clave desconocida parecida a otra conocida.
+    Warning,
+}
+
+/// Problema detectado al validar la configuración.
+///
+/// Indica la clave afectada, el archivo o variable de donde procede el valor, si se conoce, y una
+/// descripción con el tipo esperado o los valores permitidos. Ver [`issues()`].
+#[rustfmt::skip]
+#[derive(Clone, Debug)]
+pub struct ConfigIssue {
+    level  : IssueLevel,
+    key    : Option<String>,
+    origin : Option<String>,
+    message: String,
+}
+
+impl ConfigIssue {
+    /// Devuelve la gravedad del problema.
+    pub fn level(&self) -> IssueLevel {
+        self.level
+    }
+
+    /// Devuelve la clave afectada (p. ej. `"server.bind_port"`), si se conoce.
+    pub fn key(&self) -> Option<&str> {
+        self.key.as_deref()
+    }
+
+    /// Devuelve el archivo de configuración del que procede el valor, si se conoce.
+    pub fn origin(&self) -> Option<&str> {
+        self.origin.as_deref()
+    }
+
+    /// Devuelve la descripción del problema.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for ConfigIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(key) = &self.key {
+            write!(f, "{key}")?;
+            if let Some(origin) = &self.origin {
+                write!(f, " ({origin})")?;
+            }
+            f.write_str(": ")?;
+        }
+        f.write_str(&self.message)
+    }
+}
+
+// **< issues() >***********************************************************************************
+
+/// Devuelve los problemas detectados en los ajustes de configuración cargados.
+///
+/// Incluye los **errores** de los valores que no se han podido interpretar, todos a la vez y no
+/// sólo el primero, y los **avisos** de las claves desconocidas que se parecen a una clave conocida
+/// de la misma sección, probablemente por una errata (p. ej. `server.bind_prot`).
+///
+/// [`Application`](crate::app::Application) comprueba la configuración al prepararse y no arranca
+/// si hay errores, devolviendo un informe legible
+/// ([`PrepareError`](crate::app::PrepareError)).
+pub fn issues() -> Vec<ConfigIssue> {
+    let mut issues = CONFIG_ERRORS.read().clone();
+    issues.extend(unknown_keys());
+    issues
+}
+
+// Informa en la traza de eventos de los errores de los ajustes que se carguen a partir de ahora,
+// porque la aplicación ya ha comprobado la configuración.
+pub(crate) fn report_late_issues() {
+    CHECKED.store(true, Ordering::SeqCst);
+}
+
+// Registra errores de configuración, informando de ellos si ya se ha comprobado la configuración.
+fn record_issues(issues: Vec<ConfigIssue>) {
+    if CHECKED.load(Ordering::SeqCst) {
+        for issue in &issues {
+            trace::error!("Invalid setting loaded after startup, {issue}");
+        }
+    }
+    CONFIG_ERRORS.write().extend(issues);
+}
+
+// Registra un error de configuración, opcionalmente asociado a una clave.
+pub(super) fn record_error(key: Option<&str>, message: String) {
+    record_issues(vec![ConfigIssue {
+        level: IssueLevel::Error,
+        key: key.map(str::to_owned),
+        origin: key.and_then(origin_of),
+        message,
+    }]);
+}
+
+// Carga los ajustes del tipo indicado. Si algún valor no es válido registra el error y lo sustituye
+// por su valor por defecto para seguir comprobando los demás. Sólo falla si no se puede obtener un
+// valor válido, por ejemplo cuando un ajuste obligatorio no tiene valor por defecto o no se pueden
+// combinar los valores cargados.
+#[doc(hidden)]
+pub fn load_settings<T: DeserializeOwned>(
+    settings: ConfigBuilder<DefaultState>,
+    type_name: &str,
+) -> T {
+    let mut settings = settings;
+    let mut issues = Vec::new();
+    let mut replaced: Vec<String> = Vec::new();
+    loop {
+        // Los archivos que no se pueden interpretar ya se descartan al cargarlos (ver
+        // `load_values()`), así que no se descarta aquí ningún valor válido.
+        let config = match settings.clone().build() {
+            Ok(config) => config,
+            Err(e) => {
+                issues.push(issue_from(e));
+                panic!("{}", failure_report(type_name, &issues));
+            }
+        };
+        match config.try_deserialize::<T>() {
+            Ok(value) => {
+                record_issues(issues);
+                return value;
+            }
+            Err(e) => {
+                let key = error_key(&e).map(setting_key);
+                issues.push(issue_from(e));
+                match key.filter(|key| !replaced.contains(key)) {
+                    Some(key) => {
+                        settings = settings
+                            .set_override(&key, default_value(&key))
+                            .expect("Failed to reset invalid setting");
+                        replaced.push(key);
+                    }
+                    None => panic!("{}", failure_report(type_name, &issues)),
+                }
+            }
+        }
+    }
+}
+
+// Valor por defecto de un ajuste, o un valor nulo si no tiene (ajustes opcionales).
+fn default_value(key: &str) -> Value {
+    CONFIG_DEFAULTS
+        .read()
+        .iter()
+        .find(|(default, _)| default.eq_ignore_ascii_case(key))
+        .map(|(_, value)| value.clone())
+        .unwrap_or_else(|| Value::new(None, ValueKind::Nil))
+}
+
+fn error_key(error: &ConfigError) -> Option<&str> {
+    match error {
+        ConfigError::Type { key, .. } | ConfigError::At { key, .. } => key.as_deref(),
+        _ => None,
+    }
+}
+
+fn error_origin(error: &ConfigError) -> Option<&str> {
+    match error {
+        ConfigError::Type { origin, .. } | ConfigError::At { origin, .. } => origin.as_deref(),
+        _ => None,
+    }
+}
+
+// Clave del ajuste sin los índices de las listas (p. ej. `app.list` para `app.list[1]`).
+fn setting_key(key: &str) -> String {
+    key.split('[').next().unwrap_or(key).to_owned()
+}
+
+fn issue_from(error: ConfigError) -> ConfigIssue {
+    let key = error_key(&error).map(str::to_owned);
+    let origin = error_origin(&error)
+        .map(str::to_owned)
+        .or_else(|| key.as_deref().and_then(origin_of));
+    // El mensaje ya incluye la clave y el archivo, se quitan para no repetirlos.
+    let mut message = error.to_string();
+    if let Some(pos) = message.find(" for key `") {
+        message.truncate(pos);
+    }
+    ConfigIssue {
+        level: IssueLevel::Error,
+        key,
+        origin,
+        message,
+    }
+}
+
+fn failure_report(type_name: &str, issues: &[ConfigIssue]) -> String {
+    let mut report = format!("Invalid configuration for {type_name}:");
+    for issue in issues {
+        report.push_str(&format!("\n  - {issue}"));
+    }
+    report
+}
+
+// **< Claves desconocidas >************************************************************************
+
+// Claves de los archivos de configuración y de las variables de entorno, con su origen.
+fn configured_keys() -> BTreeMap<String, Option<String>> {
+    let mut keys = BTreeMap::new();
+    if let Ok(values) = CONFIG_VALUES
+        .clone()
+        .build()
+        .and_then(|config| config.collect())
+    {
+        for (key, value) in values {
+            collect_keys(key, value, &mut keys);
+        }
+    }
+    keys
+}
+
+fn collect_keys(prefix: String, value: Value, keys: &mut BTreeMap<String, Option<String>>) {
+    let origin = value.origin().map(str::to_owned);
+    match value.kind {
+        ValueKind::Table(table) => {
+            for (key, value) in table {
+                collect_keys(format!("{prefix}.{key}"), value, keys);
+            }
+        }
+        _ => {
+            keys.insert(prefix, origin);
+        }
+    }
+}
+
+fn origin_of(key: &str) -> Option<String> {
+    let key = setting_key(key);
+    configured_keys().remove(&key).flatten()
+}
+
+// Avisos para las claves desconocidas que se parecen a alguna clave conocida.
+fn unknown_keys() -> Vec<ConfigIssue> {
+    let known: Vec<String> = CONFIG_DEFAULTS
+        .read()
+        .iter()
+        .map(|(key, _)| key.to_ascii_lowercase())
+        .collect();
+    let mut issues = Vec::new();
+    for (key, origin) in configured_keys() {
+        let lowercase = key.to_ascii_lowercase();
+        if known.contains(&lowercase) || lowercase == "app.run_mode" {
+            continue;
+        }
+        let Some((section, name)) = lowercase.rsplit_once('.') else {
+            continue;
+        };
+        let suggestion = known.iter().find(|candidate| {
+            candidate
+                .rsplit_once('.')
+                .is_some_and(|(known_section, known_name)| {
+                    (known_section == section
+                        && strsim::levenshtein(known_name, name) <= MAX_TYPO_DISTANCE)
+                        || (known_name == name
+                            && strsim::levenshtein(known_section, section) <= MAX_TYPO_DISTANCE)
+                })
+        });
+        if let Some(suggestion) = suggestion {
+            issues.push(ConfigIssue {
+                level: IssueLevel::Warning,
+                key: Some(key),
+                origin,
+                message: format!("unknown key, did you mean `{suggestion}`?"),
+            });
+        }
+    }
+    issues
+}
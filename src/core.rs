@@ -209,5 +209,14 @@ pub mod component;
 // API para añadir nuevas funcionalidades usando extensiones.
 pub mod extension;
 
+// API para publicar eventos y suscribirse a ellos entre extensiones.
+pub mod event;
+
+// API para ejecutar trabajos en segundo plano.
+pub mod job;
+
+// API para programar tareas periódicas desde las extensiones.
+pub mod schedule;
+
 // API para añadir y gestionar nuevos temas.
 pub mod theme;
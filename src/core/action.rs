@@ -3,6 +3,9 @@
 //! Permite crear acciones para que otros *crates* puedan inyectar código usando funciones *ad hoc*
 //! que modifican el comportamiento predefinido en puntos concretos del flujo de ejecución de la
 //! aplicación.
+//!
+//! Las extensiones también pueden ofrecer sus propios puntos de extensión a otras extensiones
+//! declarando **ganchos** con [`declare_hook!`](crate::declare_hook).
 
 mod definition;
 pub use definition::{ActionBox, ActionDispatcher, ActionKey};
@@ -12,7 +15,9 @@ use list::ActionsList;
 
 mod all;
 pub(crate) use all::add_action;
-pub use all::dispatch_actions;
+pub use all::{
+    dispatch_actions, dispatch_alter, dispatch_collect, dispatch_first, dispatch_notify,
+};
 
 // **< actions! >***********************************************************************************
 
@@ -52,3 +57,144 @@ macro_rules! actions {
         vec![$(Box::new($action),)+]
     }};
 }
+
+// **< declare_hook! >******************************************************************************
+
+/// Declara un gancho para que otras extensiones registren funciones en un punto de extensión.
+///
+/// Genera una acción con los métodos `new()` para registrar la función desde
+/// [`actions()`](crate::core::extension::Extension::actions), `with_weight()` para ordenarla, y
+/// `dispatch()` para ejecutar todas las funciones registradas en orden de peso (las de pesos más
+/// bajos antes). Según la estrategia para agregar los resultados:
+///
+/// | Declaración               | Función registrada      | `dispatch()` devuelve       |
+/// |---------------------------|-------------------------|-----------------------------|
+/// | `Name(args);`             | `fn(args)`              | nada, las ejecuta todas     |
+/// | `Name(args) alters T;`    | `fn(args, T) -> T`      | el valor alterado por todas |
+/// | `Name(args) collects T;`  | `fn(args) -> T`         | `Vec<T>` con cada resultado |
+/// | `Name(args) finds T;`     | `fn(args) -> Option<T>` | el primer `Some`            |
+///
+/// Los argumentos se pasan a todas las funciones, así que deben ser referencias o tipos `Copy`.
+/// Ver [`dispatch_notify()`], [`dispatch_alter()`], [`dispatch_collect()`] y [`dispatch_first()`]
+/// para escribir los ganchos sin la macro.
+///
+/// # Ejemplo
+///
+/// ```rust
+/// # use pagetop::prelude::*;
+/// declare_hook! {
+///     /// Permite modificar el resumen de un artículo del blog.
+///     pub AlterSummary(title: &str) alters String;
+/// }
+///
+/// declare_hook! {
+///     /// Permite añadir etiquetas a un artículo del blog.
+///     pub CollectTags(title: &str) collects String;
+/// }
+///
+/// // Otra extensión registra sus funciones en los ganchos.
+/// pub struct Highlights;
+///
+/// impl Extension for Highlights {
+///     fn actions(&self) -> Vec<ActionBox> {
+///         actions![
+///             AlterSummary::new(|title, summary| format!("{title}: {summary}")),
+///             CollectTags::new(|_| "featured".to_string()).with_weight(-10),
+///         ]
+///     }
+/// }
+///
+/// // Y la extensión del blog ejecuta los ganchos.
+/// fn summary(title: &str) -> (String, Vec<String>) {
+///     (
+///         AlterSummary::dispatch(title, "Lorem ipsum".to_string()),
+///         CollectTags::dispatch(title),
+///     )
+/// }
+/// ```
+#[macro_export]
+macro_rules! declare_hook {
+    ( @define $(#[$attr:meta])* $vis:vis $name:ident, $f:ty ) => {
+        $(#[$attr])*
+        $vis struct $name {
+            f: $f,
+            weight: $crate::Weight,
+        }
+
+        impl $crate::core::action::ActionDispatcher for $name {
+            fn weight(&self) -> $crate::Weight {
+                self.weight
+            }
+        }
+
+        impl $name {
+            /// Crea la acción con la función que se ejecuta en este gancho.
+            #[allow(dead_code)]
+            $vis fn new(f: $f) -> Self {
+                $name { f, weight: 0 }
+            }
+
+            /// Opcional. Las funciones con pesos más bajos se ejecutan antes. Se pueden usar
+            /// valores negativos.
+            #[allow(dead_code)]
+            $vis fn with_weight(mut self, value: $crate::Weight) -> Self {
+                self.weight = value;
+                self
+            }
+        }
+    };
+    ( $(#[$attr:meta])* $vis:vis $name:ident ( $($arg:ident : $ty:ty),* $(,)? ) ; ) => {
+        $crate::declare_hook!(@define $(#[$attr])* $vis $name, fn($($ty),*));
+
+        impl $name {
+            /// Ejecuta todas las funciones registradas en este gancho.
+            #[allow(dead_code)]
+            $vis fn dispatch($($arg: $ty),*) {
+                $crate::core::action::dispatch_notify(|hook: &Self| (hook.f)($($arg),*));
+            }
+        }
+    };
+    ( $(#[$attr:meta])* $vis:vis $name:ident ( $($arg:ident : $ty:ty),* $(,)? )
+        alters $out:ty ;
+    ) => {
+        $crate::declare_hook!(@define $(#[$attr])* $vis $name, fn($($ty,)* $out) -> $out);
+
+        impl $name {
+            /// Pasa el valor por todas las funciones registradas en este gancho y devuelve el
+            /// resultado.
+            #[allow(dead_code)]
+            $vis fn dispatch($($arg: $ty,)* value: $out) -> $out {
+                $crate::core::action::dispatch_alter(value, |hook: &Self, value| {
+                    (hook.f)($($arg,)* value)
+                })
+            }
+        }
+    };
+    ( $(#[$attr:meta])* $vis:vis $name:ident ( $($arg:ident : $ty:ty),* $(,)? )
+        collects $out:ty ;
+    ) => {
+        $crate::declare_hook!(@define $(#[$attr])* $vis $name, fn($($ty),*) -> $out);
+
+        impl $name {
+            /// Devuelve los resultados de todas las funciones registradas en este gancho.
+            #[allow(dead_code)]
+            $vis fn dispatch($($arg: $ty),*) -> Vec<$out> {
+                $crate::core::action::dispatch_collect(|hook: &Self| (hook.f)($($arg),*))
+            }
+        }
+    };
+    ( $(#[$attr:meta])* $vis:vis $name:ident ( $($arg:ident : $ty:ty),* $(,)? )
+        finds $out:ty ;
+    ) => {
+        $crate::declare_hook!(@define $(#[$attr])* $vis $name, fn($($ty),*) -> Option<$out>);
+
+        impl $name {
+            /// Devuelve el primer resultado de las funciones registradas en este gancho que no sea
+            /// `None`.
+            #[allow(dead_code)]
+            $vis fn dispatch($($arg: $ty),*) -> Option<$out> {
+                $crate::core::action::dispatch_first(|hook: &Self| (hook.f)($($arg),*))
+            }
+        }
+    };
+}
@@ -1,8 +1,10 @@
 use crate::core::action::{ActionBox, ActionDispatcher, ActionKey, ActionsList};
+use crate::UniqueId;
 
 use parking_lot::RwLock;
 
 use std::collections::HashMap;
+use std::ops::ControlFlow;
 use std::sync::LazyLock;
 
 // **< ACCIONES >***********************************************************************************
@@ -70,3 +72,89 @@ where
         list.iter_map(f);
     }
 }
+
+// **< DESPACHAR GANCHOS >**************************************************************************
+
+// Recorre en orden de peso las acciones de tipo `A` sin objeto referido.
+fn visit_actions<A, F>(f: F)
+where
+    A: ActionDispatcher,
+    F: FnMut(&A) -> ControlFlow<()>,
+{
+    if let Some(list) = ACTIONS
+        .read()
+        .get(&ActionKey::new(UniqueId::of::<A>(), None, None))
+    {
+        list.visit(f);
+    }
+}
+
+/// Ejecuta todas las funciones registradas para la acción `A`, sin esperar ningún resultado.
+///
+/// Las funciones se ejecutan en orden de peso, las de pesos más bajos antes. Es la estrategia de
+/// los ganchos declarados con [`declare_hook!`](crate::declare_hook) sin resultado.
+pub fn dispatch_notify<A, F>(mut f: F)
+where
+    A: ActionDispatcher,
+    F: FnMut(&A),
+{
+    visit_actions(|action: &A| {
+        f(action);
+        ControlFlow::Continue(())
+    });
+}
+
+/// Pasa un valor por todas las funciones registradas para la acción `A`, y devuelve el valor que
+/// devuelve la última.
+///
+/// Cada función recibe el valor que ha devuelto la anterior, en orden de peso. Es la estrategia de
+/// los ganchos declarados con [`declare_hook!`](crate::declare_hook) con `alters`.
+pub fn dispatch_alter<A, T, F>(value: T, mut f: F) -> T
+where
+    A: ActionDispatcher,
+    F: FnMut(&A, T) -> T,
+{
+    let mut value = Some(value);
+    visit_actions(|action: &A| {
+        value = value.take().map(|current| f(action, current));
+        ControlFlow::Continue(())
+    });
+    value.expect("Value returned by the previous action")
+}
+
+/// Devuelve los resultados de todas las funciones registradas para la acción `A`.
+///
+/// Los resultados se devuelven en orden de peso. Es la estrategia de los ganchos declarados con
+/// [`declare_hook!`](crate::declare_hook) con `collects`.
+pub fn dispatch_collect<A, T, F>(mut f: F) -> Vec<T>
+where
+    A: ActionDispatcher,
+    F: FnMut(&A) -> T,
+{
+    let mut results = Vec::new();
+    visit_actions(|action: &A| {
+        results.push(f(action));
+        ControlFlow::Continue(())
+    });
+    results
+}
+
+/// Devuelve el primer resultado de las funciones registradas para la acción `A` que no sea `None`.
+///
+/// Las funciones se ejecutan en orden de peso, y las siguientes ya no se ejecutan. Es la estrategia
+/// de los ganchos declarados con [`declare_hook!`](crate::declare_hook) con `finds`.
+pub fn dispatch_first<A, T, F>(mut f: F) -> Option<T>
+where
+    A: ActionDispatcher,
+    F: FnMut(&A) -> Option<T>,
+{
+    let mut found = None;
+    visit_actions(|action: &A| match f(action) {
+        Some(result) => {
+            found = Some(result);
+            ControlFlow::Break(())
+        }
+        None => ControlFlow::Continue(()),
+    });
+    found
+}
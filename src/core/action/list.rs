@@ -5,6 +5,8 @@ use crate::AutoDefault;
 
 use parking_lot::RwLock;
 
+use std::ops::ControlFlow;
+
 #[derive(AutoDefault)]
 pub struct ActionsList(RwLock<Vec<ActionBox>>);
 
@@ -39,4 +41,21 @@ impl ActionsList {
             })
             .collect();
     }
+
+    // Recorre las acciones en orden de peso hasta que la función pida detenerse.
+    pub fn visit<A, F>(&self, mut f: F)
+    where
+        A: ActionDispatcher,
+        F: FnMut(&A) -> ControlFlow<()>,
+    {
+        for a in self.0.read().iter() {
+            if let Some(action) = (**a).downcast_ref::<A>() {
+                if f(action).is_break() {
+                    break;
+                }
+            } else {
+                trace::error!("Failed to downcast action of type {}", (**a).type_name());
+            }
+        }
+    }
 }
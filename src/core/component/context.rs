@@ -1,17 +1,20 @@
-use crate::core::component::{ChildOp, Component, MessageLevel, StatusMessage};
+use crate::core::component::{Child, ChildOp, Component, MessageLevel, StatusMessage};
 use crate::core::theme::all::DEFAULT_THEME;
-use crate::core::theme::{ChildrenInRegions, DefaultRegion, RegionRef, TemplateRef, ThemeRef};
+use crate::core::theme::{ChildrenInRegions, DefaultRegion, RegionRef};
+use crate::core::theme::{TemplateRef, ThemeRef};
 use crate::core::TypeInfo;
 use crate::html::{html, Markup, RoutePath};
-use crate::html::{Assets, Favicon, JavaScript, StyleSheet};
+use crate::html::{Assets, Favicon, JavaScript, StructuredData, StyleSheet};
 use crate::locale::L10n;
 use crate::locale::{LangId, LanguageIdentifier, RequestLocale};
-use crate::service::HttpRequest;
-use crate::{builder_fn, util, CowStr};
+use crate::service::{self, HttpRequest};
+use crate::{builder_fn, trace, util, CowStr};
+
+use serde::Serialize;
 
 use std::any::Any;
 use std::cell::Cell;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::fmt;
 
 /// Operaciones para modificar recursos asociados al [`Context`] de un documento.
@@ -288,6 +291,19 @@ pub struct Context {
     params     : HashMap<&'static str, (Box<dyn Any>, &'static str)>, // Parámetros en ejecución.
     id_counter : Cell<usize>,              // Cell permite incrementar desde &self en required_id().
     messages   : Vec<StatusMessage>,       // Mensajes de usuario acumulados.
+    rendered   : RenderedAssets,           // Recursos ya enviados en un renderizado por fragmentos.
+    streaming  : bool,                     // Indica si la página se envía por fragmentos.
+    deferred   : VecDeque<(String, Child)>, // Componentes aplazados al final del envío.
+    structured : StructuredData,           // Datos estructurados (JSON-LD) del documento.
+}
+
+// Registro de los recursos ya renderizados cuando el documento se envía por fragmentos.
+#[rustfmt::skip]
+#[derive(Default)]
+struct RenderedAssets {
+    favicon   : bool,
+    names     : Vec<String>,
+    structured: usize,
 }
 
 impl Default for Context {
@@ -316,6 +332,10 @@ impl Context {
             params     : HashMap::default(),
             id_counter : Cell::new(0),
             messages   : Vec::new(),
+            rendered   : RenderedAssets::default(),
+            streaming  : false,
+            deferred   : VecDeque::new(),
+            structured : StructuredData::default(),
         }
     }
 
@@ -347,6 +367,65 @@ impl Context {
         markup
     }
 
+    /// Renderiza los datos estructurados (JSON-LD) del contexto.
+    ///
+    /// Devuelve un bloque `<script type="application/ld+json">` o nada si no hay datos.
+    pub fn render_structured_data(&self) -> Markup {
+        self.structured.render()
+    }
+
+    /// Renderiza sólo los recursos añadidos desde la última llamada a
+    /// [`mark_assets_rendered()`](Self::mark_assets_rendered).
+    ///
+    /// Se usa cuando el `<head>` del documento ya se ha enviado (por ejemplo, en el renderizado por
+    /// fragmentos de una página) y hay que incluir después los recursos que los componentes hayan
+    /// ido añadiendo. Tras renderizarlos, también quedan marcados como enviados.
+    pub(crate) fn render_pending_assets(&mut self) -> Markup {
+        use std::mem::take as mem_take;
+
+        let favicon = mem_take(&mut self.favicon);
+        let stylesheets = mem_take(&mut self.stylesheets);
+        let javascripts = mem_take(&mut self.javascripts);
+        let rendered = mem_take(&mut self.rendered.names);
+
+        let markup = html! {
+            @if let Some(fi) = favicon.as_ref().filter(|_| !self.rendered.favicon) {
+                (fi.render(self))
+            }
+            (stylesheets.render_except(self, &rendered))
+            (javascripts.render_except(self, &rendered))
+            (self.structured.render_from(self.rendered.structured))
+        };
+
+        self.favicon = favicon;
+        self.stylesheets = stylesheets;
+        self.javascripts = javascripts;
+        self.rendered.names = rendered;
+        self.mark_assets_rendered();
+
+        markup
+    }
+
+    /// Marca como enviados todos los recursos actuales del contexto.
+    ///
+    /// Las llamadas posteriores a [`render_pending_assets()`](Self::render_pending_assets) sólo
+    /// renderizarán los recursos que se añadan a partir de este momento.
+    pub(crate) fn mark_assets_rendered(&mut self) {
+        self.rendered.favicon |= self.favicon.is_some();
+        self.rendered.structured = self.structured.revision();
+        for name in self.stylesheets.names().chain(self.javascripts.names()) {
+            if !self.rendered.names.iter().any(|n| n == name) {
+                self.rendered.names.push(name.to_owned());
+            }
+        }
+    }
+
+    // Devuelve una copia del componente de primer nivel con el identificador dado en cualquier
+    // región del documento.
+    pub(crate) fn child_by_id(&self, id: &str) -> Option<Child> {
+        self.regions.child_by_id(self.theme, id)
+    }
+
     /// Renderiza los componentes de una región.
     pub fn render_region(&mut self, region_ref: RegionRef) -> Markup {
         self.regions
@@ -374,6 +453,37 @@ impl Context {
         route
     }
 
+    /// Construye la ruta de una ruta con nombre aplicada al contexto actual.
+    ///
+    /// Sustituye los segmentos dinámicos del patrón de la ruta registrada con el nombre `name` por
+    /// los valores de `params`; los parámetros que no aparecen en el patrón se añaden como
+    /// parámetros de consulta (ver [`service::url_path()`](crate::service::url_path)). Igual que
+    /// [`route()`](Self::route), añade el parámetro `lang=...` si la negociación de idioma lo
+    /// requiere.
+    ///
+    /// # Panics
+    ///
+    /// En las compilaciones de desarrollo provoca un *panic* si la ruta no existe o falta algún
+    /// parámetro de su patrón, para detectar cuanto antes los enlaces rotos. En producción lo
+    /// registra como error en la traza y devuelve la ruta `"#"`.
+    pub fn url_for(&self, name: &str, params: &[(&str, &str)]) -> RoutePath {
+        match service::url_path(name, params) {
+            Ok(mut route) => {
+                if self.locale.needs_lang_query() {
+                    route.alter_param("lang", self.locale.langid().to_string());
+                }
+                route
+            }
+            Err(e) => {
+                if cfg!(debug_assertions) {
+                    panic!("Failed to build URL: {e}");
+                }
+                trace::error!("Failed to build URL: {e}");
+                RoutePath::new("#")
+            }
+        }
+    }
+
     /// Garantiza un identificador único para un componente `C`, generándolo si no se proporciona
     /// ninguno.
     ///
@@ -427,6 +537,58 @@ impl Context {
     pub fn has_messages(&self) -> bool {
         !self.messages.is_empty()
     }
+
+    /// Añade datos estructurados (JSON-LD) al documento a partir de cualquier valor serializable.
+    ///
+    /// Varios componentes o extensiones pueden contribuir a la misma entidad usando el mismo
+    /// identificador `@id` (ver [`StructuredData`]). Si la página se envía por partes y la entidad
+    /// ya se había enviado en el `<head>`, se vuelve a enviar completa con las nuevas propiedades.
+    ///
+    /// # Ejemplo
+    ///
+    /// ```rust
+    /// # use pagetop::prelude::*;
+    /// # let mut cx = Context::new(None);
+    /// cx.add_structured_data(serde_json::json!({
+    ///     "@type": "BreadcrumbList",
+    ///     "itemListElement": [
+    ///         { "@type": "ListItem", "position": 1, "name": "Blog", "item": "https://example.com/blog" }
+    ///     ]
+    /// }));
+    /// ```
+    pub fn add_structured_data(&mut self, data: impl Serialize) {
+        self.structured.add(data);
+    }
+
+    /// Devuelve los datos estructurados (JSON-LD) acumulados en el contexto.
+    pub fn structured_data(&self) -> &StructuredData {
+        &self.structured
+    }
+
+    /// Indica si la página se está enviando por fragmentos
+    /// ([`Page::stream()`](crate::response::page::Page::stream)).
+    ///
+    /// Los componentes pueden consultarlo para aplazar contenidos lentos, como hace
+    /// [`Deferred`](crate::base::component::Deferred).
+    pub fn is_streaming(&self) -> bool {
+        self.streaming
+    }
+
+    // Activa el modo de envío por fragmentos.
+    pub(crate) fn enable_streaming(&mut self) {
+        self.streaming = true;
+    }
+
+    // Aplaza el renderizado de un componente hasta el final del envío por fragmentos. El marcado
+    // resultante sustituirá al elemento del documento con el identificador `id`.
+    pub(crate) fn defer(&mut self, id: String, child: Child) {
+        self.deferred.push_back((id, child));
+    }
+
+    // Extrae el siguiente componente aplazado, si existe.
+    pub(crate) fn take_deferred(&mut self) -> Option<(String, Child)> {
+        self.deferred.pop_front()
+    }
 }
 
 /// Permite a [`Context`](crate::core::component::Context) actuar como proveedor de idioma.
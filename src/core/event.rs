@@ -0,0 +1,349 @@
+//! API para publicar eventos y suscribirse a ellos entre extensiones.
+//!
+//! Las [acciones](crate::core::action) permiten alterar el renderizado en puntos concretos. Los
+//! **eventos** sirven en cambio para anunciar hechos del dominio de la aplicación, como que se ha
+//! registrado un usuario o se ha guardado un contenido, sin saber qué extensiones los atienden.
+//!
+//! Un evento es cualquier tipo que implementa [`Event`]. Una extensión lo anuncia con
+//! [`publish()`] o [`publish_and_wait()`], y las extensiones interesadas declaran sus
+//! [`Subscriber`] en [`Extension::subscribers()`](crate::core::extension::Extension::subscribers):
+//!
+//! - Los suscriptores síncronos ([`Subscriber::on()`]) reciben una referencia al evento y se
+//!   ejecutan en el momento de publicarlo.
+//! - Los suscriptores asíncronos ([`Subscriber::on_async()`]) reciben una copia del evento y se
+//!   ejecutan en el *runtime* de actix. Con [`publish()`] se lanzan sin esperar a que terminen, y
+//!   con [`publish_and_wait()`] se espera a cada uno de ellos.
+//! - Los suscriptores con pesos ([`Weight`]) más bajos reciben el evento antes. Con el mismo peso
+//!   se respeta el orden de carga de las extensiones.
+//!
+//! Para comprobar en las pruebas qué eventos se publican se puede usar [`EventRecorder`].
+//!
+//! # Ejemplo
+//!
+//! ```rust,no_run
+//! # use pagetop::prelude::*;
+//! #[derive(Clone)]
+//! pub struct UserRegistered {
+//!     pub email: String,
+//! }
+//!
+//! impl Event for UserRegistered {}
+//!
+//! pub struct Newsletter;
+//!
+//! impl Extension for Newsletter {
+//!     fn subscribers(&self) -> Vec<Subscriber> {
+//!         vec![
+//!             Subscriber::on(|user: &UserRegistered| {
+//!                 trace::info!("New user {}", user.email);
+//!             })
+//!             .with_weight(-10),
+//!             Subscriber::on_async(subscribe_user),
+//!         ]
+//!     }
+//! }
+//!
+//! async fn subscribe_user(user: UserRegistered) {
+//!     trace::info!("Subscribing {} to the newsletter", user.email);
+//! }
+//!
+//! fn register(email: &str) {
+//!     event::publish(UserRegistered {
+//!         email: email.to_string(),
+//!     });
+//! }
+//! ```
+
+use crate::core::TypeInfo;
+use crate::{service, trace, UniqueId, Weight};
+
+use parking_lot::{Mutex, RwLock};
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, LazyLock};
+
+type SyncHandler = Arc<dyn Fn(&dyn Any) + Send + Sync>;
+
+type AsyncHandler = Arc<dyn Fn(&dyn Any) -> Pin<Box<dyn Future<Output = ()>>> + Send + Sync>;
+
+// Suscriptores de las extensiones para cada tipo de evento, ordenados por peso.
+static SUBSCRIBERS: LazyLock<RwLock<HashMap<UniqueId, Vec<Subscriber>>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+// Funciones de los registros de eventos activos, con su identificador y el tipo de evento.
+static RECORDERS: LazyLock<RwLock<Vec<(u64, UniqueId, SyncHandler)>>> =
+    LazyLock::new(|| RwLock::new(Vec::new()));
+
+static NEXT_RECORDER_ID: AtomicU64 = AtomicU64::new(1);
+
+// **< Event >**************************************************************************************
+
+/// Interfaz común de los eventos que se publican entre extensiones.
+///
+/// Basta con implementarlo sin métodos. Los suscriptores asíncronos reciben una copia del evento,
+/// por eso tiene que implementar [`Clone`].
+pub trait Event: Any + Clone + Send + Sync {}
+
+// **< Subscriber >*********************************************************************************
+
+/// Función suscrita a un tipo de evento.
+///
+/// Ver [`Extension::subscribers()`](crate::core::extension::Extension::subscribers).
+#[derive(Clone)]
+pub struct Subscriber {
+    event_type_id: UniqueId,
+    event_name: &'static str,
+    handler: Handler,
+    weight: Weight,
+}
+
+#[derive(Clone)]
+enum Handler {
+    Sync(SyncHandler),
+    Async(AsyncHandler),
+}
+
+impl Subscriber {
+    /// Suscribe una función que se ejecuta al publicar el evento `E`.
+    pub fn on<E, F>(handler: F) -> Self
+    where
+        E: Event,
+        F: Fn(&E) + Send + Sync + 'static,
+    {
+        Subscriber {
+            event_type_id: UniqueId::of::<E>(),
+            event_name: TypeInfo::ShortName.of::<E>(),
+            handler: Handler::Sync(Arc::new(move |event: &dyn Any| {
+                if let Some(event) = event.downcast_ref::<E>() {
+                    handler(event);
+                }
+            })),
+            weight: 0,
+        }
+    }
+
+    /// Suscribe una función asíncrona que se ejecuta en el *runtime* de actix al publicar el
+    /// evento `E`.
+    pub fn on_async<E, F, Fut>(handler: F) -> Self
+    where
+        E: Event,
+        F: Fn(E) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + 'static,
+    {
+        Subscriber {
+            event_type_id: UniqueId::of::<E>(),
+            event_name: TypeInfo::ShortName.of::<E>(),
+            handler: Handler::Async(Arc::new(move |event: &dyn Any| {
+                match event.downcast_ref::<E>() {
+                    Some(event) => Box::pin(handler(event.clone())),
+                    None => Box::pin(async {}),
+                }
+            })),
+            weight: 0,
+        }
+    }
+
+    /// Opcional. Los suscriptores con pesos más bajos reciben el evento antes. Se pueden usar
+    /// valores negativos.
+    pub fn with_weight(mut self, value: Weight) -> Self {
+        self.weight = value;
+        self
+    }
+
+    /// Devuelve el nombre corto del tipo de evento.
+    pub fn event_name(&self) -> &'static str {
+        self.event_name
+    }
+
+    /// Devuelve el peso del suscriptor.
+    pub fn weight(&self) -> Weight {
+        self.weight
+    }
+
+    /// Devuelve `true` si es un suscriptor asíncrono.
+    pub fn is_async(&self) -> bool {
+        matches!(self.handler, Handler::Async(_))
+    }
+}
+
+// Registra los suscriptores de las extensiones, sustituyendo a los anteriores.
+pub(crate) fn register_subscribers(subscribers: Vec<Subscriber>) {
+    let mut registry: HashMap<UniqueId, Vec<Subscriber>> = HashMap::new();
+    for subscriber in subscribers {
+        registry
+            .entry(subscriber.event_type_id)
+            .or_default()
+            .push(subscriber);
+    }
+    for list in registry.values_mut() {
+        // La ordenación estable mantiene el orden de carga con el mismo peso.
+        list.sort_by_key(|subscriber| subscriber.weight);
+    }
+    *SUBSCRIBERS.write() = registry;
+}
+
+// Suscriptores del evento `E`, sin bloquear el registro mientras se ejecutan.
+fn subscribers_of<E: Event>(event: &E) -> Vec<Subscriber> {
+    for (_, event_type_id, record) in RECORDERS.read().iter() {
+        if *event_type_id == UniqueId::of::<E>() {
+            record(event);
+        }
+    }
+    let subscribers = SUBSCRIBERS
+        .read()
+        .get(&UniqueId::of::<E>())
+        .cloned()
+        .unwrap_or_default();
+    trace::debug!(
+        "Publishing event \"{}\" to {} subscriber(s)",
+        TypeInfo::ShortName.of::<E>(),
+        subscribers.len()
+    );
+    subscribers
+}
+
+// **< publish() >**********************************************************************************
+
+/// Publica un evento para todos sus suscriptores.
+///
+/// Los suscriptores síncronos se ejecutan antes de volver, y los asíncronos se lanzan en el
+/// *runtime* de actix sin esperar a que terminen.
+///
+/// # Panics
+///
+/// Si hay suscriptores asíncronos y se publica fuera del *runtime* de actix.
+pub fn publish<E: Event>(event: E) {
+    for subscriber in subscribers_of(&event) {
+        match subscriber.handler {
+            Handler::Sync(handler) => handler(&event),
+            Handler::Async(handler) => {
+                service::rt::spawn(handler(&event));
+            }
+        }
+    }
+}
+
+/// Publica un evento para todos sus suscriptores y espera a que terminen.
+///
+/// Los suscriptores, síncronos y asíncronos, se ejecutan uno tras otro en orden de peso.
+pub async fn publish_and_wait<E: Event>(event: E) {
+    for subscriber in subscribers_of(&event) {
+        match subscriber.handler {
+            Handler::Sync(handler) => handler(&event),
+            Handler::Async(handler) => handler(&event).await,
+        }
+    }
+}
+
+// **< EventRecorder >******************************************************************************
+
+/// Registro de los eventos de tipo `E` publicados, para comprobarlos en las pruebas.
+///
+/// Guarda una copia de cada evento publicado mientras exista, antes de entregarlo a los
+/// suscriptores. Los eventos publicados en otras pruebas que se ejecutan a la vez también se
+/// registran.
+///
+/// # Ejemplo
+///
+/// ```rust
+/// # use pagetop::prelude::*;
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct ContentSaved(u32);
+///
+/// impl Event for ContentSaved {}
+///
+/// let saved = EventRecorder::<ContentSaved>::new();
+/// event::publish(ContentSaved(7));
+/// saved.assert_published(|event| event.0 == 7);
+/// assert_eq!(saved.events(), [ContentSaved(7)]);
+/// ```
+pub struct EventRecorder<E: Event> {
+    id: u64,
+    events: Arc<Mutex<Vec<E>>>,
+}
+
+impl<E: Event> Default for EventRecorder<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E: Event> EventRecorder<E> {
+    /// Empieza a registrar los eventos de tipo `E` que se publiquen.
+    pub fn new() -> Self {
+        let id = NEXT_RECORDER_ID.fetch_add(1, Ordering::SeqCst);
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&events);
+        RECORDERS.write().push((
+            id,
+            UniqueId::of::<E>(),
+            Arc::new(move |event: &dyn Any| {
+                if let Some(event) = event.downcast_ref::<E>() {
+                    recorded.lock().push(event.clone());
+                }
+            }),
+        ));
+        EventRecorder { id, events }
+    }
+
+    /// Devuelve los eventos registrados, en el orden en que se publicaron.
+    pub fn events(&self) -> Vec<E> {
+        self.events.lock().clone()
+    }
+
+    /// Devuelve el número de eventos registrados.
+    pub fn count(&self) -> usize {
+        self.events.lock().len()
+    }
+
+    /// Devuelve el último evento registrado.
+    pub fn last(&self) -> Option<E> {
+        self.events.lock().last().cloned()
+    }
+
+    /// Descarta los eventos registrados hasta ahora.
+    pub fn clear(&self) {
+        self.events.lock().clear();
+    }
+
+    /// Comprueba que se ha publicado algún evento que cumple la condición indicada.
+    ///
+    /// # Panics
+    ///
+    /// Si ningún evento registrado cumple la condición.
+    #[track_caller]
+    pub fn assert_published(&self, predicate: impl Fn(&E) -> bool) {
+        let events = self.events.lock();
+        assert!(
+            events.iter().any(predicate),
+            "No matching \"{}\" event published ({} recorded)",
+            TypeInfo::ShortName.of::<E>(),
+            events.len()
+        );
+    }
+
+    /// Comprueba que no se ha publicado ningún evento.
+    ///
+    /// # Panics
+    ///
+    /// Si hay algún evento registrado.
+    #[track_caller]
+    pub fn assert_none(&self) {
+        let count = self.count();
+        assert!(
+            count == 0,
+            "Expected no \"{}\" events, {count} published",
+            TypeInfo::ShortName.of::<E>()
+        );
+    }
+}
+
+impl<E: Event> Drop for EventRecorder<E> {
+    fn drop(&mut self) {
+        RECORDERS.write().retain(|(id, _, _)| *id != self.id);
+    }
+}
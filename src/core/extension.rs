@@ -6,4 +6,9 @@
 mod definition;
 pub use definition::{Extension, ExtensionRef};
 
+mod graph;
+pub use graph::{Dependency, ExtensionGraph};
+
+mod lifecycle;
+
 pub(crate) mod all;
@@ -1,10 +1,19 @@
+use crate::app::cli;
 use crate::core::action::add_action;
-use crate::core::extension::ExtensionRef;
+use crate::core::event;
+use crate::core::extension::{lifecycle, ExtensionGraph, ExtensionRef};
+use crate::core::job::JobType;
+use crate::core::schedule::ScheduledTask;
 use crate::core::theme::all::THEMES;
+use crate::html::Link;
+use crate::response::robots::{self, RobotsRule};
+use crate::response::sitemap::{self, SitemapEntry};
+use crate::response::well_known::{self, WellKnown};
 use crate::{global, service, static_files_service, trace};
 
 use parking_lot::RwLock;
 
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::LazyLock;
 
 // **< EXTENSIONES >********************************************************************************
@@ -15,51 +24,20 @@ static ENABLED_EXTENSIONS: LazyLock<RwLock<Vec<ExtensionRef>>> =
 static DROPPED_EXTENSIONS: LazyLock<RwLock<Vec<ExtensionRef>>> =
     LazyLock::new(|| RwLock::new(Vec::new()));
 
-// **< REGISTRO DE LAS EXTENSIONES >****************************************************************
-
-pub fn register_extensions(root_extension: Option<ExtensionRef>) {
-    // Prepara la lista de extensiones habilitadas.
-    let mut enabled_list: Vec<ExtensionRef> = Vec::new();
-
-    // Primero añade el tema básico a la lista de extensiones habilitadas.
-    add_to_enabled(&mut enabled_list, &crate::base::theme::Basic);
-
-    // Si se proporciona una extensión raíz inicial, se añade a la lista de extensiones habilitadas.
-    if let Some(extension) = root_extension {
-        add_to_enabled(&mut enabled_list, extension);
-    }
-
-    // Añade la página de bienvenida predefinida si se habilita en la configuración.
-    if global::SETTINGS.app.welcome {
-        add_to_enabled(&mut enabled_list, &crate::base::extension::Welcome);
-    }
-
-    // Guarda la lista final de extensiones habilitadas.
-    ENABLED_EXTENSIONS.write().append(&mut enabled_list);
-
-    // Prepara una lista de extensiones deshabilitadas.
-    let mut dropped_list: Vec<ExtensionRef> = Vec::new();
-
-    // Si se proporciona una extensión raíz, analiza su lista de dependencias.
-    if let Some(extension) = root_extension {
-        add_to_dropped(&mut dropped_list, extension);
-    }
+static DISABLED_EXTENSIONS: LazyLock<RwLock<Vec<ExtensionRef>>> =
+    LazyLock::new(|| RwLock::new(Vec::new()));
 
-    // Guarda la lista final de extensiones deshabilitadas.
-    DROPPED_EXTENSIONS.write().append(&mut dropped_list);
-}
+// **< REGISTRO DE LAS EXTENSIONES >****************************************************************
 
-fn add_to_enabled(list: &mut Vec<ExtensionRef>, extension: ExtensionRef) {
-    // Verifica que la extensión no esté en la lista para evitar duplicados.
-    if !list.iter().any(|e| e.type_id() == extension.type_id()) {
-        // Añade primero (en orden inverso) las dependencias de la extensión.
-        for d in extension.dependencies().iter().rev() {
-            add_to_enabled(list, *d);
-        }
+// Resuelve el grafo de dependencias y registra las extensiones habilitadas en orden de carga.
+// Devuelve los problemas encontrados.
+pub fn register_extensions(root_extension: Option<ExtensionRef>) -> Vec<String> {
+    let graph = ExtensionGraph::resolve(root_extension);
 
-        // Añade la propia extensión a la lista.
-        list.push(extension);
+    let order: Vec<&str> = graph.load_order().iter().map(|e| e.short_name()).collect();
+    trace::info!("Extensions load order: {}", order.join(", "));
 
+    for extension in graph.load_order() {
         // Comprueba si la extensión tiene un tema asociado que deba registrarse.
         if let Some(theme) = extension.theme() {
             let mut registered_themes = THEMES.write();
@@ -75,35 +53,27 @@ fn add_to_enabled(list: &mut Vec<ExtensionRef>, extension: ExtensionRef) {
             trace::debug!("Enabling \"{}\" extension", extension.short_name());
         }
     }
+
+    // Guarda las listas finales de extensiones habilitadas, descartadas y deshabilitadas.
+    *ENABLED_EXTENSIONS.write() = graph.load_order().to_vec();
+    *DROPPED_EXTENSIONS.write() = graph.dropped().to_vec();
+    *DISABLED_EXTENSIONS.write() = graph.disabled().to_vec();
+
+    graph.problems().to_vec()
 }
 
-fn add_to_dropped(list: &mut Vec<ExtensionRef>, extension: ExtensionRef) {
-    // Recorre las extensiones que la actual recomienda deshabilitar.
-    for d in &extension.drop_extensions() {
-        // Verifica que la extensión no esté ya en la lista.
-        if !list.iter().any(|e| e.type_id() == d.type_id()) {
-            // Comprueba si la extensión está habilitada. Si es así, registra una advertencia.
-            if ENABLED_EXTENSIONS
-                .read()
-                .iter()
-                .any(|e| e.type_id() == extension.type_id())
-            {
-                trace::warn!(
-                    "Trying to drop \"{}\" extension which is enabled",
-                    extension.short_name()
-                );
-            } else {
-                // Si la extensión no está habilitada, se añade a la lista y registra la acción.
-                list.push(*d);
-                trace::debug!("Extension \"{}\" dropped", d.short_name());
-                // Añade recursivamente las dependencias de la extensión eliminada.
-                // De este modo, todas las dependencias se tienen en cuenta para ser deshabilitadas.
-                for dependency in &extension.dependencies() {
-                    add_to_dropped(list, *dependency);
-                }
-            }
-        }
-    }
+// **< EXTENSIONES REGISTRADAS >********************************************************************
+
+pub fn enabled_extensions() -> Vec<ExtensionRef> {
+    ENABLED_EXTENSIONS.read().clone()
+}
+
+pub fn dropped_extensions() -> Vec<ExtensionRef> {
+    DROPPED_EXTENSIONS.read().clone()
+}
+
+pub fn disabled_extensions() -> Vec<ExtensionRef> {
+    DISABLED_EXTENSIONS.read().clone()
 }
 
 // **< REGISTRO DE LAS ACCIONES >*******************************************************************
@@ -116,6 +86,32 @@ pub fn register_actions() {
     }
 }
 
+// **< REGISTRO DE LOS SUSCRIPTORES >**************************************************************
+
+pub fn register_subscribers() {
+    event::register_subscribers(
+        ENABLED_EXTENSIONS
+            .read()
+            .iter()
+            .flat_map(|extension| extension.subscribers())
+            .collect(),
+    );
+}
+
+// **< CARGA LOS AJUSTES DE LAS EXTENSIONES >******************************************************
+
+pub fn load_settings() {
+    for extension in ENABLED_EXTENSIONS.read().iter() {
+        extension.settings();
+    }
+}
+
+// **< INSTALA Y ACTUALIZA LAS EXTENSIONES >********************************************************
+
+pub fn run_lifecycle() -> Vec<String> {
+    lifecycle::run_lifecycle(&ENABLED_EXTENSIONS.read(), &DROPPED_EXTENSIONS.read())
+}
+
 // **< INICIALIZA LAS EXTENSIONES >*****************************************************************
 
 pub fn initialize_extensions() {
@@ -125,20 +121,120 @@ pub fn initialize_extensions() {
     }
 }
 
+// **< SUBCOMANDOS >********************************************************************************
+
+pub fn commands() -> Vec<cli::Command> {
+    ENABLED_EXTENSIONS
+        .read()
+        .iter()
+        .flat_map(|extension| extension.commands())
+        .collect()
+}
+
+// **< TAREAS PROGRAMADAS >*************************************************************************
+
+pub fn scheduled_tasks() -> Vec<ScheduledTask> {
+    ENABLED_EXTENSIONS
+        .read()
+        .iter()
+        .flat_map(|extension| {
+            let owner = extension.short_name();
+            extension
+                .scheduled_tasks()
+                .into_iter()
+                .map(move |task| task.with_owner(owner))
+        })
+        .collect()
+}
+
+// **< TRABAJOS EN SEGUNDO PLANO >*****************************************************************
+
+pub fn job_types() -> Vec<JobType> {
+    ENABLED_EXTENSIONS
+        .read()
+        .iter()
+        .flat_map(|extension| extension.jobs())
+        .collect()
+}
+
+// **< CANALES DE SINDICACIÓN >*********************************************************************
+
+pub fn feed_links() -> Vec<Link> {
+    ENABLED_EXTENSIONS
+        .read()
+        .iter()
+        .flat_map(|extension| extension.feeds())
+        .collect()
+}
+
+// **< MAPA DEL SITIO >*****************************************************************************
+
+// Indica si alguna extensión publica entradas en el mapa del sitio. Se comprueba al configurar el
+// servicio web para no generar todas las entradas en cada petición de `/robots.txt`.
+static HAS_SITEMAP: AtomicBool = AtomicBool::new(false);
+
+pub fn sitemap_entries() -> Vec<SitemapEntry> {
+    ENABLED_EXTENSIONS
+        .read()
+        .iter()
+        .flat_map(|extension| extension.sitemap())
+        .collect()
+}
+
+pub fn has_sitemap_entries() -> bool {
+    HAS_SITEMAP.load(Ordering::Relaxed)
+}
+
+fn check_sitemap_entries() {
+    let has_sitemap = ENABLED_EXTENSIONS
+        .read()
+        .iter()
+        .any(|extension| !extension.sitemap().is_empty());
+    HAS_SITEMAP.store(has_sitemap, Ordering::Relaxed);
+}
+
+// **< ROBOTS.TXT Y RUTAS CONOCIDAS >***************************************************************
+
+pub fn robots_rules() -> Vec<RobotsRule> {
+    ENABLED_EXTENSIONS
+        .read()
+        .iter()
+        .flat_map(|extension| extension.robots())
+        .collect()
+}
+
+pub fn well_known_documents() -> Vec<WellKnown> {
+    ENABLED_EXTENSIONS
+        .read()
+        .iter()
+        .flat_map(|extension| extension.well_known())
+        .collect()
+}
+
 // **< CONFIGURA LOS SERVICIOS >********************************************************************
 
 pub fn configure_services(scfg: &mut service::web::ServiceConfig) {
-    // Sólo compila durante el desarrollo, para evitar errores 400 en la traza de eventos.
-    #[cfg(debug_assertions)]
-    scfg.route(
-        // Ruta automática lanzada por Chrome DevTools.
-        "/.well-known/appspecific/com.chrome.devtools.json",
-        service::web::get().to(|| async { service::HttpResponse::NotFound().finish() }),
-    );
+    check_sitemap_entries();
+    robots::configure_service(scfg);
+    sitemap::configure_service(scfg);
 
+    // Asigna al inventario de rutas la extensión que configura cada una.
     for extension in ENABLED_EXTENSIONS.read().iter() {
+        service::set_route_owner(extension.short_name());
+        service::configure_routes(scfg, extension.routes());
         extension.configure_service(scfg);
+        if extension.has_unlisted_routes() {
+            service::add_unlisted();
+        }
     }
+    service::set_route_owner("PageTop");
+
+    // Las rutas conocidas sin documento, como la que lanza Chrome DevTools, responden con un 404
+    // sin contenido para evitar errores 400 en la traza de eventos. Se configuran después de las
+    // extensiones para no ocultar las rutas que éstas definan en `/.well-known/`.
+    well_known::configure_service(scfg);
 
     static_files_service!(scfg, [&global::SETTINGS.dev.pagetop_static_dir, assets] => "/");
+
+    service::publish_routes();
 }
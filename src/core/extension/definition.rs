@@ -1,7 +1,16 @@
+use crate::app::cli;
 use crate::core::action::ActionBox;
+use crate::core::event::Subscriber;
+use crate::core::extension::Dependency;
+use crate::core::job::JobType;
+use crate::core::schedule::ScheduledTask;
 use crate::core::theme::ThemeRef;
 use crate::core::AnyInfo;
+use crate::html::Link;
 use crate::locale::L10n;
+use crate::response::robots::RobotsRule;
+use crate::response::sitemap::SitemapEntry;
+use crate::response::well_known::WellKnown;
 use crate::{actions, service};
 
 /// Interfaz común que debe implementar cualquier extensión de PageTop.
@@ -63,14 +72,38 @@ pub trait Extension: AnyInfo + Send + Sync {
         None
     }
 
+    /// Versión de la extensión, con la sintaxis de [*Semantic Versioning*](https://semver.org).
+    ///
+    /// Se usa para comprobar los requisitos de versión de otras extensiones (ver [`Dependency`]).
+    /// Normalmente basta con devolver `Some(env!("CARGO_PKG_VERSION"))`. Por defecto devuelve
+    /// `None`.
+    fn version(&self) -> Option<&'static str> {
+        None
+    }
+
     /// Otras extensiones que deben habilitarse **antes** de esta.
     ///
     /// PageTop resolverá automáticamente estas dependencias respetando el orden durante el arranque
-    /// de la aplicación.
+    /// de la aplicación (ver [`ExtensionGraph`](crate::core::extension::ExtensionGraph)).
     fn dependencies(&self) -> Vec<ExtensionRef> {
         vec![]
     }
 
+    /// Dependencias opcionales o con requisitos de versión.
+    ///
+    /// Se añaden a las de [`dependencies()`](Self::dependencies). Ver [`Dependency`].
+    fn requirements(&self) -> Vec<Dependency> {
+        vec![]
+    }
+
+    /// Otras extensiones disponibles que sólo se habilitan si se indican en la configuración.
+    ///
+    /// Se habilitan añadiendo su nombre corto a
+    /// [`extensions.enabled`](crate::global::Extensions::enabled), junto con sus dependencias.
+    fn optional_extensions(&self) -> Vec<ExtensionRef> {
+        vec![]
+    }
+
     /// Devuelve la lista de acciones que la extensión registra.
     ///
     /// Estas [acciones](crate::core::action) se despachan por orden de registro o por
@@ -80,15 +113,89 @@ pub trait Extension: AnyInfo + Send + Sync {
         actions![]
     }
 
+    /// Devuelve las funciones que la extensión suscribe a los eventos que publican otras
+    /// extensiones.
+    ///
+    /// Ver [`event`](crate::core::event) para más detalles.
+    fn subscribers(&self) -> Vec<Subscriber> {
+        Vec::new()
+    }
+
+    /// Carga los ajustes de configuración propios de la extensión.
+    ///
+    /// Los ajustes declarados con [`include_config!`](crate::include_config) se cargan la primera
+    /// vez que se usan. Las extensiones que los declaren deberían forzar aquí su carga, para que se
+    /// comprueben al preparar la aplicación, antes de [`install()`](Self::install), y aparezcan en
+    /// el subcomando `config` de la [línea de comandos](crate::app::cli):
+    ///
+    /// ```rust,ignore
+    /// fn settings(&self) {
+    ///     std::sync::LazyLock::force(&config::SETTINGS);
+    /// }
+    /// ```
+    fn settings(&self) {}
+
+    /// Instala la extensión la primera vez que se habilita, por ejemplo para crear sus datos.
+    ///
+    /// PageTop guarda las extensiones instaladas y su [versión](Self::version) en el archivo
+    /// indicado en [`extensions.state_path`](crate::global::Extensions::state_path), y sólo llama
+    /// a este método, antes de [`initialize()`](Self::initialize), si la extensión no consta como
+    /// instalada. Si devuelve un error la aplicación no arranca, y se volverá a intentar en el
+    /// siguiente arranque.
+    ///
+    /// Igual que [`upgrade()`](Self::upgrade) y [`uninstall()`](Self::uninstall), se llama al
+    /// preparar la aplicación, salvo con los subcomandos de la [línea de comandos](crate::app::cli)
+    /// que sólo consultan la aplicación.
+    fn install(&self) -> std::io::Result<()> {
+        Ok(())
+    }
+
+    /// Actualiza una extensión instalada con una versión anterior, por ejemplo para migrar sus
+    /// datos.
+    ///
+    /// Se llama una sola vez, antes de [`initialize()`](Self::initialize), cuando la versión
+    /// guardada (`from_version`) es distinta de la actual. Funciona igual que
+    /// [`install()`](Self::install) en caso de error. La aplicación no arranca si la versión
+    /// actual es anterior a la guardada.
+    fn upgrade(&self, from_version: &str) -> std::io::Result<()> {
+        let _ = from_version;
+        Ok(())
+    }
+
+    /// Desinstala la extensión cuando otra la descarta con
+    /// [`drop_extensions()`](Self::drop_extensions), por ejemplo para eliminar sus datos.
+    ///
+    /// Sólo se llama si la extensión consta como instalada, y después deja de constar. La
+    /// desinstalación es definitiva: si la extensión vuelve a habilitarse más adelante se instalará
+    /// de nuevo con [`install()`](Self::install), sin los datos eliminados.
+    fn uninstall(&self) -> std::io::Result<()> {
+        Ok(())
+    }
+
     /// Inicializa la extensión durante la fase de arranque de la aplicación.
     ///
     /// Se llama una sola vez, después de que todas las dependencias se han inicializado y antes de
     /// aceptar cualquier petición HTTP.
     fn initialize(&self) {}
 
+    /// Devuelve las rutas con nombre de la extensión.
+    ///
+    /// PageTop las configura en el servicio web antes de llamar a
+    /// [`configure_service()`](Self::configure_service) y guarda su patrón para generar las URL a
+    /// partir del nombre con [`Context::url_for()`](crate::core::component::Context::url_for).
+    /// También aparecen en el [inventario de rutas](service::routes). Ver [`Route`](service::Route)
+    /// para más detalles.
+    fn routes(&self) -> Vec<service::Route> {
+        Vec::new()
+    }
+
     /// Configura los servicios web de la extensión, como rutas, *middleware*, acceso a ficheros
     /// estáticos, etc., usando [`ServiceConfig`](crate::service::web::ServiceConfig).
     ///
+    /// Las rutas que se añaden aquí no aparecen en el [inventario de rutas](service::routes). Para
+    /// avisarlo en el inventario hay que indicarlo con
+    /// [`has_unlisted_routes()`](Self::has_unlisted_routes).
+    ///
     /// # Ejemplo
     ///
     /// ```rust,ignore
@@ -104,11 +211,83 @@ pub trait Extension: AnyInfo + Send + Sync {
     #[allow(unused_variables)]
     fn configure_service(&self, scfg: &mut service::web::ServiceConfig) {}
 
+    /// Indica si [`configure_service()`](Self::configure_service) añade rutas que no aparecen en el
+    /// [inventario de rutas](service::routes), para avisarlo junto al inventario (ver
+    /// [`service::unlisted_routes()`]).
+    ///
+    /// Por defecto devuelve `false`. Las rutas declaradas en [`routes()`](Self::routes) y los
+    /// archivos estáticos publicados con [`static_files_service!`](crate::static_files_service) ya
+    /// aparecen en el inventario.
+    fn has_unlisted_routes(&self) -> bool {
+        false
+    }
+
+    /// Devuelve los subcomandos que la extensión añade a la línea de comandos de la aplicación.
+    ///
+    /// Se ejecutan con [`Application::cli()`](crate::app::Application::cli). Ver
+    /// [`cli`](crate::app::cli) para más detalles.
+    fn commands(&self) -> Vec<cli::Command> {
+        Vec::new()
+    }
+
+    /// Devuelve las tareas periódicas de la extensión.
+    ///
+    /// PageTop las programa al arrancar el servidor web con
+    /// [`Application::run()`](crate::app::Application::run), salvo que se desactive
+    /// [`app.scheduled_tasks`](crate::global::App::scheduled_tasks). Ver
+    /// [`schedule`](crate::core::schedule) para más detalles.
+    fn scheduled_tasks(&self) -> Vec<ScheduledTask> {
+        Vec::new()
+    }
+
+    /// Devuelve los tipos de trabajo en segundo plano que encola la extensión.
+    ///
+    /// Los trabajos se pueden encolar sin declarar su tipo, pero entonces no se reanudan los que
+    /// quedaron pendientes en [`jobs.store_path`](crate::global::Jobs::store_path) al volver a
+    /// arrancar la aplicación. Ver [`job`](crate::core::job) para más detalles.
+    fn jobs(&self) -> Vec<JobType> {
+        Vec::new()
+    }
+
+    /// Devuelve las URL que la extensión publica en el mapa del sitio (`/sitemap.xml`).
+    ///
+    /// PageTop reúne las entradas de todas las extensiones habilitadas. Si alguna devuelve entradas
+    /// al configurar el servicio web, `/robots.txt` enlaza el mapa del sitio. Ver
+    /// [`sitemap`](crate::response::sitemap) para más detalles.
+    fn sitemap(&self) -> Vec<SitemapEntry> {
+        Vec::new()
+    }
+
+    /// Devuelve los enlaces a los canales de sindicación que publica la extensión.
+    ///
+    /// PageTop los añade en la cabecera de todas las páginas para que los navegadores y lectores
+    /// puedan descubrirlos. Ver [`feed`](crate::response::feed) para más detalles.
+    fn feeds(&self) -> Vec<Link> {
+        Vec::new()
+    }
+
+    /// Devuelve las reglas que la extensión añade a `/robots.txt`.
+    ///
+    /// Sólo se publican si la configuración permite el rastreo del sitio. Ver
+    /// [`robots`](crate::response::robots) para más detalles.
+    fn robots(&self) -> Vec<RobotsRule> {
+        Vec::new()
+    }
+
+    /// Devuelve los documentos que la extensión publica en `/.well-known/`, como `security.txt`.
+    ///
+    /// Ver [`well_known`](crate::response::well_known) para más detalles.
+    fn well_known(&self) -> Vec<WellKnown> {
+        Vec::new()
+    }
+
     /// Permite declarar extensiones destinadas a deshabilitar o desinstalar recursos de otras
     /// extensiones asociadas a versiones anteriores de la aplicación.
     ///
-    /// Actualmente PageTop no utiliza este método, pero se reserva como *placeholder* para futuras
-    /// implementaciones.
+    /// PageTop no las habilita, y es un error que alguna extensión habilitada las necesite (ver
+    /// [`ExtensionGraph`](crate::core::extension::ExtensionGraph)). Las que constan como instaladas
+    /// se **desinstalan** llamando a su método [`uninstall()`](Self::uninstall), que puede eliminar
+    /// sus datos de forma irreversible.
     fn drop_extensions(&self) -> Vec<ExtensionRef> {
         vec![]
     }
@@ -0,0 +1,419 @@
+use crate::core::extension::ExtensionRef;
+use crate::{global, trace};
+
+use semver::{Version, VersionReq};
+
+use std::any::TypeId;
+
+// **< Dependency >*********************************************************************************
+
+/// Dependencia de una extensión con otra, opcional o con un requisito de versión.
+///
+/// Se declaran en [`Extension::requirements()`](crate::core::extension::Extension::requirements).
+/// Las extensiones de
+/// [`Extension::dependencies()`](crate::core::extension::Extension::dependencies) son dependencias
+/// obligatorias sin requisito de versión.
+///
+/// # Ejemplo
+///
+/// ```rust
+/// # use pagetop::prelude::*;
+/// # pub struct Storage;
+/// # impl Extension for Storage {}
+/// # pub struct Search;
+/// # impl Extension for Search {}
+/// pub struct Blog;
+///
+/// impl Extension for Blog {
+///     fn requirements(&self) -> Vec<Dependency> {
+///         vec![
+///             Dependency::required(&Storage).with_version("^1.2"),
+///             Dependency::optional(&Search),
+///         ]
+///     }
+/// }
+/// ```
+#[rustfmt::skip]
+#[derive(Clone)]
+pub struct Dependency {
+    extension: ExtensionRef,
+    version  : Option<String>,
+    optional : bool,
+}
+
+impl Dependency {
+    /// Dependencia obligatoria, que se habilita automáticamente antes que la extensión que la
+    /// declara.
+    pub fn required(extension: ExtensionRef) -> Self {
+        Dependency {
+            extension,
+            version: None,
+            optional: false,
+        }
+    }
+
+    /// Dependencia opcional, que no se habilita automáticamente. Si está habilitada, por otra
+    /// extensión o por la configuración, se carga antes que la extensión que la declara.
+    pub fn optional(extension: ExtensionRef) -> Self {
+        Dependency {
+            optional: true,
+            ..Self::required(extension)
+        }
+    }
+
+    /// Exige que la versión de la extensión cumpla el requisito indicado con la sintaxis de Cargo
+    /// (p. ej. `"^1.2"` o `">=0.3, <0.5"`). Ver [`Extension::version()`].
+    ///
+    /// [`Extension::version()`]: crate::core::extension::Extension::version
+    pub fn with_version(mut self, requirement: impl Into<String>) -> Self {
+        self.version = Some(requirement.into());
+        self
+    }
+
+    /// Devuelve la extensión de la que se depende.
+    pub fn extension(&self) -> ExtensionRef {
+        self.extension
+    }
+
+    /// Devuelve el requisito de versión, si lo hay.
+    pub fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+
+    /// Indica si la dependencia es opcional.
+    pub fn is_optional(&self) -> bool {
+        self.optional
+    }
+}
+
+// **< ExtensionGraph >*****************************************************************************
+
+/// Grafo de dependencias de las extensiones de una aplicación.
+///
+/// Antes de habilitar ninguna extensión, [`resolve()`](Self::resolve) construye el grafo completo a
+/// partir de la extensión raíz, las extensiones predefinidas y la configuración
+/// ([`[extensions]`](crate::global::Extensions)), y lo comprueba:
+///
+/// - Detecta los **ciclos** entre dependencias.
+/// - Detecta los **conflictos**, cuando una extensión descarta con
+///   [`drop_extensions()`](crate::core::extension::Extension::drop_extensions) otra que alguna
+///   extensión habilitada necesita, o cuando se deshabilita en la configuración una dependencia
+///   obligatoria.
+/// - Comprueba los **requisitos de versión** de las [dependencias](Dependency).
+///
+/// Las dependencias opcionales sólo se tienen en cuenta si la extensión está habilitada.
+///
+/// [`Application`](crate::app::Application) resuelve el grafo al prepararse, muestra en las trazas
+/// el orden de carga resultante y termina con un informe si hay problemas.
+pub struct ExtensionGraph {
+    nodes: Vec<Node>,
+    load_order: Vec<ExtensionRef>,
+    dropped: Vec<ExtensionRef>,
+    disabled: Vec<ExtensionRef>,
+    problems: Vec<String>,
+}
+
+struct Node {
+    extension: ExtensionRef,
+    dependencies: Vec<Dependency>,
+}
+
+impl ExtensionGraph {
+    /// Construye y comprueba el grafo de dependencias a partir de la extensión raíz indicada.
+    pub fn resolve(root_extension: Option<ExtensionRef>) -> Self {
+        let mut graph = ExtensionGraph {
+            nodes: Vec::new(),
+            load_order: Vec::new(),
+            dropped: Vec::new(),
+            disabled: Vec::new(),
+            problems: Vec::new(),
+        };
+        graph.add_extensions(root_extension);
+        graph.add_dropped();
+        graph.check_versions();
+        graph.sort();
+        graph
+    }
+
+    /// Devuelve las extensiones habilitadas en orden de carga, cada una después de sus
+    /// dependencias.
+    pub fn load_order(&self) -> &[ExtensionRef] {
+        &self.load_order
+    }
+
+    /// Devuelve las extensiones descartadas por las extensiones habilitadas.
+    pub fn dropped(&self) -> &[ExtensionRef] {
+        &self.dropped
+    }
+
+    /// Devuelve las extensiones deshabilitadas en la configuración.
+    pub fn disabled(&self) -> &[ExtensionRef] {
+        &self.disabled
+    }
+
+    /// Devuelve los problemas encontrados. Si hay alguno, la aplicación no debe arrancar.
+    pub fn problems(&self) -> &[String] {
+        &self.problems
+    }
+
+    // Añade las extensiones habilitadas con todas sus dependencias obligatorias.
+    fn add_extensions(&mut self, root_extension: Option<ExtensionRef>) {
+        let settings = &global::SETTINGS.extensions;
+
+        // Primero el tema básico, que necesita el propio PageTop.
+        self.add(&crate::base::theme::Basic, Some("PageTop"));
+
+        // La extensión raíz, si se proporciona.
+        if let Some(extension) = root_extension {
+            self.add(extension, None);
+        }
+
+        // La página de bienvenida predefinida si se habilita en la configuración.
+        let welcome: ExtensionRef = &crate::base::extension::Welcome;
+        if global::SETTINGS.app.welcome || is_listed(&settings.enabled, welcome) {
+            self.add(welcome, None);
+        }
+
+        // La página con el inventario de rutas si se habilita en la configuración, salvo en
+        // producción.
+        let route_inventory: ExtensionRef = &crate::base::extension::RouteInventory;
+        if global::SETTINGS.dev.routes_page || is_listed(&settings.enabled, route_inventory) {
+            if global::SETTINGS.app.run_mode == "production" {
+                trace::warn!("Route inventory page is disabled in production");
+            } else {
+                self.add(route_inventory, None);
+            }
+        }
+
+        // Las extensiones opcionales que se habilitan en la configuración. Las que se habilitan
+        // pueden declarar a su vez otras extensiones opcionales.
+        let mut optional: Vec<ExtensionRef> = vec![welcome, route_inventory];
+        let mut index = 0;
+        while index < self.nodes.len() {
+            for extension in self.nodes[index].extension.optional_extensions() {
+                if !contains(&optional, extension) {
+                    optional.push(extension);
+                    if is_listed(&settings.enabled, extension) {
+                        self.add(extension, None);
+                    }
+                }
+            }
+            index += 1;
+        }
+
+        // Comprueba que las extensiones indicadas en la configuración existen.
+        for name in &settings.enabled {
+            if !optional.iter().any(|e| has_name(*e, name)) {
+                self.problems.push(format!(
+                    "Unknown extension \"{name}\" in extensions.enabled"
+                ));
+            }
+        }
+        for name in &settings.disabled {
+            let known = self.nodes.iter().any(|node| has_name(node.extension, name))
+                || optional.iter().any(|e| has_name(*e, name))
+                || self.disabled.iter().any(|e| has_name(*e, name));
+            if !known {
+                self.problems.push(format!(
+                    "Unknown extension \"{name}\" in extensions.disabled"
+                ));
+            }
+        }
+    }
+
+    // Añade la extensión y sus dependencias obligatorias, salvo que esté deshabilitada en la
+    // configuración. Es un error deshabilitar una extensión que otra necesita (`required_by`).
+    fn add(&mut self, extension: ExtensionRef, required_by: Option<&str>) {
+        if self.node(extension).is_some() {
+            return;
+        }
+
+        if is_listed(&global::SETTINGS.extensions.disabled, extension) {
+            if !contains(&self.disabled, extension) {
+                self.disabled.push(extension);
+            }
+            match required_by {
+                Some(required_by) => self.problems.push(format!(
+                    "Extension \"{}\" is disabled in extensions.disabled but required by \"{}\"",
+                    extension.short_name(),
+                    required_by
+                )),
+                None => trace::info!(
+                    "Extension \"{}\" disabled in configuration",
+                    extension.short_name()
+                ),
+            }
+            return;
+        }
+
+        let mut dependencies: Vec<Dependency> = extension
+            .dependencies()
+            .into_iter()
+            .map(Dependency::required)
+            .collect();
+        dependencies.extend(extension.requirements());
+        self.nodes.push(Node {
+            extension,
+            dependencies: dependencies.clone(),
+        });
+
+        for dependency in dependencies.iter().filter(|d| !d.optional) {
+            self.add(dependency.extension, Some(extension.short_name()));
+        }
+    }
+
+    // Añade las extensiones descartadas, comprobando que ninguna extensión habilitada las necesita.
+    fn add_dropped(&mut self) {
+        for index in 0..self.nodes.len() {
+            let extension = self.nodes[index].extension;
+            for dropped in extension.drop_extensions() {
+                if self.node(dropped).is_none() {
+                    if !contains(&self.dropped, dropped) {
+                        self.dropped.push(dropped);
+                        trace::debug!("Extension \"{}\" dropped", dropped.short_name());
+                    }
+                } else if let Some(required_by) = self.required_by(dropped) {
+                    self.problems.push(format!(
+                        "Extension \"{}\" drops \"{}\", which is required by \"{}\"",
+                        extension.short_name(),
+                        dropped.short_name(),
+                        required_by.short_name()
+                    ));
+                } else {
+                    trace::warn!(
+                        "Trying to drop \"{}\" extension which is enabled",
+                        dropped.short_name()
+                    );
+                }
+            }
+        }
+    }
+
+    // Comprueba los requisitos de versión de las dependencias habilitadas.
+    fn check_versions(&mut self) {
+        let mut problems = Vec::new();
+        for node in &self.nodes {
+            for dependency in &node.dependencies {
+                let Some(requirement) = dependency.version() else {
+                    continue;
+                };
+                if self.node(dependency.extension).is_none() {
+                    continue;
+                }
+                let name = node.extension.short_name();
+                let target = dependency.extension.short_name();
+                let requirement = match VersionReq::parse(requirement) {
+                    Ok(requirement) => requirement,
+                    Err(e) => {
+                        problems.push(format!(
+                            "Extension \"{name}\" has an invalid version requirement \
+                             \"{requirement}\" for \"{target}\": {e}"
+                        ));
+                        continue;
+                    }
+                };
+                match dependency.extension.version().map(Version::parse) {
+                    Some(Ok(version)) if requirement.matches(&version) => {}
+                    Some(Ok(version)) => problems.push(format!(
+                        "Extension \"{name}\" requires \"{target}\" {requirement}, found {version}"
+                    )),
+                    Some(Err(e)) => problems.push(format!(
+                        "Extension \"{target}\" has an invalid version: {e}"
+                    )),
+                    None => problems.push(format!(
+                        "Extension \"{name}\" requires \"{target}\" {requirement}, but \
+                         \"{target}\" does not declare its version"
+                    )),
+                }
+            }
+        }
+        self.problems.append(&mut problems);
+    }
+
+    // Ordena las extensiones para que cada una se cargue después de sus dependencias, incluidas
+    // las opcionales que estén habilitadas, y detecta los ciclos.
+    fn sort(&mut self) {
+        let mut state = vec![Visit::Pending; self.nodes.len()];
+        let mut path = Vec::new();
+        for index in 0..self.nodes.len() {
+            self.visit(index, &mut state, &mut path);
+        }
+    }
+
+    fn visit(&mut self, index: usize, state: &mut [Visit], path: &mut Vec<usize>) {
+        match state[index] {
+            Visit::Done => return,
+            Visit::InProgress => {
+                let start = path.iter().position(|i| *i == index).unwrap_or(0);
+                let mut cycle: Vec<&str> = path[start..]
+                    .iter()
+                    .map(|i| self.nodes[*i].extension.short_name())
+                    .collect();
+                cycle.push(self.nodes[index].extension.short_name());
+                self.problems
+                    .push(format!("Dependency cycle: {}", cycle.join(" -> ")));
+                return;
+            }
+            Visit::Pending => {}
+        }
+        state[index] = Visit::InProgress;
+        path.push(index);
+        // Las dependencias se visitan en orden inverso, como se han cargado siempre.
+        let dependencies: Vec<usize> = self.nodes[index]
+            .dependencies
+            .iter()
+            .rev()
+            .filter_map(|dependency| self.position(dependency.extension))
+            .collect();
+        for dependency in dependencies {
+            self.visit(dependency, state, path);
+        }
+        path.pop();
+        state[index] = Visit::Done;
+        self.load_order.push(self.nodes[index].extension);
+    }
+
+    fn node(&self, extension: ExtensionRef) -> Option<&Node> {
+        self.position(extension).map(|index| &self.nodes[index])
+    }
+
+    fn position(&self, extension: ExtensionRef) -> Option<usize> {
+        let type_id = extension.type_id();
+        self.nodes
+            .iter()
+            .position(|node| node.extension.type_id() == type_id)
+    }
+
+    // Primera extensión habilitada que necesita obligatoriamente la extensión indicada.
+    fn required_by(&self, extension: ExtensionRef) -> Option<ExtensionRef> {
+        let type_id: TypeId = extension.type_id();
+        self.nodes
+            .iter()
+            .find(|node| {
+                node.dependencies
+                    .iter()
+                    .any(|d| !d.optional && d.extension.type_id() == type_id)
+            })
+            .map(|node| node.extension)
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Visit {
+    Pending,
+    InProgress,
+    Done,
+}
+
+// Indica si la extensión está en una lista de nombres cortos de la configuración.
+fn is_listed(names: &[String], extension: ExtensionRef) -> bool {
+    names.iter().any(|name| has_name(extension, name))
+}
+
+fn has_name(extension: ExtensionRef, name: &str) -> bool {
+    extension.short_name().eq_ignore_ascii_case(name.trim())
+}
+
+fn contains(list: &[ExtensionRef], extension: ExtensionRef) -> bool {
+    list.iter().any(|e| e.type_id() == extension.type_id())
+}
@@ -0,0 +1,122 @@
+use crate::core::extension::ExtensionRef;
+use crate::{global, trace, util};
+
+use semver::Version;
+use serde::{Deserialize, Serialize};
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{Error, Result};
+use std::path::Path;
+
+// Estado guardado de las extensiones instaladas, indexadas por su nombre corto.
+#[derive(Default, Deserialize, Serialize)]
+struct InstalledState {
+    extensions: BTreeMap<String, InstalledExtension>,
+}
+
+#[derive(Deserialize, Serialize)]
+struct InstalledExtension {
+    version: Option<String>,
+}
+
+// Ejecuta la desinstalación de las extensiones descartadas que estaban instaladas, y después la
+// instalación o actualización de las habilitadas en orden de carga. El estado se guarda después de
+// cada paso para no repetir los que ya han terminado si alguno falla. Devuelve los problemas
+// encontrados, y se detiene en el primero porque las siguientes extensiones pueden depender de él.
+pub(crate) fn run_lifecycle(enabled: &[ExtensionRef], dropped: &[ExtensionRef]) -> Vec<String> {
+    let path = global::SETTINGS.extensions.state_path.trim();
+    if path.is_empty() {
+        trace::debug!("Extension state file not configured, skipping install and upgrade");
+        return Vec::new();
+    }
+    let path = Path::new(path);
+    let mut state = match load_state(path) {
+        Ok(state) => state,
+        Err(e) => {
+            return vec![format!(
+                "Unable to read extension state file \"{}\": {e}",
+                path.display()
+            )]
+        }
+    };
+
+    for &extension in dropped {
+        let name = extension.short_name();
+        if !state.extensions.contains_key(name) {
+            continue;
+        }
+        trace::info!("Uninstalling \"{name}\" extension");
+        if let Err(e) = extension.uninstall() {
+            return vec![format!("Failed to uninstall \"{name}\" extension: {e}")];
+        }
+        state.extensions.remove(name);
+        if let Err(e) = util::write_json_atomic(path, &state) {
+            return vec![save_problem(path, e)];
+        }
+    }
+
+    for &extension in enabled {
+        let name = extension.short_name();
+        let version = extension.version();
+        let result = match state.extensions.get(name) {
+            None => {
+                trace::info!("Installing \"{name}\" extension");
+                extension.install()
+            }
+            Some(installed) => match (installed.version.as_deref(), version) {
+                (Some(from), Some(to)) if from != to => {
+                    if is_downgrade(from, to) {
+                        return vec![format!(
+                            "Extension \"{name}\" {to} is older than the installed version {from}"
+                        )];
+                    }
+                    trace::info!("Upgrading \"{name}\" extension from {from} to {to}");
+                    extension.upgrade(from)
+                }
+                (installed, current) if installed == current => continue,
+                // Sólo cambia la versión declarada, se guarda sin más.
+                _ => Ok(()),
+            },
+        };
+        if let Err(e) = result {
+            return vec![format!(
+                "Failed to install or upgrade \"{name}\" extension: {e}"
+            )];
+        }
+        state.extensions.insert(
+            name.to_owned(),
+            InstalledExtension {
+                version: version.map(str::to_owned),
+            },
+        );
+        if let Err(e) = util::write_json_atomic(path, &state) {
+            return vec![save_problem(path, e)];
+        }
+    }
+
+    Vec::new()
+}
+
+fn load_state(path: &Path) -> Result<InstalledState> {
+    if !path.exists() {
+        return Ok(InstalledState::default());
+    }
+    let content = fs::read_to_string(path)?;
+    serde_json::from_str(&content).map_err(Error::other)
+}
+
+fn save_problem(path: &Path, error: Error) -> String {
+    format!(
+        "Unable to write extension state file \"{}\": {error}",
+        path.display()
+    )
+}
+
+// Sólo se puede saber si es una versión anterior cuando las dos siguen *Semantic Versioning*.
+fn is_downgrade(from: &str, to: &str) -> bool {
+    match (Version::parse(from), Version::parse(to)) {
+        (Ok(from), Ok(to)) => to < from,
+        _ => false,
+    }
+}
@@ -0,0 +1,428 @@
+//! API para ejecutar trabajos en segundo plano.
+//!
+//! Los manejadores de las peticiones pueden delegar las tareas que no necesitan para responder,
+//! como enviar correos o reconstruir cachés, encolándolas con [`enqueue()`]. Cada tipo de trabajo
+//! es una estructura serializable que implementa [`Job`], y la cola lo ejecuta después en un hilo
+//! propio, sin bloquear la respuesta:
+//!
+//! - Se ejecutan a la vez hasta [`jobs.workers`](crate::global::Jobs::workers) trabajos.
+//! - Si un trabajo falla se reintenta hasta [`max_retries()`](Job::max_retries) veces, esperando
+//!   [`jobs.retry_delay`](crate::global::Jobs::retry_delay) antes del primer reintento y cada vez
+//!   el doble que la anterior. Si también falla el último intento se descarta, y se registra como
+//!   error en la traza de eventos con sus datos para poder repetirlo a mano.
+//! - Con [`jobs.store_path`](crate::global::Jobs::store_path) los trabajos pendientes se guardan
+//!   en un archivo y se reanudan al volver a arrancar la aplicación. Para ello las extensiones
+//!   deben declarar sus tipos de trabajo en
+//!   [`Extension::jobs()`](crate::core::extension::Extension::jobs).
+//! - Al detenerse el servidor web de [`Application::run()`](crate::app::Application::run) y al
+//!   terminar [`Application::cli()`](crate::app::Application::cli), la aplicación espera a que
+//!   terminen los trabajos pendientes con [`drain()`].
+//!
+//! Cada ejecución se registra en la traza de eventos dentro de un *span* `job` con el nombre del
+//! tipo de trabajo, su identificador y el número de intento.
+//!
+//! # Ejemplo
+//!
+//! ```rust,no_run
+//! # use pagetop::prelude::*;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Deserialize, Serialize)]
+//! pub struct SendWelcome {
+//!     email: String,
+//! }
+//!
+//! impl Job for SendWelcome {
+//!     async fn run(self) -> std::io::Result<()> {
+//!         trace::info!("Sending welcome mail to {}", self.email);
+//!         Ok(())
+//!     }
+//! }
+//!
+//! pub struct Accounts;
+//!
+//! impl Extension for Accounts {
+//!     fn jobs(&self) -> Vec<JobType> {
+//!         vec![JobType::of::<SendWelcome>()]
+//!     }
+//! }
+//!
+//! async fn register(email: String) -> std::io::Result<()> {
+//!     job::enqueue(SendWelcome { email })?;
+//!     Ok(())
+//! }
+//! ```
+
+use crate::core::extension::all as extensions;
+use crate::core::TypeInfo;
+use crate::{global, service, trace, util};
+
+use parking_lot::{Mutex, RwLock};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use tokio::sync::{Notify, Semaphore};
+use tracing::Instrument;
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::future::Future;
+use std::io::Error;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, LazyLock, OnceLock};
+use std::time::{Duration, Instant};
+
+type JobRunner = fn(Value) -> Pin<Box<dyn Future<Output = Result<(), Error>>>>;
+
+// Tipos de trabajo conocidos, indexados por su nombre.
+static JOB_TYPES: LazyLock<RwLock<HashMap<&'static str, JobRunner>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+// Cola de trabajos, que se pone en marcha la primera vez que se necesita.
+static QUEUE: OnceLock<JobQueue> = OnceLock::new();
+
+// **< Job >****************************************************************************************
+
+/// Interfaz común de los trabajos que se ejecutan en segundo plano.
+///
+/// Los datos del trabajo se serializan al encolarlo, y se vuelven a leer en cada intento.
+pub trait Job: Serialize + DeserializeOwned + 'static {
+    /// Devuelve el nombre que identifica el tipo de trabajo en la cola y en el archivo de trabajos
+    /// pendientes. Por defecto es el nombre corto del tipo.
+    fn name() -> &'static str {
+        TypeInfo::ShortName.of::<Self>()
+    }
+
+    /// Devuelve el número de reintentos si el trabajo falla. Por defecto
+    /// [`jobs.max_retries`](crate::global::Jobs::max_retries).
+    fn max_retries(&self) -> u32 {
+        global::SETTINGS.jobs.max_retries
+    }
+
+    /// Ejecuta el trabajo. Si devuelve un error se reintenta más tarde.
+    fn run(self) -> impl Future<Output = Result<(), Error>>;
+}
+
+// **< JobType >************************************************************************************
+
+/// Tipo de trabajo que declara una extensión.
+///
+/// Ver [`Extension::jobs()`](crate::core::extension::Extension::jobs).
+pub struct JobType {
+    name: &'static str,
+    runner: JobRunner,
+}
+
+impl JobType {
+    /// Declara el tipo de trabajo `J`.
+    pub fn of<J: Job>() -> Self {
+        JobType {
+            name: J::name(),
+            runner: run_as::<J>,
+        }
+    }
+
+    /// Devuelve el nombre del tipo de trabajo.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn register(self) {
+        JOB_TYPES.write().insert(self.name, self.runner);
+    }
+}
+
+fn run_as<J: Job>(payload: Value) -> Pin<Box<dyn Future<Output = Result<(), Error>>>> {
+    Box::pin(async move {
+        let job: J = serde_json::from_value(payload).map_err(Error::other)?;
+        job.run().await
+    })
+}
+
+// **< enqueue() >**********************************************************************************
+
+/// Encola un trabajo para ejecutarlo en segundo plano y devuelve su identificador.
+///
+/// Devuelve un error si el trabajo no se puede serializar, o si la aplicación está terminando y ya
+/// no acepta trabajos nuevos (ver [`drain()`]).
+pub fn enqueue<J: Job>(job: J) -> Result<u64, Error> {
+    JobType::of::<J>().register();
+    let queue = queue();
+    if queue.draining.load(Ordering::SeqCst) {
+        return Err(Error::other("Job queue is shutting down"));
+    }
+    let queued = QueuedJob {
+        id: queue.next_id.fetch_add(1, Ordering::SeqCst),
+        job: J::name().to_owned(),
+        payload: serde_json::to_value(&job).map_err(Error::other)?,
+        attempt: 0,
+        max_retries: job.max_retries(),
+    };
+    let id = queued.id;
+    queue.save(queued.clone());
+    queue.send(queued);
+    trace::debug!("Job \"{}\" #{id} enqueued", J::name());
+    Ok(id)
+}
+
+// **< drain() >************************************************************************************
+
+/// Deja de aceptar trabajos nuevos y espera a que terminen los pendientes.
+///
+/// Espera como mucho [`jobs.drain_timeout`](crate::global::Jobs::drain_timeout) segundos, y
+/// devuelve el número de trabajos que no han terminado. Si se guardan en
+/// [`jobs.store_path`](crate::global::Jobs::store_path) se reanudarán en el siguiente arranque, si
+/// no se pierden.
+///
+/// [`Application::run()`](crate::app::Application::run) y
+/// [`Application::cli()`](crate::app::Application::cli) lo llaman al terminar. Si ya se había
+/// llamado antes, devuelve los trabajos pendientes sin volver a esperar.
+pub async fn drain() -> usize {
+    let Some(queue) = QUEUE.get() else {
+        return 0;
+    };
+    if queue.draining.swap(true, Ordering::SeqCst) {
+        return queue.pending.lock().len();
+    }
+    let idle = async {
+        loop {
+            let notified = queue.idle.notified();
+            if queue.pending.lock().is_empty() {
+                break;
+            }
+            notified.await;
+        }
+    };
+    let timeout = Duration::from_secs(global::SETTINGS.jobs.drain_timeout);
+    let _ = service::rt::time::timeout(timeout, idle).await;
+
+    let remaining = queue.pending.lock().len();
+    if remaining > 0 {
+        if queue.store.is_some() {
+            trace::info!("{remaining} pending job(s) saved, they will resume on next start");
+        } else {
+            trace::warn!("{remaining} pending job(s) discarded on shutdown");
+        }
+    }
+    remaining
+}
+
+// Pone en marcha la cola de trabajos para reanudar los pendientes.
+pub(crate) fn start() {
+    queue();
+}
+
+// Devuelve la cola de trabajos, poniéndola en marcha la primera vez. Los trabajos recuperados se
+// envían a ejecutar sólo cuando la cola ya está disponible para los trabajadores.
+fn queue() -> &'static JobQueue {
+    let mut started = false;
+    let queue = QUEUE.get_or_init(|| {
+        started = true;
+        JobQueue::start()
+    });
+    if started {
+        queue.resume();
+    }
+    queue
+}
+
+// **< JobQueue >***********************************************************************************
+
+#[derive(Clone, Deserialize, Serialize)]
+struct QueuedJob {
+    id: u64,
+    job: String,
+    payload: Value,
+    attempt: u32,
+    max_retries: u32,
+}
+
+// Trabajos pendientes guardados en el archivo de trabajos.
+#[derive(Default, Deserialize, Serialize)]
+struct StoredJobs {
+    jobs: Vec<QueuedJob>,
+}
+
+struct JobQueue {
+    sender: UnboundedSender<QueuedJob>,
+    // Trabajos encolados, en ejecución o a la espera de un reintento.
+    pending: Mutex<BTreeMap<u64, QueuedJob>>,
+    next_id: AtomicU64,
+    store: Option<PathBuf>,
+    idle: Notify,
+    draining: AtomicBool,
+}
+
+impl JobQueue {
+    fn start() -> Self {
+        for job_type in extensions::job_types() {
+            job_type.register();
+        }
+
+        let path = global::SETTINGS.jobs.store_path.trim();
+        let store = (!path.is_empty()).then(|| PathBuf::from(path));
+        let recovered = match store.as_deref().map(load_jobs).transpose() {
+            Ok(stored) => stored.unwrap_or_default().jobs,
+            Err(e) => {
+                trace::error!("Unable to read job store file \"{path}\": {e}");
+                Vec::new()
+            }
+        };
+
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let workers = global::SETTINGS.jobs.workers.max(1);
+        std::thread::Builder::new()
+            .name("pagetop-jobs".to_owned())
+            .spawn(move || service::rt::System::new().block_on(run_workers(receiver, workers)))
+            .expect("Failed to start the job queue thread");
+
+        let next_id = recovered.iter().map(|job| job.id + 1).max().unwrap_or(1);
+        JobQueue {
+            sender,
+            pending: Mutex::new(recovered.into_iter().map(|job| (job.id, job)).collect()),
+            next_id: AtomicU64::new(next_id),
+            store,
+            idle: Notify::new(),
+            draining: AtomicBool::new(false),
+        }
+    }
+
+    // Envía a ejecutar los trabajos pendientes recuperados al ponerse en marcha la cola.
+    fn resume(&self) {
+        let recovered: Vec<QueuedJob> = self.pending.lock().values().cloned().collect();
+        if !recovered.is_empty() {
+            trace::info!("Resuming {} pending job(s)", recovered.len());
+        }
+        for job in recovered {
+            self.send(job);
+        }
+    }
+
+    fn send(&self, job: QueuedJob) {
+        // El receptor vive tanto como la aplicación.
+        let _ = self.sender.send(job);
+    }
+
+    // Guarda el trabajo como pendiente, o actualiza su número de intentos.
+    fn save(&self, job: QueuedJob) {
+        let mut pending = self.pending.lock();
+        pending.insert(job.id, job);
+        self.persist(&pending);
+    }
+
+    // El trabajo ha terminado, o se ha descartado.
+    fn finish(&self, id: u64) {
+        let mut pending = self.pending.lock();
+        pending.remove(&id);
+        self.persist(&pending);
+        if pending.is_empty() {
+            self.idle.notify_waiters();
+        }
+    }
+
+    fn persist(&self, pending: &BTreeMap<u64, QueuedJob>) {
+        if let Some(path) = &self.store {
+            let stored = StoredJobs {
+                jobs: pending.values().cloned().collect(),
+            };
+            if let Err(e) = util::write_json_atomic(path, &stored) {
+                trace::error!("Unable to write job store file \"{}\": {e}", path.display());
+            }
+        }
+    }
+}
+
+// Ejecuta los trabajos recibidos, como mucho `workers` a la vez.
+async fn run_workers(mut receiver: UnboundedReceiver<QueuedJob>, workers: usize) {
+    let slots = Arc::new(Semaphore::new(workers));
+    while let Some(job) = receiver.recv().await {
+        let Ok(permit) = Arc::clone(&slots).acquire_owned().await else {
+            break;
+        };
+        let span = trace::info_span!("job", job = %job.job, id = job.id, attempt = job.attempt + 1);
+        service::rt::spawn(
+            async move {
+                run_job(job).await;
+                drop(permit);
+            }
+            .instrument(span),
+        );
+    }
+}
+
+async fn run_job(mut job: QueuedJob) {
+    // Los trabajos sólo se envían cuando la cola ya está en marcha.
+    let Some(queue) = QUEUE.get() else {
+        trace::error!(
+            "Job \"{}\" #{} received before the job queue started",
+            job.job,
+            job.id
+        );
+        return;
+    };
+    let runner = JOB_TYPES.read().get(job.job.as_str()).copied();
+    let Some(runner) = runner else {
+        dead_letter(&job, "unknown job type");
+        queue.finish(job.id);
+        return;
+    };
+
+    job.attempt += 1;
+    let started = Instant::now();
+    match runner(job.payload.clone()).await {
+        Ok(()) => {
+            trace::info!(
+                "Job \"{}\" #{} finished in {:?}",
+                job.job,
+                job.id,
+                started.elapsed()
+            );
+            queue.finish(job.id);
+        }
+        Err(e) if job.attempt <= job.max_retries => {
+            let delay = retry_delay(job.attempt);
+            trace::warn!(
+                "Job \"{}\" #{} failed, retrying in {delay:?}: {e}",
+                job.job,
+                job.id
+            );
+            queue.save(job.clone());
+            service::rt::spawn(async move {
+                service::rt::time::sleep(delay).await;
+                queue.send(job);
+            });
+        }
+        Err(e) => {
+            dead_letter(&job, &e.to_string());
+            queue.finish(job.id);
+        }
+    }
+}
+
+// Registra el trabajo descartado con sus datos, para poder repetirlo a mano.
+fn dead_letter(job: &QueuedJob, reason: &str) {
+    trace::error!(
+        "Job \"{}\" #{} discarded after {} attempt(s): {reason}. Payload: {}",
+        job.job,
+        job.id,
+        job.attempt,
+        job.payload
+    );
+}
+
+// Espera antes del reintento indicado, el doble cada vez.
+fn retry_delay(attempt: u32) -> Duration {
+    let factor = 1u64 << attempt.saturating_sub(1).min(16);
+    Duration::from_millis(global::SETTINGS.jobs.retry_delay.saturating_mul(factor))
+}
+
+fn load_jobs(path: &Path) -> Result<StoredJobs, Error> {
+    if !path.exists() {
+        return Ok(StoredJobs::default());
+    }
+    let content = fs::read_to_string(path)?;
+    serde_json::from_str(&content).map_err(Error::other)
+}
@@ -0,0 +1,337 @@
+//! API para programar tareas periódicas desde las extensiones.
+//!
+//! Las extensiones declaran sus tareas implementando
+//! [`Extension::scheduled_tasks()`](crate::core::extension::Extension::scheduled_tasks), que
+//! devuelve una lista de [`ScheduledTask`]. Cada tarea tiene un nombre único, una programación
+//! [`Schedule`] por intervalo o con una expresión *cron*, y una función asíncrona que la ejecuta.
+//!
+//! Las tareas se programan en el *runtime* de actix al arrancar el servidor web con
+//! [`Application::run()`](crate::app::Application::run):
+//!
+//! - Una tarea nunca se solapa consigo misma. Si llega la hora de ejecutarla y la ejecución
+//!   anterior no ha terminado, se omite con un aviso.
+//! - Con [`with_jitter()`](ScheduledTask::with_jitter) cada ejecución se retrasa un tiempo
+//!   aleatorio, para repartir la carga de tareas programadas a la misma hora.
+//! - Cada ejecución se registra en la traza de eventos dentro de un *span* `scheduled_task` con el
+//!   nombre de la tarea y el número de ejecución, incluyendo su duración y si ha fallado.
+//!
+//! Si se ejecutan varias instancias de la aplicación, se puede desactivar la programación en
+//! algunas de ellas con [`app.scheduled_tasks`](crate::global::App::scheduled_tasks). El subcomando
+//! `task` de la [línea de comandos](crate::app::cli) lista las tareas o ejecuta una de ellas en el
+//! momento, esté o no desactivada la programación.
+//!
+//! # Ejemplo
+//!
+//! ```rust,no_run
+//! # use pagetop::prelude::*;
+//! use std::time::Duration;
+//!
+//! pub struct Blog;
+//!
+//! impl Extension for Blog {
+//!     fn scheduled_tasks(&self) -> Vec<ScheduledTask> {
+//!         vec![
+//!             ScheduledTask::new("blog-cache", Schedule::every(Duration::from_secs(300)))
+//!                 .with_jitter(Duration::from_secs(30))
+//!                 .to(refresh_cache),
+//!             ScheduledTask::new("blog-digest", Schedule::cron("0 8 * * mon").unwrap())
+//!                 .to(send_digest),
+//!         ]
+//!     }
+//! }
+//!
+//! async fn refresh_cache() -> std::io::Result<()> {
+//!     Ok(())
+//! }
+//!
+//! async fn send_digest() -> std::io::Result<()> {
+//!     Ok(())
+//! }
+//! ```
+
+mod cron;
+use cron::Cron;
+pub use cron::CronError;
+
+use crate::core::extension::all as extensions;
+use crate::{global, service, trace};
+
+use chrono::{DateTime, Local};
+use tracing::Instrument;
+
+use std::cell::Cell;
+use std::collections::HashSet;
+use std::fmt;
+use std::future::Future;
+use std::io::{Error, ErrorKind};
+use std::pin::Pin;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+type TaskHandler = Rc<dyn Fn() -> Pin<Box<dyn Future<Output = Result<(), Error>>>>>;
+
+// **< Schedule >***********************************************************************************
+
+/// Programación de una tarea periódica.
+#[derive(Clone, Debug)]
+pub struct Schedule(ScheduleKind);
+
+#[derive(Clone, Debug)]
+enum ScheduleKind {
+    Every(Duration),
+    Cron(Cron, String),
+}
+
+impl Schedule {
+    /// Programa la tarea cada intervalo indicado, empezando un intervalo después de arrancar.
+    ///
+    /// # Panics
+    ///
+    /// Si el intervalo es nulo.
+    pub fn every(interval: Duration) -> Self {
+        assert!(!interval.is_zero(), "Schedule interval must not be zero");
+        Schedule(ScheduleKind::Every(interval))
+    }
+
+    /// Programa la tarea con una expresión *cron* de cinco campos: minuto, hora, día del mes, mes
+    /// y día de la semana, en la hora local.
+    ///
+    /// Cada campo admite `*`, valores, rangos (`1-5`), pasos (`*/15`) y listas (`1,15`). Los meses
+    /// y los días de la semana también admiten sus nombres en inglés abreviados (`jan`, `mon`). El
+    /// domingo es `0` o `7`. Como en *cron*, si se restringen tanto el día del mes como el de la
+    /// semana, basta con que coincida uno de los dos. También se aceptan las abreviaturas
+    /// `@yearly`, `@monthly`, `@weekly`, `@daily` y `@hourly`.
+    ///
+    /// Devuelve [`CronError`] si la expresión no es válida.
+    pub fn cron(expression: &str) -> Result<Self, CronError> {
+        let cron = Cron::parse(expression)?;
+        Ok(Schedule(ScheduleKind::Cron(
+            cron,
+            expression.trim().to_owned(),
+        )))
+    }
+
+    /// Devuelve el siguiente instante programado después del indicado, o `None` si no hay
+    /// ninguno.
+    pub fn next_after(&self, after: DateTime<Local>) -> Option<DateTime<Local>> {
+        match &self.0 {
+            ScheduleKind::Every(interval) => {
+                after.checked_add_signed(chrono::Duration::from_std(*interval).ok()?)
+            }
+            ScheduleKind::Cron(cron, _) => cron.next_after(after),
+        }
+    }
+}
+
+impl fmt::Display for Schedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.0 {
+            ScheduleKind::Every(interval) => {
+                let secs = interval.as_secs();
+                if interval.subsec_nanos() != 0 {
+                    write!(f, "every {}ms", interval.as_millis())
+                } else if secs % 3600 == 0 {
+                    write!(f, "every {}h", secs / 3600)
+                } else if secs % 60 == 0 {
+                    write!(f, "every {}m", secs / 60)
+                } else {
+                    write!(f, "every {secs}s")
+                }
+            }
+            ScheduleKind::Cron(_, expression) => f.write_str(expression),
+        }
+    }
+}
+
+// **< ScheduledTask >******************************************************************************
+
+/// Tarea periódica que aporta una extensión.
+///
+/// Ver [`Extension::scheduled_tasks()`](crate::core::extension::Extension::scheduled_tasks).
+pub struct ScheduledTask {
+    name: String,
+    schedule: Schedule,
+    jitter: Duration,
+    handler: Option<TaskHandler>,
+    owner: &'static str,
+}
+
+impl ScheduledTask {
+    /// Crea una tarea con el nombre y la programación indicados.
+    pub fn new(name: impl Into<String>, schedule: Schedule) -> Self {
+        ScheduledTask {
+            name: name.into(),
+            schedule,
+            jitter: Duration::ZERO,
+            handler: None,
+            owner: "",
+        }
+    }
+
+    /// Retrasa cada ejecución un tiempo aleatorio entre cero y el indicado.
+    pub fn with_jitter(mut self, jitter: Duration) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Asigna la función asíncrona que ejecuta la tarea.
+    pub fn to<F, Fut>(mut self, handler: F) -> Self
+    where
+        F: Fn() -> Fut + 'static,
+        Fut: Future<Output = Result<(), Error>> + 'static,
+    {
+        self.handler = Some(Rc::new(move || Box::pin(handler())));
+        self
+    }
+
+    /// Devuelve el nombre de la tarea.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Devuelve la programación de la tarea.
+    pub fn schedule(&self) -> &Schedule {
+        &self.schedule
+    }
+
+    /// Devuelve el retraso aleatorio máximo de cada ejecución.
+    pub fn jitter(&self) -> Duration {
+        self.jitter
+    }
+
+    /// Devuelve el nombre corto de la extensión que declara la tarea.
+    pub fn owner(&self) -> &'static str {
+        self.owner
+    }
+
+    pub(crate) fn with_owner(mut self, owner: &'static str) -> Self {
+        self.owner = owner;
+        self
+    }
+}
+
+// **< Tareas programadas >*************************************************************************
+
+// Tareas de las extensiones habilitadas, descartando las que repiten el nombre de otra.
+pub(crate) fn tasks() -> Vec<ScheduledTask> {
+    let mut names = HashSet::new();
+    extensions::scheduled_tasks()
+        .into_iter()
+        .filter(|task| {
+            let unique = names.insert(task.name.clone());
+            if !unique {
+                trace::warn!(
+                    "Scheduled task \"{}\" is already declared, ignoring",
+                    task.name
+                );
+            }
+            unique
+        })
+        .collect()
+}
+
+// Programa las tareas de las extensiones en el *runtime* de actix, salvo que esté desactivado.
+pub(crate) fn start() {
+    if !global::SETTINGS.app.scheduled_tasks {
+        trace::info!("Scheduled tasks are disabled in this instance");
+        return;
+    }
+    for task in tasks() {
+        let Some(handler) = task.handler.clone() else {
+            trace::warn!("Scheduled task \"{}\" has no handler, ignoring", task.name);
+            continue;
+        };
+        trace::info!("Scheduling task \"{}\" ({})", task.name, task.schedule);
+        service::rt::spawn(schedule_loop(task, handler));
+    }
+}
+
+// Ejecuta ahora la tarea con el nombre indicado.
+pub(crate) async fn run_now(name: &str) -> Result<(), Error> {
+    let task = tasks()
+        .into_iter()
+        .find(|task| task.name == name)
+        .ok_or_else(|| {
+            Error::new(
+                ErrorKind::NotFound,
+                format!("Scheduled task \"{name}\" not found"),
+            )
+        })?;
+    let Some(handler) = task.handler else {
+        return Err(Error::new(
+            ErrorKind::Unsupported,
+            format!("Scheduled task \"{name}\" has no handler"),
+        ));
+    };
+    let span = trace::info_span!("scheduled_task", task = %task.name, run = "manual");
+    run_task(&task.name, &handler).instrument(span).await
+}
+
+async fn schedule_loop(task: ScheduledTask, handler: TaskHandler) {
+    let running = Rc::new(Cell::new(false));
+    let mut run: u64 = 0;
+    loop {
+        let now = Local::now();
+        let Some(next) = task.schedule.next_after(now) else {
+            trace::warn!("Scheduled task \"{}\" has no next run, stopping", task.name);
+            return;
+        };
+        let delay = (next - now).to_std().unwrap_or_default() + random_jitter(task.jitter);
+        service::rt::time::sleep(delay).await;
+
+        if running.get() {
+            trace::warn!(
+                "Scheduled task \"{}\" is still running, skipping this run",
+                task.name
+            );
+            continue;
+        }
+        run += 1;
+        running.set(true);
+        let guard = RunningGuard(Rc::clone(&running));
+        let name = task.name.clone();
+        let handler = Rc::clone(&handler);
+        let span = trace::info_span!("scheduled_task", task = %name, run);
+        service::rt::spawn(
+            async move {
+                let _guard = guard;
+                let _ = run_task(&name, &handler).await;
+            }
+            .instrument(span),
+        );
+    }
+}
+
+async fn run_task(name: &str, handler: &TaskHandler) -> Result<(), Error> {
+    let started = Instant::now();
+    trace::debug!("Running scheduled task \"{name}\"");
+    let result = handler().await;
+    match &result {
+        Ok(()) => trace::info!(
+            "Scheduled task \"{name}\" finished in {:?}",
+            started.elapsed()
+        ),
+        Err(e) => trace::error!(
+            "Scheduled task \"{name}\" failed after {:?}: {e}",
+            started.elapsed()
+        ),
+    }
+    result
+}
+
+fn random_jitter(jitter: Duration) -> Duration {
+    if jitter.is_zero() {
+        return Duration::ZERO;
+    }
+    let max = u64::try_from(jitter.as_millis()).unwrap_or(u64::MAX);
+    Duration::from_millis(fastrand::u64(0..=max))
+}
+
+// Marca la tarea como terminada al acabar la ejecución, incluso si la función entra en pánico.
+struct RunningGuard(Rc<Cell<bool>>);
+
+impl Drop for RunningGuard {
+    fn drop(&mut self) {
+        self.0.set(false);
+    }
+}
@@ -0,0 +1,208 @@
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, TimeZone, Timelike};
+
+use std::fmt;
+
+// Número máximo de días que se buscan hacia delante, suficiente para cubrir cualquier 29 de
+// febrero.
+const MAX_DAYS_AHEAD: i64 = 366 * 8;
+
+const MONTH_NAMES: [&str; 12] = [
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+
+const WEEKDAY_NAMES: [&str; 7] = ["sun", "mon", "tue", "wed", "thu", "fri", "sat"];
+
+// **< CronError >**********************************************************************************
+
+/// Error al interpretar una expresión *cron*.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CronError {
+    expression: String,
+    message: String,
+}
+
+impl CronError {
+    fn new(expression: &str, message: impl Into<String>) -> Self {
+        CronError {
+            expression: expression.to_owned(),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for CronError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid cron expression \"{}\": {}",
+            self.expression, self.message
+        )
+    }
+}
+
+impl std::error::Error for CronError {}
+
+// **< Cron >***************************************************************************************
+
+// Expresión cron de cinco campos (minuto, hora, día del mes, mes y día de la semana). Cada campo se
+// guarda como un mapa de bits con los valores permitidos.
+#[derive(Clone, Debug)]
+pub(super) struct Cron {
+    minutes: u64,
+    hours: u64,
+    days_of_month: u64,
+    months: u64,
+    days_of_week: u64,
+    // Si alguno de los días no admite todos los valores, basta con que coincida uno de los dos,
+    // como en cron. Se decide por los valores y no por el texto, así `*/1` equivale a `*`.
+    any_day_of_month: bool,
+    any_day_of_week: bool,
+}
+
+impl Cron {
+    pub(super) fn parse(expression: &str) -> Result<Self, CronError> {
+        let expanded = match expression.trim() {
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            "@monthly" => "0 0 1 * *",
+            "@weekly" => "0 0 * * 0",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@hourly" => "0 * * * *",
+            other => other,
+        };
+        let fields: Vec<&str> = expanded.split_whitespace().collect();
+        let [minute, hour, day_of_month, month, day_of_week] = fields[..] else {
+            return Err(CronError::new(
+                expression,
+                format!("expected 5 fields, found {}", fields.len()),
+            ));
+        };
+        let field = |value, min, max, names: &[&str], name: &str| {
+            parse_field(value, min, max, names)
+                .map_err(|message| CronError::new(expression, format!("{name}: {message}")))
+        };
+        // El domingo puede ser 0 o 7.
+        let mut days_of_week = field(day_of_week, 0, 7, &WEEKDAY_NAMES, "day of week")?;
+        if days_of_week & (1 << 7) != 0 {
+            days_of_week |= 1;
+        }
+        let days_of_month = field(day_of_month, 1, 31, &[], "day of month")?;
+        Ok(Cron {
+            minutes: field(minute, 0, 59, &[], "minute")?,
+            hours: field(hour, 0, 23, &[], "hour")?,
+            days_of_month,
+            months: field(month, 1, 12, &MONTH_NAMES, "month")?,
+            days_of_week,
+            any_day_of_month: days_of_month & all(1, 31) == all(1, 31),
+            any_day_of_week: days_of_week & all(0, 6) == all(0, 6),
+        })
+    }
+
+    // Siguiente instante que cumple la expresión, estrictamente posterior al indicado.
+    pub(super) fn next_after(&self, after: DateTime<Local>) -> Option<DateTime<Local>> {
+        // Primer minuto completo posterior al instante indicado, sin segundos ni fracciones.
+        let start = after.naive_local() + Duration::minutes(1);
+        let start = start
+            .with_second(0)
+            .and_then(|start| start.with_nanosecond(0))
+            .unwrap_or(start);
+        let start_date = start.date();
+        for offset in 0..MAX_DAYS_AHEAD {
+            let date = start_date + Duration::days(offset);
+            if !self.matches_date(date) {
+                continue;
+            }
+            for hour in 0..24 {
+                if !contains(self.hours, hour) {
+                    continue;
+                }
+                for minute in 0..60 {
+                    if !contains(self.minutes, minute) {
+                        continue;
+                    }
+                    let Some(time) = date.and_hms_opt(hour, minute, 0) else {
+                        continue;
+                    };
+                    if time < start {
+                        continue;
+                    }
+                    // Las horas que no existen por el cambio de horario se saltan.
+                    if let Some(next) = Local.from_local_datetime(&time).earliest() {
+                        return Some(next);
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    fn matches_date(&self, date: NaiveDate) -> bool {
+        if !contains(self.months, date.month()) {
+            return false;
+        }
+        let day_of_month = contains(self.days_of_month, date.day());
+        let day_of_week = contains(self.days_of_week, date.weekday().num_days_from_sunday());
+        match (self.any_day_of_month, self.any_day_of_week) {
+            (true, true) => true,
+            (false, true) => day_of_month,
+            (true, false) => day_of_week,
+            (false, false) => day_of_month || day_of_week,
+        }
+    }
+}
+
+fn contains(bits: u64, value: u32) -> bool {
+    bits & (1 << value) != 0
+}
+
+// Mapa de bits con todos los valores entre `min` y `max`, ambos incluidos.
+fn all(min: u32, max: u32) -> u64 {
+    (min..=max).fold(0, |bits, value| bits | 1 << value)
+}
+
+// Interpreta un campo con listas (`1,15`), rangos (`1-5`), pasos (`*/10`, `0-30/5`) y nombres.
+fn parse_field(field: &str, min: u32, max: u32, names: &[&str]) -> Result<u64, String> {
+    let mut bits = 0;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => match step.parse::<u32>() {
+                Ok(step) if step > 0 => (range, step),
+                _ => return Err(format!("invalid step \"{step}\"")),
+            },
+            None => (part, 1),
+        };
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((start, end)) = range.split_once('-') {
+            (
+                parse_value(start, min, max, names)?,
+                parse_value(end, min, max, names)?,
+            )
+        } else {
+            let value = parse_value(range, min, max, names)?;
+            // Con paso, un valor inicial llega hasta el final (p. ej. `5/15`).
+            (value, if step > 1 { max } else { value })
+        };
+        if start > end {
+            return Err(format!("invalid range \"{range}\""));
+        }
+        for value in (start..=end).step_by(step as usize) {
+            bits |= 1 << value;
+        }
+    }
+    Ok(bits)
+}
+
+fn parse_value(value: &str, min: u32, max: u32, names: &[&str]) -> Result<u32, String> {
+    let lowercase = value.to_ascii_lowercase();
+    let parsed = match names.iter().position(|name| *name == lowercase) {
+        // Los nombres de los meses empiezan en 1 y los de los días de la semana en 0.
+        Some(index) => index as u32 + min,
+        None => value
+            .parse::<u32>()
+            .map_err(|_| format!("invalid value \"{value}\""))?,
+    };
+    if parsed < min || parsed > max {
+        return Err(format!("value {parsed} out of range {min}-{max}"));
+    }
+    Ok(parsed)
+}
@@ -49,7 +49,7 @@ use crate::{util, AutoDefault};
 ///
 /// El tema decide qué regiones mostrar en el cuerpo del documento, normalmente usando una plantilla
 /// ([`Template`]) al renderizar la página ([`Page`](crate::response::page::Page)).
-pub trait Region {
+pub trait Region: RegionRender {
     /// Devuelve el nombre de la región.
     ///
     /// Este nombre es el identificador lógico de la región y se usa como clave en el [`Context`]
@@ -103,6 +103,23 @@ pub trait Region {
 /// Referencia estática a una región.
 pub type RegionRef = &'static dyn Region;
 
+/// Renderiza una región a partir de su referencia estática ([`RegionRef`]).
+///
+/// Se implementa automáticamente para cualquier tipo que implemente [`Region`], llamando a su método
+/// [`Region::render()`], y permite renderizar las regiones de las que sólo se conoce su referencia,
+/// por ejemplo al enviar la página por fragmentos.
+pub trait RegionRender {
+    /// Renderiza el contenedor de la región con [`Region::render()`].
+    fn render_ref(&'static self, cx: &mut Context) -> Markup;
+}
+
+impl<R: Region> RegionRender for R {
+    #[inline]
+    fn render_ref(&'static self, cx: &mut Context) -> Markup {
+        self.render(cx)
+    }
+}
+
 // **< DefaultRegion >******************************************************************************
 
 /// Regiones básicas que PageTop proporciona por defecto.
@@ -180,6 +197,25 @@ pub trait Template {
             (DefaultRegion::Footer.render(cx))
         }
     }
+
+    /// Devuelve las regiones de la plantilla en el orden en que deben enviarse al renderizar la
+    /// página por fragmentos ([`Page::stream()`](crate::response::page::Page::stream)).
+    ///
+    /// También permite resolver por su nombre las regiones solicitadas en una petición de
+    /// renderizado parcial ([`Fragment`](crate::response::page::Fragment)). Si devuelve `None`, se
+    /// buscan entre las regiones de [`DefaultRegion`].
+    ///
+    /// Si devuelve `Some` y el tema lo habilita con
+    /// [`Theme::stream_by_regions()`](crate::core::theme::Theme::stream_by_regions), cada región
+    /// se renderiza con [`Region::render()`] y se envía al cliente en cuanto está lista, sin pasar
+    /// por [`Theme::render_page_body()`](crate::core::theme::Theme::render_page_body). En otro
+    /// caso, el cuerpo completo se envía en un único fragmento tal y como lo genera el tema.
+    ///
+    /// Las plantillas que sobrescriban [`Self::render()`] sólo deberían devolver `Some` si la
+    /// secuencia de regiones reproduce fielmente el marcado de su propio renderizado.
+    fn stream_regions(&'static self) -> Option<Vec<RegionRef>> {
+        None
+    }
 }
 
 /// Referencia estática a una plantilla.
@@ -193,7 +229,8 @@ pub enum DefaultTemplate {
     /// Plantilla predeterminada.
     ///
     /// Utiliza la implementación por defecto de [`Template::render()`] y se emplea cuando no se
-    /// selecciona ninguna otra plantilla explícitamente.
+    /// selecciona ninguna otra plantilla explícitamente. Al renderizar por fragmentos envía cada
+    /// región básica por separado si el tema lo habilita.
     #[default]
     Standard,
 
@@ -204,7 +241,15 @@ pub enum DefaultTemplate {
     Error,
 }
 
-impl Template for DefaultTemplate {}
+impl Template for DefaultTemplate {
+    fn stream_regions(&'static self) -> Option<Vec<RegionRef>> {
+        Some(vec![
+            &DefaultRegion::Header,
+            &DefaultRegion::Content,
+            &DefaultRegion::Footer,
+        ])
+    }
+}
 
 // **< render_component! >**************************************************************************
 
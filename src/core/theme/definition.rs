@@ -1,5 +1,6 @@
 use crate::base::component::{Html, Intro, IntroOpening};
 use crate::core::component::{ChildOp, Component, ComponentError, Context, Contextual};
+use crate::core::extension::all::feed_links;
 use crate::core::extension::Extension;
 use crate::core::theme::{DefaultRegion, DefaultTemplate, TemplateRef};
 use crate::global;
@@ -116,6 +117,20 @@ pub trait Theme: Extension + Send + Sync {
         }
     }
 
+    /// Indica si [`Page::stream()`](crate::response::page::Page::stream) puede enviar por separado
+    /// las regiones que declara la plantilla con
+    /// [`Template::stream_regions()`](crate::core::theme::Template::stream_regions).
+    ///
+    /// Al enviar cada región por separado no se llama a
+    /// [`render_page_body()`](Self::render_page_body), por eso los temas que lo sobrescriban no
+    /// deberían habilitarlo. La implementación por defecto devuelve `false` y la página se envía
+    /// con el cuerpo completo que genera el tema. No se hereda del tema padre, cada tema tiene que
+    /// habilitarlo expresamente.
+    #[inline]
+    fn stream_by_regions(&self) -> bool {
+        false
+    }
+
     /// Acciones específicas del tema después de renderizar el `<body>` de la página.
     ///
     /// Se invoca tras la generación del contenido del `<body>`. Es útil para:
@@ -148,7 +163,11 @@ pub trait Theme: Extension + Send + Sync {
     /// - La etiqueta `viewport` básica para diseño adaptable.
     /// - Los metadatos (`name`/`content`) y propiedades (`property`/`content`) declarados en la
     ///   página.
+    /// - Los elementos `<link>` declarados en la página (URL canónica, versiones alternativas,
+    ///   paginación, etc.).
+    /// - Los metadatos de Open Graph y de tarjeta de Twitter/X, si la página los define.
     /// - Los *assets* registrados en el contexto de la página.
+    /// - Los datos estructurados (JSON-LD) acumulados en el contexto de la página.
     ///
     /// Los temas pueden sobrescribir este método para añadir etiquetas adicionales (por ejemplo,
     /// *favicons* personalizados, manifest, etiquetas de analítica, etc.).
@@ -172,15 +191,36 @@ pub trait Theme: Extension + Send + Sync {
 
             meta name="viewport" content=(viewport);
             @for (name, content) in page.metadata() {
-                meta name=(name) content=(content) {}
+                @if let Some(content) = content.lookup(&*page) {
+                    meta name=(name) content=(content) {}
+                }
             }
 
             meta http-equiv="X-UA-Compatible" content="IE=edge";
             @for (property, content) in page.properties() {
-                meta property=(property) content=(content) {}
+                @if let Some(content) = content.lookup(&*page) {
+                    meta property=(property) content=(content) {}
+                }
+            }
+
+            @for link in page.links() {
+                (link.render())
+            }
+            @for link in feed_links() {
+                @if !page.links().iter().any(|l| l.href() == link.href()) {
+                    (link.render())
+                }
+            }
+
+            @if let Some(open_graph) = page.open_graph() {
+                (open_graph.render(page))
+            }
+            @if let Some(twitter_card) = page.twitter_card() {
+                (twitter_card.render(page))
             }
 
             (page.context().render_assets())
+            (page.context().render_structured_data())
         }
     }
 
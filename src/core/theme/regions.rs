@@ -63,6 +63,13 @@ impl ChildrenInRegions {
 
     /// Construye una lista de componentes frescos para la región indicada.
     ///
+    /// Equivale a [`children_for_name()`](Self::children_for_name) con el nombre de la región.
+    pub fn children_for(&mut self, theme_ref: ThemeRef, region_ref: RegionRef) -> Children {
+        self.children_for_name(theme_ref, region_ref.name())
+    }
+
+    /// Construye una lista de componentes frescos para la región con el nombre indicado.
+    ///
     /// El orden es: prototipos globales comunes → children propios de la página →
     /// prototipos específicos del tema activo.
     ///
@@ -72,8 +79,7 @@ impl ChildrenInRegions {
     /// clonarse).
     ///
     /// [`ComponentClone`]: crate::core::component::ComponentClone
-    pub fn children_for(&mut self, theme_ref: ThemeRef, region_ref: RegionRef) -> Children {
-        let name = region_ref.name();
+    pub fn children_for_name(&mut self, theme_ref: ThemeRef, name: &str) -> Children {
         let common = COMMON_REGIONS.read();
         let themed = THEME_REGIONS.read();
 
@@ -102,6 +108,34 @@ impl ChildrenInRegions {
 
         result
     }
+
+    /// Devuelve una copia del primer componente con el identificador dado en cualquier región.
+    ///
+    /// Busca primero entre los children propios de la página, después entre los prototipos
+    /// globales comunes y, por último, entre los prototipos específicos del tema activo. Sólo se
+    /// consideran los componentes de primer nivel de cada región que tengan un identificador
+    /// explícito.
+    pub fn child_by_id(&self, theme_ref: ThemeRef, id: &str) -> Option<Child> {
+        let common = COMMON_REGIONS.read();
+        let themed = THEME_REGIONS.read();
+
+        let find_proto = |protos: &Vec<Arc<dyn ComponentGlobal>>| {
+            protos
+                .iter()
+                .map(|proto| proto.as_child())
+                .find(|child| child.id().as_deref() == Some(id))
+        };
+
+        self.0
+            .values()
+            .find_map(|children| children.get_by_id(id).cloned())
+            .or_else(|| common.values().find_map(find_proto))
+            .or_else(|| {
+                themed
+                    .get(&theme_ref.type_id())
+                    .and_then(|theme_map| theme_map.values().find_map(find_proto))
+            })
+    }
 }
 
 // *************************************************************************************************
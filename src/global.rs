@@ -7,6 +7,9 @@ use serde::Deserialize;
 mod lang_negotiation;
 pub use lang_negotiation::LangNegotiation;
 
+mod robots_policy;
+pub use robots_policy::RobotsPolicy;
+
 mod startup_banner;
 pub use startup_banner::StartupBanner;
 
@@ -26,9 +29,25 @@ include_config!(SETTINGS: Settings => [
     "app.lang_negotiation"    => "Full",
     "app.startup_banner"      => "Slant",
     "app.welcome"             => true,
+    "app.robots"              => "Auto",
+    "app.watch_config"        => false,
+    "app.scheduled_tasks"     => true,
 
     // [dev]
     "dev.pagetop_static_dir"  => "",
+    "dev.routes_page"         => false,
+
+    // [extensions]
+    "extensions.enabled"      => Vec::<String>::new(),
+    "extensions.disabled"     => Vec::<String>::new(),
+    "extensions.state_path"   => "",
+
+    // [jobs]
+    "jobs.workers"            => 4,
+    "jobs.max_retries"        => 3,
+    "jobs.retry_delay"        => 1_000,
+    "jobs.drain_timeout"      => 30,
+    "jobs.store_path"         => "",
 
     // [log]
     "log.enabled"             => true,
@@ -47,11 +66,14 @@ include_config!(SETTINGS: Settings => [
 // **< Settings >***********************************************************************************
 
 #[derive(Debug, Deserialize)]
-/// Tipos para las secciones globales [`[app]`](App), [`[dev]`](Dev), [`[log]`](Log) y
-/// [`[server]`](Server) de [`SETTINGS`].
+/// Tipos para las secciones globales [`[app]`](App), [`[dev]`](Dev),
+/// [`[extensions]`](Extensions), [`[jobs]`](Jobs), [`[log]`](Log) y [`[server]`](Server) de
+/// [`SETTINGS`].
 pub struct Settings {
     pub app: App,
     pub dev: Dev,
+    pub extensions: Extensions,
+    pub jobs: Jobs,
     pub log: Log,
     pub server: Server,
 }
@@ -88,8 +110,19 @@ pub struct App {
     /// Activa la página de bienvenida de PageTop.
     ///
     /// Si está activada, se instala la extensión [`Welcome`](crate::base::extension::Welcome), que
-    /// ofrece una página de bienvenida predefinida en `"/"`.
+    /// ofrece una página de bienvenida predefinida en `"/"`. Ver también [`Extensions`].
     pub welcome: bool,
+    /// Política para los rastreadores en `/robots.txt`: *"Auto"*, *"Allow"* o *"Disallow"*.
+    ///
+    /// Con *"Auto"* sólo se permite el rastreo si el modo de ejecución es *"production"*. Ver
+    /// [`RobotsPolicy`] para los modos disponibles.
+    pub robots: RobotsPolicy,
+    /// Vigila los archivos de configuración mientras el servidor web está en marcha y, si cambian,
+    /// recarga los [ajustes dinámicos](crate::config::DynamicSetting).
+    pub watch_config: bool,
+    /// Programa las [tareas periódicas](crate::core::schedule) de las extensiones al arrancar el
+    /// servidor web. Se puede desactivar en las instancias que no deban ejecutarlas.
+    pub scheduled_tasks: bool,
     /// Modo de ejecución, dado por la variable de entorno `PAGETOP_RUN_MODE`, o *"default"* si no
     /// está definido.
     pub run_mode: String,
@@ -107,6 +140,59 @@ pub struct Dev {
     ///
     /// Si la cadena está vacía, se ignora este ajuste.
     pub pagetop_static_dir: String,
+    /// Activa la página de desarrollo con el inventario de rutas.
+    ///
+    /// Si está activada y el modo de ejecución no es *"production"*, se instala la extensión
+    /// [`RouteInventory`](crate::base::extension::RouteInventory), que muestra en `/dev/routes` las
+    /// rutas configuradas en el servicio web.
+    pub routes_page: bool,
+}
+
+#[derive(Debug, Deserialize)]
+/// Sección `[extensions]` de la configuración. Forma parte de [`Settings`].
+///
+/// Las extensiones se indican por su nombre corto, sin distinguir mayúsculas y minúsculas (p. ej.
+/// `disabled = ["Welcome"]`). Los nombres desconocidos, o deshabilitar una extensión que otra
+/// habilitada necesita, son errores de configuración que impiden arrancar la aplicación.
+pub struct Extensions {
+    /// Extensiones opcionales que se habilitan, como las que declaran otras extensiones en
+    /// [`optional_extensions()`](crate::core::extension::Extension::optional_extensions) o las
+    /// predefinidas [`Welcome`](crate::base::extension::Welcome) y
+    /// [`RouteInventory`](crate::base::extension::RouteInventory).
+    pub enabled: Vec<String>,
+    /// Extensiones que no se habilitan aunque se declaren en el código o en la configuración.
+    pub disabled: Vec<String>,
+    /// Archivo donde se guardan las extensiones instaladas y su versión (p. ej.
+    /// *"data/extensions.json"*), para llamar sólo cuando corresponde a
+    /// [`install()`](crate::core::extension::Extension::install),
+    /// [`upgrade()`](crate::core::extension::Extension::upgrade) y
+    /// [`uninstall()`](crate::core::extension::Extension::uninstall).
+    ///
+    /// Si la cadena está vacía no se guarda el estado y no se llama a ninguno de estos métodos.
+    pub state_path: String,
+}
+
+#[derive(Debug, Deserialize)]
+/// Sección `[jobs]` de la configuración. Forma parte de [`Settings`].
+///
+/// Ajustes de la cola de [trabajos en segundo plano](crate::core::job).
+pub struct Jobs {
+    /// Número máximo de trabajos que se ejecutan a la vez (al menos uno).
+    pub workers: usize,
+    /// Número de reintentos de un trabajo que falla, salvo que el trabajo indique otro en
+    /// [`max_retries()`](crate::core::job::Job::max_retries).
+    pub max_retries: u32,
+    /// Espera en milisegundos antes del primer reintento (p. ej., `1_000` para un segundo). Cada
+    /// reintento siguiente espera el doble que el anterior.
+    pub retry_delay: u64,
+    /// Tiempo máximo en segundos que se espera al parar la aplicación para que terminen los
+    /// trabajos pendientes.
+    pub drain_timeout: u64,
+    /// Archivo donde se guardan los trabajos pendientes (p. ej. *"data/jobs.json"*), para
+    /// reanudarlos al volver a arrancar la aplicación.
+    ///
+    /// Si la cadena está vacía los trabajos sólo se guardan en memoria.
+    pub store_path: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -117,6 +203,10 @@ pub struct Log {
     /// Opciones, o combinación de opciones separadas por comas, para filtrar las trazas: *"Error"*,
     /// *"Warn"*, *"Info"*, *"Debug"* o *"Trace"*.
     /// Ejemplo: *"Error,actix_server::builder=Info,tracing_actix_web=Debug"*.
+    ///
+    /// Es un ajuste dinámico, el filtro se actualiza al
+    /// [recargar la configuración](crate::config::reload) sin reiniciar la aplicación. Este campo
+    /// conserva el valor con el que arrancó la aplicación.
     pub tracing: String,
     /// Muestra los mensajes de traza en el terminal (*"Stdout"*) o los vuelca en archivos con
     /// rotación: *"Daily"*, *"Hourly"*, *"Minutely"* o *"Endless"*.
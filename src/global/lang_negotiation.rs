@@ -1,6 +1,6 @@
 use crate::AutoDefault;
 
-use serde::{Deserialize, Deserializer};
+use serde::{de, Deserialize, Deserializer};
 
 /// Modos disponibles para negociar el idioma de una petición HTTP.
 ///
@@ -40,15 +40,10 @@ impl<'de> Deserialize<'de> for LangNegotiation {
             "noquery" => Self::NoQuery,
             "configonly" => Self::ConfigOnly,
             _ => {
-                let default = Self::default();
-                println!(
-                    concat!(
-                        "\nInvalid value \"{}\" for [app].lang_negotiation. ",
-                        "Using \"{:?}\". Check settings.",
-                    ),
-                    raw, default,
-                );
-                default
+                return Err(de::Error::unknown_variant(
+                    &raw,
+                    &["Full", "NoQuery", "ConfigOnly"],
+                ))
             }
         };
         Ok(result)
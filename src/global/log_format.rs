@@ -1,6 +1,6 @@
 use crate::AutoDefault;
 
-use serde::{Deserialize, Deserializer};
+use serde::{de, Deserialize, Deserializer};
 
 /// Formatos disponibles para mostrar las trazas.
 ///
@@ -35,15 +35,10 @@ impl<'de> Deserialize<'de> for LogFormat {
             "compact" => Self::Compact,
             "pretty" => Self::Pretty,
             _ => {
-                let default = Self::default();
-                println!(
-                    concat!(
-                        "\nInvalid value \"{}\" for [log].format. ",
-                        "Using \"{:?}\". Check settings.",
-                    ),
-                    raw, default,
-                );
-                default
+                return Err(de::Error::unknown_variant(
+                    &raw,
+                    &["Json", "Full", "Compact", "Pretty"],
+                ))
             }
         };
         Ok(result)
@@ -1,6 +1,6 @@
 use crate::AutoDefault;
 
-use serde::{Deserialize, Deserializer};
+use serde::{de, Deserialize, Deserializer};
 
 /// Modos de salida y rotación para el registro de trazas.
 ///
@@ -35,15 +35,10 @@ impl<'de> Deserialize<'de> for LogRolling {
             "minutely" => Self::Minutely,
             "endless" => Self::Endless,
             _ => {
-                let default = Self::default();
-                println!(
-                    concat!(
-                        "\nInvalid value \"{}\" for [log].rolling. ",
-                        "Using \"{:?}\". Check settings.",
-                    ),
-                    raw, default,
-                );
-                default
+                return Err(de::Error::unknown_variant(
+                    &raw,
+                    &["Stdout", "Daily", "Hourly", "Minutely", "Endless"],
+                ))
             }
         };
         Ok(result)
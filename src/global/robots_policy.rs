@@ -0,0 +1,54 @@
+use crate::AutoDefault;
+
+use serde::{de, Deserialize, Deserializer};
+
+/// Políticas disponibles para indicar a los rastreadores qué pueden indexar.
+///
+/// El ajuste [`global::SETTINGS.app.robots`](crate::global::App::robots) determina el contenido de
+/// `/robots.txt` (ver [`robots`](crate::response::robots)).
+#[derive(AutoDefault, Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RobotsPolicy {
+    /// Depende del modo de ejecución: en *"production"* se comporta como `RobotsPolicy::Allow` y en
+    /// cualquier otro modo como `RobotsPolicy::Disallow`. Evita así que se indexen por error los
+    /// entornos de desarrollo o de pruebas. Es el comportamiento por defecto.
+    #[default]
+    Auto,
+
+    /// Permite el rastreo según las reglas aportadas por las extensiones.
+    Allow,
+
+    /// Impide el rastreo de todo el sitio, ignorando las reglas de las extensiones.
+    Disallow,
+}
+
+impl RobotsPolicy {
+    /// Indica si se permite el rastreo del sitio en el modo de ejecución dado.
+    pub fn allows_crawling(&self, run_mode: &str) -> bool {
+        match self {
+            Self::Auto => run_mode == "production",
+            Self::Allow => true,
+            Self::Disallow => false,
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for RobotsPolicy {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let raw = String::deserialize(deserializer)?;
+        let result = match raw.trim().to_ascii_lowercase().as_str() {
+            "auto" => Self::Auto,
+            "allow" => Self::Allow,
+            "disallow" => Self::Disallow,
+            _ => {
+                return Err(de::Error::unknown_variant(
+                    &raw,
+                    &["Auto", "Allow", "Disallow"],
+                ))
+            }
+        };
+        Ok(result)
+    }
+}
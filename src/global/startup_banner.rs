@@ -1,6 +1,6 @@
 use crate::AutoDefault;
 
-use serde::{Deserialize, Deserializer};
+use serde::{de, Deserialize, Deserializer};
 
 /// Opciones para el *banner* ASCII mostrado al arrancar la aplicación.
 ///
@@ -34,15 +34,10 @@ impl<'de> Deserialize<'de> for StartupBanner {
             "speed" => Self::Speed,
             "starwars" => Self::Starwars,
             _ => {
-                let default = Self::default();
-                println!(
-                    concat!(
-                        "\nInvalid value \"{}\" for [app].startup_banner. ",
-                        "Using \"{:?}\". Check settings.",
-                    ),
-                    raw, default,
-                );
-                default
+                return Err(de::Error::unknown_variant(
+                    &raw,
+                    &["Off", "Slant", "Small", "Speed", "Starwars"],
+                ))
             }
         };
         Ok(result)
@@ -3,7 +3,7 @@
 mod maud;
 pub use maud::{display, html, html_private, Escaper, Markup, PreEscaped, DOCTYPE};
 
-mod route;
+pub(crate) mod route;
 pub use route::RoutePath;
 
 // **< HTML DOCUMENT ASSETS >***********************************************************************
@@ -14,9 +14,15 @@ pub use assets::javascript::JavaScript;
 pub use assets::stylesheet::{StyleSheet, TargetMedia};
 pub use assets::{Asset, Assets};
 
+mod link;
+pub use link::{Link, LinkRel};
+
 mod logo;
 pub use logo::PageTopSvg;
 
+mod structured_data;
+pub use structured_data::StructuredData;
+
 // **< HTML ATTRIBUTES >****************************************************************************
 
 mod attr;
@@ -82,8 +82,23 @@ impl<T: Asset> Assets<T> {
         }
     }
 
+    /// Devuelve un iterador sobre los nombres de los recursos del conjunto.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().map(|a| a.name())
+    }
+
+    /// Renderiza los recursos del conjunto en orden ascendente de peso.
     pub fn render(&self, cx: &mut Context) -> Markup {
-        let mut assets = self.0.iter().collect::<Vec<_>>();
+        self.render_except(cx, &[])
+    }
+
+    /// Renderiza los recursos del conjunto excepto los indicados por nombre.
+    pub(crate) fn render_except(&self, cx: &mut Context, names: &[String]) -> Markup {
+        let mut assets = self
+            .0
+            .iter()
+            .filter(|a| !names.iter().any(|n| n == a.name()))
+            .collect::<Vec<_>>();
         assets.sort_by_key(|a| a.weight());
         html! {
             @for a in assets {
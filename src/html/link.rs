@@ -0,0 +1,176 @@
+use crate::html::{html, Markup};
+use crate::CowStr;
+
+/// Relación (`rel`) de un enlace [`Link`] con el documento.
+#[derive(Clone, Debug, PartialEq)]
+pub enum LinkRel {
+    /// URL canónica del documento (`rel="canonical"`).
+    Canonical,
+    /// Versión alternativa del documento, por ejemplo en otro idioma o formato
+    /// (`rel="alternate"`).
+    Alternate,
+    /// Documento anterior en una serie paginada (`rel="prev"`).
+    Prev,
+    /// Documento siguiente en una serie paginada (`rel="next"`).
+    Next,
+    /// Origen al que el navegador puede conectarse por adelantado (`rel="preconnect"`).
+    Preconnect,
+    /// Origen cuyo nombre de dominio puede resolverse por adelantado (`rel="dns-prefetch"`).
+    DnsPrefetch,
+    /// Manifiesto de la aplicación web (`rel="manifest"`).
+    Manifest,
+    /// Cualquier otra relación.
+    Custom(CowStr),
+}
+
+impl LinkRel {
+    /// Devuelve el valor del atributo `rel`.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Canonical => "canonical",
+            Self::Alternate => "alternate",
+            Self::Prev => "prev",
+            Self::Next => "next",
+            Self::Preconnect => "preconnect",
+            Self::DnsPrefetch => "dns-prefetch",
+            Self::Manifest => "manifest",
+            Self::Custom(rel) => rel,
+        }
+    }
+}
+
+/// Elemento `<link>` para la cabecera del documento.
+///
+/// Representa enlaces que relacionan el documento con otros recursos, como la URL canónica, las
+/// versiones en otros idiomas, la paginación o el manifiesto de la aplicación. A diferencia de las
+/// hojas de estilo ([`StyleSheet`](crate::html::StyleSheet)) o el *favicon*
+/// ([`Favicon`](crate::html::Favicon)), no son recursos que el navegador cargue para mostrar la
+/// página, sino información sobre el documento.
+///
+/// # Ejemplo
+///
+/// ```rust
+/// # use pagetop::prelude::*;
+/// let canonical = Link::canonical("https://example.com/blog/hello");
+/// let english = Link::alternate("https://example.com/en/blog/hello").with_hreflang("en");
+/// let fonts = Link::preconnect("https://fonts.example.com").with_crossorigin(true);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Link {
+    rel: LinkRel,
+    href: CowStr,
+    hreflang: Option<CowStr>,
+    mime: Option<CowStr>,
+    title: Option<CowStr>,
+    crossorigin: bool,
+}
+
+impl Link {
+    /// Crea un enlace con la relación y la URL indicadas.
+    pub fn new(rel: LinkRel, href: impl Into<CowStr>) -> Self {
+        Link {
+            rel,
+            href: href.into(),
+            hreflang: None,
+            mime: None,
+            title: None,
+            crossorigin: false,
+        }
+    }
+
+    /// Crea un enlace a la URL canónica del documento.
+    pub fn canonical(href: impl Into<CowStr>) -> Self {
+        Self::new(LinkRel::Canonical, href)
+    }
+
+    /// Crea un enlace a una versión alternativa del documento.
+    ///
+    /// Suele completarse con [`with_hreflang()`](Self::with_hreflang) para otros idiomas o con
+    /// [`with_type()`](Self::with_type) para otros formatos.
+    pub fn alternate(href: impl Into<CowStr>) -> Self {
+        Self::new(LinkRel::Alternate, href)
+    }
+
+    /// Crea un enlace al documento anterior en una serie paginada.
+    pub fn prev(href: impl Into<CowStr>) -> Self {
+        Self::new(LinkRel::Prev, href)
+    }
+
+    /// Crea un enlace al documento siguiente en una serie paginada.
+    pub fn next(href: impl Into<CowStr>) -> Self {
+        Self::new(LinkRel::Next, href)
+    }
+
+    /// Crea un enlace para conectar por adelantado con otro origen.
+    pub fn preconnect(href: impl Into<CowStr>) -> Self {
+        Self::new(LinkRel::Preconnect, href)
+    }
+
+    /// Crea un enlace para resolver por adelantado el dominio de otro origen.
+    pub fn dns_prefetch(href: impl Into<CowStr>) -> Self {
+        Self::new(LinkRel::DnsPrefetch, href)
+    }
+
+    /// Crea un enlace al manifiesto de la aplicación web.
+    pub fn manifest(href: impl Into<CowStr>) -> Self {
+        Self::new(LinkRel::Manifest, href)
+    }
+
+    // **< Link BUILDER >***************************************************************************
+
+    /// Establece el idioma del recurso enlazado (`hreflang`), por ejemplo `"es"` o `"x-default"`.
+    pub fn with_hreflang(mut self, hreflang: impl Into<CowStr>) -> Self {
+        self.hreflang = Some(hreflang.into());
+        self
+    }
+
+    /// Establece el tipo MIME del recurso enlazado (`type`).
+    pub fn with_type(mut self, mime: impl Into<CowStr>) -> Self {
+        self.mime = Some(mime.into());
+        self
+    }
+
+    /// Establece un título descriptivo para el enlace (`title`).
+    pub fn with_title(mut self, title: impl Into<CowStr>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Indica si el recurso debe solicitarse en modo CORS (`crossorigin`).
+    pub fn with_crossorigin(mut self, crossorigin: bool) -> Self {
+        self.crossorigin = crossorigin;
+        self
+    }
+
+    // **< Link GETTERS >***************************************************************************
+
+    /// Devuelve la relación del enlace.
+    pub fn rel(&self) -> &LinkRel {
+        &self.rel
+    }
+
+    /// Devuelve la URL del recurso enlazado.
+    pub fn href(&self) -> &str {
+        &self.href
+    }
+
+    /// Devuelve el idioma del recurso enlazado, si se ha definido.
+    pub fn hreflang(&self) -> Option<&str> {
+        self.hreflang.as_deref()
+    }
+
+    // **< Link RENDER >****************************************************************************
+
+    /// Renderiza el elemento `<link>`.
+    pub fn render(&self) -> Markup {
+        html! {
+            link
+                rel=(self.rel.as_str())
+                href=(self.href)
+                hreflang=[self.hreflang.as_deref()]
+                type=[self.mime.as_deref()]
+                title=[self.title.as_deref()]
+                crossorigin[self.crossorigin];
+        }
+    }
+}
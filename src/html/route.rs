@@ -1,14 +1,21 @@
+use crate::service::HttpRequest;
 use crate::{builder_fn, AutoDefault, CowStr};
 
-use std::fmt;
+use std::fmt::{self, Write};
 
 /// Representa una ruta como un *path* inicial más una lista opcional de parámetros.
 ///
-/// Modela rutas del estilo `/path/to/resource?foo=bar&debug` o `https://example.com/path?foo=bar`,
-/// pensadas para usarse en atributos HTML como `href`, `action` o `src`.
+/// Modela rutas del estilo `/path/to/resource?foo=bar&debug#section` o
+/// `https://example.com/path?foo=bar`, pensadas para usarse en atributos HTML como `href`, `action`
+/// o `src`.
 ///
-/// `RoutePath` no valida ni interpreta la estructura del *path*; simplemente concatena los
-/// parámetros de consulta sobre el valor proporcionado.
+/// `RoutePath` guarda el *path*, los parámetros y el fragmento decodificados, y los codifica
+/// (*percent-encoding*) al mostrarlo. En el *path* se codifican los caracteres que no pueden
+/// aparecer en una URL, como los espacios o los caracteres no ASCII, pero se mantienen las
+/// secuencias `%XX` que ya tenga. Las claves y valores de los parámetros y el fragmento se
+/// codifican siempre, por lo que pueden contener `&`, `=`, `#` o espacios sin corromper la URL.
+/// Para añadir segmentos que puedan contener `/` o `?` se puede usar
+/// [`with_segment()`](Self::with_segment).
 ///
 /// # Ejemplos
 ///
@@ -24,8 +31,15 @@ use std::fmt;
 /// // Ruta absoluta a un recurso externo.
 /// let external = RoutePath::new("https://example.com/export").with_param("format", "csv");
 /// assert_eq!(external.to_string(), "https://example.com/export?format=csv");
+///
+/// // Valores y segmentos codificados.
+/// let encoded = RoutePath::new("/tags")
+///     .with_segment("C/C++")
+///     .with_param("q", "tom & jerry")
+///     .with_fragment("año 2025");
+/// assert_eq!(encoded.to_string(), "/tags/C%2FC++?q=tom%20%26%20jerry#a%C3%B1o%202025");
 /// ```
-#[derive(AutoDefault)]
+#[derive(AutoDefault, Clone, Debug)]
 pub struct RoutePath {
     /// *Path* inicial sobre el que se añadirán los parámetros.
     ///
@@ -38,19 +52,61 @@ pub struct RoutePath {
     /// Cada clave es única y se mantiene el orden de inserción. El valor vacío se utiliza para
     /// representar *flags* sin valor explícito (por ejemplo `?debug`).
     query: indexmap::IndexMap<String, String>,
+
+    /// Fragmento opcional de la ruta (la parte tras `#`), sin codificar.
+    fragment: Option<String>,
 }
 
 impl RoutePath {
     /// Crea un `RoutePath` a partir de un *path* inicial.
     ///
-    /// Por ejemplo: `RoutePath::new("/about")`.
+    /// Por ejemplo: `RoutePath::new("/about")`. El *path* se guarda tal cual; para interpretar
+    /// también los parámetros y el fragmento de una URL completa se usa [`parse()`](Self::parse).
     pub fn new(path: impl Into<CowStr>) -> Self {
         Self {
             path: path.into(),
             query: indexmap::IndexMap::new(),
+            fragment: None,
+        }
+    }
+
+    /// Crea un `RoutePath` interpretando una URL con parámetros de consulta y fragmento.
+    ///
+    /// El *path*, los parámetros y el fragmento se decodifican (`%XX`, y `+` como espacio en los
+    /// parámetros). En el *path* se mantienen codificados `%2F`, `%3F`, `%23` y `%25`, porque
+    /// decodificarlos cambiaría la ruta. Los parámetros sin valor se guardan como *flags*, y si una
+    /// clave se repite prevalece el último valor.
+    ///
+    /// ```rust
+    /// # use pagetop::prelude::*;
+    /// let route = RoutePath::parse("/search?q=tom+%26+jerry&page=2&debug#results");
+    /// assert_eq!(route.path(), "/search");
+    /// assert_eq!(route.param("q"), Some("tom & jerry"));
+    /// assert_eq!(route.param("debug"), Some(""));
+    /// assert_eq!(route.fragment(), Some("results"));
+    ///
+    /// let route = RoutePath::parse("/docs/my%20file/a%2Fb");
+    /// assert_eq!(route.path(), "/docs/my file/a%2Fb");
+    /// assert_eq!(route.to_string(), "/docs/my%20file/a%2Fb");
+    /// ```
+    pub fn parse(url: &str) -> Self {
+        let (url, fragment) = match url.split_once('#') {
+            Some((url, fragment)) => (url, Some(decode(fragment, false))),
+            None => (url, None),
+        };
+        let (path, query) = url.split_once('?').unwrap_or((url, ""));
+
+        let mut route = Self::new(decode_path(path));
+        for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            route.query.insert(decode(key, true), decode(value, true));
         }
+        route.fragment = fragment;
+        route
     }
 
+    // **< RoutePath BUILDER >**********************************************************************
+
     /// Añade o sustituye un parámetro `key=value`. Si la clave ya existe, el valor se sobrescribe.
     #[builder_fn]
     pub fn with_param(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
@@ -58,6 +114,19 @@ impl RoutePath {
         self
     }
 
+    /// Añade o sustituye varios parámetros a la vez, por ejemplo para combinar filtros.
+    #[builder_fn]
+    pub fn with_params<K, V>(mut self, params: impl IntoIterator<Item = (K, V)>) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        for (key, value) in params {
+            self.query.insert(key.into(), value.into());
+        }
+        self
+    }
+
     /// Añade o sustituye un *flag* sin valor, por ejemplo `?debug`.
     #[builder_fn]
     pub fn with_flag(mut self, flag: impl Into<String>) -> Self {
@@ -65,29 +134,95 @@ impl RoutePath {
         self
     }
 
-    /// Devuelve el *path* inicial tal y como se pasó a [`RoutePath::new`], sin parámetros.
+    /// Añade un segmento al final del *path*, codificando cualquier carácter reservado, incluido
+    /// `/`.
+    ///
+    /// Útil para construir rutas con valores arbitrarios, como identificadores o etiquetas.
+    #[builder_fn]
+    pub fn with_segment(mut self, segment: impl AsRef<str>) -> Self {
+        let mut path = self.path.trim_end_matches('/').to_owned();
+        path.push('/');
+        encode_segment(&mut path, segment.as_ref(), false);
+        self.path = path.into();
+        self
+    }
+
+    /// Establece el fragmento de la ruta (la parte tras `#`), o lo elimina con una cadena vacía.
+    #[builder_fn]
+    pub fn with_fragment(mut self, fragment: impl Into<String>) -> Self {
+        let fragment = fragment.into();
+        self.fragment = (!fragment.is_empty()).then_some(fragment);
+        self
+    }
+
+    /// Devuelve la ruta sin el parámetro `key`, si existía.
+    ///
+    /// Útil para generar enlaces que quitan un filtro o vuelven a la primera página.
+    pub fn without_param(mut self, key: &str) -> Self {
+        self.remove_param(key);
+        self
+    }
+
+    /// Elimina el parámetro `key` de la ruta, si existe.
+    pub fn remove_param(&mut self, key: &str) -> &mut Self {
+        self.query.shift_remove(key);
+        self
+    }
+
+    /// Elimina todos los parámetros de la ruta.
+    pub fn clear_params(&mut self) -> &mut Self {
+        self.query.clear();
+        self
+    }
+
+    // **< RoutePath GETTERS >**********************************************************************
+
+    /// Devuelve el *path* inicial sin parámetros, tal y como se pasó a [`RoutePath::new`] o
+    /// decodificado si se obtuvo con [`parse()`](Self::parse) o de una petición.
     pub fn path(&self) -> &str {
         &self.path
     }
+
+    /// Devuelve el valor del parámetro `key`, o una cadena vacía si es un *flag*.
+    pub fn param(&self, key: &str) -> Option<&str> {
+        self.query.get(key).map(String::as_str)
+    }
+
+    /// Devuelve los parámetros de la ruta en orden de inserción.
+    pub fn params(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.query
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+    }
+
+    /// Devuelve el fragmento de la ruta, si existe.
+    pub fn fragment(&self) -> Option<&str> {
+        self.fragment.as_deref()
+    }
 }
 
 impl fmt::Display for RoutePath {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.write_str(&self.path)?;
+        let mut url = String::with_capacity(self.path.len());
+        encode(&mut url, &self.path, is_path_char);
         if !self.query.is_empty() {
-            f.write_str("?")?;
+            url.push('?');
             for (i, (key, value)) in self.query.iter().enumerate() {
                 if i > 0 {
-                    f.write_str("&")?;
+                    url.push('&');
                 }
-                f.write_str(key)?;
+                encode(&mut url, key, is_query_char);
                 if !value.is_empty() {
-                    f.write_str("=")?;
-                    f.write_str(value)?;
+                    url.push('=');
+                    encode(&mut url, value, is_query_char);
                 }
             }
         }
-        Ok(())
+        if let Some(fragment) = &self.fragment {
+            url.push('#');
+            encode(&mut url, fragment, |c| is_query_char(c) || c == b'?');
+        }
+        f.write_str(&url)
     }
 }
 
@@ -102,3 +237,143 @@ impl From<String> for RoutePath {
         RoutePath::new(path)
     }
 }
+
+impl From<&HttpRequest> for RoutePath {
+    /// Crea un `RoutePath` con el *path* y los parámetros de consulta de la petición,
+    /// decodificados igual que con [`parse()`](Self::parse).
+    fn from(request: &HttpRequest) -> Self {
+        let mut route = Self::parse(&format!("?{}", request.query_string()));
+        route.path = decode_path(request.uri().path()).into();
+        route
+    }
+}
+
+// **< Codificación >*******************************************************************************
+
+// Caracteres que se mantienen en el *path*: todos los imprimibles ASCII salvo los que nunca son
+// válidos en una URL. Se conservan `%`, `?` y `#` para no alterar rutas ya codificadas o completas.
+fn is_path_char(c: u8) -> bool {
+    c.is_ascii_graphic()
+        && !matches!(
+            c,
+            b'"' | b'<' | b'>' | b'\\' | b'^' | b'`' | b'{' | b'|' | b'}'
+        )
+}
+
+// Caracteres que se mantienen en claves, valores y fragmentos: los no reservados y los que no
+// tienen significado especial dentro de un parámetro.
+fn is_query_char(c: u8) -> bool {
+    c.is_ascii_alphanumeric()
+        || matches!(
+            c,
+            b'-' | b'.'
+                | b'_'
+                | b'~'
+                | b'/'
+                | b':'
+                | b'@'
+                | b'!'
+                | b'$'
+                | b'\''
+                | b'('
+                | b')'
+                | b'*'
+                | b','
+                | b';'
+        )
+}
+
+// Caracteres que se mantienen en un segmento del *path*.
+fn is_segment_char(c: u8) -> bool {
+    c.is_ascii_alphanumeric()
+        || matches!(
+            c,
+            b'-' | b'.'
+                | b'_'
+                | b'~'
+                | b':'
+                | b'@'
+                | b'!'
+                | b'$'
+                | b'&'
+                | b'\''
+                | b'('
+                | b')'
+                | b'*'
+                | b'+'
+                | b','
+                | b';'
+                | b'='
+        )
+}
+
+fn encode(out: &mut String, text: &str, keep: impl Fn(u8) -> bool) {
+    for &byte in text.as_bytes() {
+        if keep(byte) {
+            out.push(byte as char);
+        } else {
+            let _ = write!(out, "%{byte:02X}");
+        }
+    }
+}
+
+/// Codifica un valor como segmento de un *path*. Si `keep_slash` es `true`, el valor puede ocupar
+/// varios segmentos y se mantienen las `/`.
+pub(crate) fn encode_segment(out: &mut String, segment: &str, keep_slash: bool) {
+    encode(out, segment, |c| {
+        is_segment_char(c) || (keep_slash && c == b'/')
+    });
+}
+
+// Decodifica las secuencias `%XX` de un *path*, salvo las de `/`, `?`, `#` y `%`, que cambiarían la
+// ruta al mostrarla de nuevo. Si el resultado no es UTF-8 válido, devuelve el *path* sin cambios.
+fn decode_path(path: &str) -> String {
+    let bytes = path.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) if !matches!(byte, b'/' | b'?' | b'#' | b'%') => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8(decoded).unwrap_or_else(|_| path.to_owned())
+}
+
+// Decodifica las secuencias `%XX` y, si `plus_as_space` es `true`, también `+` como espacio.
+fn decode(text: &str, plus_as_space: bool) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (b'+', _) if plus_as_space => {
+                decoded.push(b' ');
+                i += 1;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
@@ -0,0 +1,158 @@
+use crate::html::{html, Markup, PreEscaped};
+use crate::trace;
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+// Vocabulario por defecto de los datos estructurados.
+const SCHEMA_ORG: &str = "https://schema.org";
+
+/// Datos estructurados (JSON-LD) de un documento.
+///
+/// Acumula las entidades de [schema.org](https://schema.org) que aportan los componentes, los temas
+/// o las extensiones al renderizar una página, y las renderiza en un único bloque
+/// `<script type="application/ld+json">`.
+///
+/// Las entidades con el mismo identificador (`@id`) se combinan en una sola: las propiedades de la
+/// última contribución sustituyen a las anteriores y las nuevas se añaden. Las entidades sin `@id`
+/// se añaden tal cual. Si hay más de una entidad, se agrupan en un `@graph`.
+///
+/// El JSON se escapa para que ningún contenido pueda cerrar el elemento `<script>` antes de tiempo.
+///
+/// # Ejemplo
+///
+/// ```rust
+/// # use pagetop::prelude::*;
+/// #[derive(serde::Serialize)]
+/// struct Organization {
+///     #[serde(rename = "@type")]
+///     kind: &'static str,
+///     name: &'static str,
+///     url: &'static str,
+/// }
+///
+/// let mut data = StructuredData::default();
+/// data.add(Organization {
+///     kind: "Organization",
+///     name: "PageTop",
+///     url: "https://pagetop.cillero.es",
+/// });
+/// assert_eq!(data.len(), 1);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct StructuredData {
+    entities: Vec<Value>,
+    // Número de contribuciones tras las que se modificó cada entidad por última vez.
+    changed: Vec<usize>,
+    revision: usize,
+}
+
+impl StructuredData {
+    /// Añade una entidad serializable, combinándola con la existente si comparten `@id`.
+    ///
+    /// Los valores que no sean objetos JSON o que no puedan serializarse se descartan registrando
+    /// un aviso.
+    pub fn add(&mut self, data: impl Serialize) {
+        match serde_json::to_value(data) {
+            Ok(Value::Object(entity)) => self.merge(entity),
+            Ok(other) => trace::warn!("Ignoring structured data that is not an object: {other}"),
+            Err(e) => trace::warn!("Ignoring structured data that cannot be serialized: {e}"),
+        }
+    }
+
+    /// Devuelve el número de entidades.
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    /// Indica si no hay ninguna entidad.
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    /// Devuelve las entidades acumuladas.
+    pub fn entities(&self) -> &[Value] {
+        &self.entities
+    }
+
+    /// Renderiza todas las entidades en un bloque `<script type="application/ld+json">`.
+    pub fn render(&self) -> Markup {
+        Self::render_entities(&self.entities)
+    }
+
+    // Número de contribuciones recibidas hasta ahora, para usar con `render_from()`.
+    pub(crate) fn revision(&self) -> usize {
+        self.revision
+    }
+
+    // Renderiza las entidades añadidas o modificadas después de la revisión `from`. Las entidades
+    // que ya se habían enviado y reciben propiedades nuevas se renderizan otra vez completas, con
+    // el mismo `@id`, para que no se pierda ninguna propiedad.
+    pub(crate) fn render_from(&self, from: usize) -> Markup {
+        let entities: Vec<Value> = self
+            .entities
+            .iter()
+            .zip(&self.changed)
+            .filter(|(_, changed)| **changed > from)
+            .map(|(entity, _)| entity.clone())
+            .collect();
+        Self::render_entities(&entities)
+    }
+
+    fn merge(&mut self, mut entity: Map<String, Value>) {
+        self.revision += 1;
+        if let Some(id) = entity.get("@id").cloned() {
+            if let Some(index) = self.entities.iter().position(|e| e.get("@id") == Some(&id)) {
+                if let Value::Object(existing) = &mut self.entities[index] {
+                    existing.append(&mut entity);
+                }
+                self.changed[index] = self.revision;
+                return;
+            }
+        }
+        self.entities.push(Value::Object(entity));
+        self.changed.push(self.revision);
+    }
+
+    fn render_entities(entities: &[Value]) -> Markup {
+        let document = match entities {
+            [] => return html! {},
+            [entity] => {
+                let mut entity = entity.clone();
+                if let Value::Object(map) = &mut entity {
+                    map.entry("@context").or_insert_with(|| SCHEMA_ORG.into());
+                }
+                entity
+            }
+            _ => {
+                let graph = entities
+                    .iter()
+                    .map(|entity| {
+                        let mut entity = entity.clone();
+                        if let Value::Object(map) = &mut entity {
+                            if map.get("@context").and_then(Value::as_str) == Some(SCHEMA_ORG) {
+                                map.remove("@context");
+                            }
+                        }
+                        entity
+                    })
+                    .collect();
+                let mut document = Map::new();
+                document.insert("@context".into(), SCHEMA_ORG.into());
+                document.insert("@graph".into(), Value::Array(graph));
+                Value::Object(document)
+            }
+        };
+
+        // Escapa los caracteres que podrían cerrar el <script> o abrir otro elemento HTML.
+        let json = document
+            .to_string()
+            .replace('<', "\\u003c")
+            .replace('>', "\\u003e")
+            .replace('&', "\\u0026");
+
+        html! {
+            script type="application/ld+json" { (PreEscaped(json)) }
+        }
+    }
+}
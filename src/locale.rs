@@ -103,6 +103,7 @@ mod request;
 pub use request::RequestLocale;
 
 mod l10n;
+pub(crate) use l10n::has_translations;
 pub use l10n::L10n;
 
 // **< include_locales! >***************************************************************************
@@ -189,6 +189,25 @@ impl Locale {
     pub fn default_langid() -> &'static LanguageIdentifier {
         (*CONFIG_LANGID).unwrap_or(&FALLBACK_LANGID)
     }
+
+    /// Devuelve los identificadores de idioma soportados por PageTop, sin duplicados y ordenados.
+    ///
+    /// Los alias de idioma (por ejemplo, `"es"` y `"es-es"`) se resuelven a un único identificador
+    /// canónico (`"es-ES"`).
+    ///
+    /// # Ejemplo
+    ///
+    /// ```rust
+    /// # use pagetop::prelude::*;
+    /// let languages = Locale::supported_langids();
+    /// assert!(languages.iter().any(|l| l.to_string() == "es-ES"));
+    /// ```
+    pub fn supported_langids() -> Vec<&'static LanguageIdentifier> {
+        let mut langids: Vec<_> = LANGUAGES.values().map(|(langid, _)| langid).collect();
+        langids.sort_by_key(|langid| langid.to_string());
+        langids.dedup();
+        langids
+    }
 }
 
 /// Permite a [`Locale`] actuar como proveedor de idioma.
@@ -0,0 +1,11 @@
+routes_extension_name = Route Inventory
+routes_extension_description = Development page listing the routes of the web service.
+
+routes_title = Registered routes
+routes_method = Method
+routes_pattern = Pattern
+routes_name = Name
+routes_owner = Extension
+routes_embedded_files = (embedded files)
+routes_file_system = (file system)
+routes_unlisted = Extensions that configure routes not listed above
@@ -0,0 +1,11 @@
+routes_extension_name = Inventario de rutas
+routes_extension_description = Página de desarrollo con las rutas del servicio web.
+
+routes_title = Rutas registradas
+routes_method = Método
+routes_pattern = Patrón
+routes_name = Nombre
+routes_owner = Extensión
+routes_embedded_files = (archivos embebidos)
+routes_file_system = (sistema de ficheros)
+routes_unlisted = Extensiones que configuran rutas que no aparecen en la lista
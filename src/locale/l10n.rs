@@ -1,7 +1,7 @@
 use crate::html::{Markup, PreEscaped};
 use crate::{include_locales, AutoDefault, CowStr};
 
-use super::{LangId, Locale};
+use super::{LangId, LanguageIdentifier, Locale};
 
 use fluent_templates::Loader;
 use fluent_templates::StaticLoader as Locales;
@@ -12,6 +12,14 @@ use std::fmt;
 
 include_locales!(LOCALES_PAGETOP);
 
+// Indica si las traducciones predefinidas de PageTop incluyen el idioma indicado o alguna de sus
+// variantes regionales (p. ej. `en-US` para `en-GB`).
+pub(crate) fn has_translations(langid: &LanguageIdentifier) -> bool {
+    LOCALES_PAGETOP
+        .locales()
+        .any(|locale| locale.language == langid.language)
+}
+
 /// Operación de localización a realizar.
 ///
 /// * `None` - No se aplica ninguna localización.
@@ -188,3 +196,27 @@ impl L10n {
         PreEscaped(self.lookup(language).unwrap_or_default())
     }
 }
+
+impl From<&'static str> for L10n {
+    /// Convierte una cadena literal en un texto sin traducción, como [`L10n::n()`].
+    #[inline]
+    fn from(text: &'static str) -> Self {
+        L10n::n(text)
+    }
+}
+
+impl From<String> for L10n {
+    /// Convierte una cadena en un texto sin traducción, como [`L10n::n()`].
+    #[inline]
+    fn from(text: String) -> Self {
+        L10n::n(text)
+    }
+}
+
+impl From<CowStr> for L10n {
+    /// Convierte una cadena en un texto sin traducción, como [`L10n::n()`].
+    #[inline]
+    fn from(text: CowStr) -> Self {
+        L10n::n(text)
+    }
+}
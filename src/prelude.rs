@@ -17,7 +17,7 @@ pub use crate::include_locales;
 // crate::service
 pub use crate::static_files_service;
 // crate::core::action
-pub use crate::actions;
+pub use crate::{actions, declare_hook};
 // crate::core::theme
 pub use crate::{render_component, setup_component};
 
@@ -42,13 +42,18 @@ pub use crate::core::{AnyCast, AnyInfo, TypeInfo};
 
 pub use crate::core::action::*;
 pub use crate::core::component::*;
+pub use crate::core::event::{self, Event, EventRecorder, Subscriber};
 pub use crate::core::extension::*;
+pub use crate::core::job::{self, Job, JobType};
+pub use crate::core::schedule::*;
 pub use crate::core::theme::*;
 
-pub use crate::response::{json::*, page::*, redirect::*, ResponseError};
+pub use crate::response::{
+    feed::*, json::*, page::*, redirect::*, robots::*, sitemap::*, well_known::*, ResponseError,
+};
 
 pub use crate::base::action;
 pub use crate::base::component::*;
 pub use crate::base::theme;
 
-pub use crate::app::Application;
+pub use crate::app::{cli, AppServer, Application, PrepareError, SiteExport};
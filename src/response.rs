@@ -1,9 +1,38 @@
 //! Respuestas a las peticiones web en sus diferentes formatos.
 
+use crate::service::HttpRequest;
+
 pub use actix_web::ResponseError;
 
 pub mod page;
 
 pub mod json;
 
+pub mod feed;
+
 pub mod redirect;
+
+pub mod robots;
+
+pub mod sitemap;
+
+pub mod well_known;
+
+// **< Helpers >************************************************************************************
+
+// Esquema y servidor de la petición, sin barra final (por ejemplo, `https://example.com`).
+pub(crate) fn base_url(request: &HttpRequest) -> String {
+    let info = request.connection_info();
+    format!("{}://{}", info.scheme(), info.host())
+}
+
+// Convierte una ruta en URL absoluta; las URL absolutas se devuelven tal cual.
+pub(crate) fn absolute_url(base: &str, loc: &str) -> String {
+    if loc.starts_with("http://") || loc.starts_with("https://") {
+        loc.to_owned()
+    } else if loc.starts_with('/') {
+        format!("{base}{loc}")
+    } else {
+        format!("{base}/{loc}")
+    }
+}
@@ -0,0 +1,459 @@
+//! Respuestas de sindicación de contenidos en formato Atom o RSS 2.0.
+//!
+//! [`Feed`] construye un documento [Atom](https://www.rfc-editor.org/rfc/rfc4287) o
+//! [RSS 2.0](https://www.rssboard.org/rss-specification) a partir de una lista de entradas
+//! [`FeedEntry`]. Los títulos y resúmenes son *textos localizados* ([`L10n`]) que se traducen al
+//! idioma de la petición, los enlaces se indican con [`RoutePath`] y se convierten en URL absolutas
+//! usando el esquema y el servidor de la petición, y las fechas usan los tipos de
+//! [`datetime`](crate::datetime).
+//!
+//! `Feed` implementa [`Responder`], por lo que puede devolverse directamente desde un manejador con
+//! el tipo de contenido apropiado para cada formato.
+//!
+//! Para que los navegadores y lectores descubran el canal, una extensión puede publicarlo con
+//! [`Extension::feeds()`](crate::core::extension::Extension::feeds). PageTop añade entonces el
+//! enlace `<link rel="alternate">` correspondiente en la cabecera de todas las páginas.
+//!
+//! # Ejemplo
+//!
+//! ```rust
+//! # use pagetop::prelude::*;
+//! pub struct Blog;
+//!
+//! impl Extension for Blog {
+//!     fn configure_service(&self, scfg: &mut service::web::ServiceConfig) {
+//!         scfg.route("/blog/feed.atom", service::web::get().to(blog_feed));
+//!     }
+//!
+//!     fn feeds(&self) -> Vec<Link> {
+//!         vec![FeedFormat::Atom.link("/blog/feed.atom").with_title("Blog")]
+//!     }
+//! }
+//!
+//! async fn blog_feed(request: HttpRequest) -> Feed {
+//!     Feed::atom(request)
+//!         .with_title(L10n::n("Blog"))
+//!         .with_link("/blog")
+//!         .with_entry(
+//!             FeedEntry::new(
+//!                 L10n::n("Hello world"),
+//!                 "/blog/hello-world",
+//!                 Utc.with_ymd_and_hms(2025, 3, 14, 10, 0, 0).unwrap(),
+//!             )
+//!             .with_summary(L10n::n("First post.")),
+//!         )
+//! }
+//! ```
+
+use crate::datetime::{DateTime, FixedOffset, SecondsFormat, Utc};
+use crate::html::{Escaper, Link, Markup, RoutePath};
+use crate::locale::{L10n, LangId, RequestLocale};
+use crate::response::{absolute_url, base_url};
+use crate::service::{BoxBody, HttpRequest, HttpResponse};
+use crate::{builder_fn, global, AutoDefault, CowStr};
+
+use actix_web::Responder;
+
+use std::fmt::Write;
+
+// **< FeedFormat >*********************************************************************************
+
+/// Formatos de documento disponibles para un [`Feed`].
+#[derive(AutoDefault, Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FeedFormat {
+    /// Formato Atom (`application/atom+xml`). Es el formato por defecto.
+    #[default]
+    Atom,
+    /// Formato RSS 2.0 (`application/rss+xml`).
+    Rss,
+}
+
+impl FeedFormat {
+    /// Devuelve el tipo MIME del formato.
+    pub fn mime(&self) -> &'static str {
+        match self {
+            Self::Atom => "application/atom+xml",
+            Self::Rss => "application/rss+xml",
+        }
+    }
+
+    /// Crea el enlace `<link rel="alternate">` que anuncia un canal en este formato.
+    pub fn link(&self, href: impl Into<CowStr>) -> Link {
+        Link::alternate(href).with_type(self.mime())
+    }
+}
+
+// **< FeedEntry >**********************************************************************************
+
+/// Entrada de un canal de sindicación.
+#[rustfmt::skip]
+pub struct FeedEntry {
+    id        : Option<CowStr>,
+    title     : L10n,
+    link      : RoutePath,
+    updated   : DateTime<FixedOffset>,
+    published : Option<DateTime<FixedOffset>>,
+    summary   : Option<L10n>,
+    content   : Option<Markup>,
+    author    : Option<CowStr>,
+    categories: Vec<CowStr>,
+}
+
+impl FeedEntry {
+    /// Crea una entrada con el título, el enlace a su página y la fecha de su última modificación.
+    pub fn new(
+        title: L10n,
+        link: impl Into<RoutePath>,
+        updated: impl Into<DateTime<FixedOffset>>,
+    ) -> Self {
+        FeedEntry {
+            id: None,
+            title,
+            link: link.into(),
+            updated: updated.into(),
+            published: None,
+            summary: None,
+            content: None,
+            author: None,
+            categories: Vec::new(),
+        }
+    }
+
+    // **< FeedEntry BUILDER >**********************************************************************
+
+    /// Establece un identificador permanente para la entrada. Por defecto se usa la URL absoluta de
+    /// su enlace.
+    #[builder_fn]
+    pub fn with_id(mut self, id: impl Into<CowStr>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Establece la fecha de publicación de la entrada.
+    #[builder_fn]
+    pub fn with_published(mut self, published: impl Into<DateTime<FixedOffset>>) -> Self {
+        self.published = Some(published.into());
+        self
+    }
+
+    /// Establece un resumen de la entrada.
+    #[builder_fn]
+    pub fn with_summary(mut self, summary: L10n) -> Self {
+        self.summary = Some(summary);
+        self
+    }
+
+    /// Establece el contenido HTML completo de la entrada.
+    #[builder_fn]
+    pub fn with_content(mut self, content: Markup) -> Self {
+        self.content = Some(content);
+        self
+    }
+
+    /// Establece el nombre del autor de la entrada.
+    #[builder_fn]
+    pub fn with_author(mut self, author: impl Into<CowStr>) -> Self {
+        self.author = Some(author.into());
+        self
+    }
+
+    /// Añade una categoría a la entrada.
+    #[builder_fn]
+    pub fn with_category(mut self, category: impl Into<CowStr>) -> Self {
+        self.categories.push(category.into());
+        self
+    }
+
+    // **< FeedEntry GETTERS >**********************************************************************
+
+    /// Devuelve la fecha de la última modificación de la entrada.
+    pub fn updated(&self) -> &DateTime<FixedOffset> {
+        &self.updated
+    }
+}
+
+// **< Feed >***************************************************************************************
+
+/// Canal de sindicación que se responde como documento Atom o RSS 2.0.
+#[rustfmt::skip]
+pub struct Feed {
+    request : HttpRequest,
+    locale  : RequestLocale,
+    format  : FeedFormat,
+    title   : L10n,
+    subtitle: Option<L10n>,
+    link    : RoutePath,
+    author  : Option<CowStr>,
+    updated : Option<DateTime<FixedOffset>>,
+    entries : Vec<FeedEntry>,
+}
+
+impl Feed {
+    /// Crea un canal vacío en el formato indicado para la petición recibida.
+    ///
+    /// Por defecto el título del canal es el nombre de la aplicación y su enlace la página de
+    /// inicio (`"/"`).
+    pub fn new(request: HttpRequest, format: FeedFormat) -> Self {
+        Feed {
+            locale: RequestLocale::from_request(Some(&request)),
+            request,
+            format,
+            title: L10n::n(global::SETTINGS.app.name.as_str()),
+            subtitle: None,
+            link: RoutePath::new("/"),
+            author: None,
+            updated: None,
+            entries: Vec::new(),
+        }
+    }
+
+    /// Crea un canal vacío en formato Atom.
+    pub fn atom(request: HttpRequest) -> Self {
+        Self::new(request, FeedFormat::Atom)
+    }
+
+    /// Crea un canal vacío en formato RSS 2.0.
+    pub fn rss(request: HttpRequest) -> Self {
+        Self::new(request, FeedFormat::Rss)
+    }
+
+    // **< Feed BUILDER >***************************************************************************
+
+    /// Establece el título del canal.
+    #[builder_fn]
+    pub fn with_title(mut self, title: L10n) -> Self {
+        self.title = title;
+        self
+    }
+
+    /// Establece una descripción breve del canal.
+    #[builder_fn]
+    pub fn with_subtitle(mut self, subtitle: L10n) -> Self {
+        self.subtitle = Some(subtitle);
+        self
+    }
+
+    /// Establece la página web asociada al canal.
+    #[builder_fn]
+    pub fn with_link(mut self, link: impl Into<RoutePath>) -> Self {
+        self.link = link.into();
+        self
+    }
+
+    /// Establece el nombre del autor del canal.
+    #[builder_fn]
+    pub fn with_author(mut self, author: impl Into<CowStr>) -> Self {
+        self.author = Some(author.into());
+        self
+    }
+
+    /// Establece la fecha de la última modificación del canal. Por defecto es la fecha más
+    /// reciente de sus entradas.
+    #[builder_fn]
+    pub fn with_updated(mut self, updated: impl Into<DateTime<FixedOffset>>) -> Self {
+        self.updated = Some(updated.into());
+        self
+    }
+
+    /// Añade una entrada al canal.
+    #[builder_fn]
+    pub fn with_entry(mut self, entry: FeedEntry) -> Self {
+        self.entries.push(entry);
+        self
+    }
+
+    // **< Feed GETTERS >***************************************************************************
+
+    /// Devuelve el formato del canal.
+    pub fn format(&self) -> FeedFormat {
+        self.format
+    }
+
+    /// Devuelve las entradas del canal.
+    pub fn entries(&self) -> &[FeedEntry] {
+        &self.entries
+    }
+
+    // **< Feed RENDER >****************************************************************************
+
+    /// Renderiza el documento XML del canal.
+    pub fn render(&self) -> String {
+        match self.format {
+            FeedFormat::Atom => self.render_atom(),
+            FeedFormat::Rss => self.render_rss(),
+        }
+    }
+
+    fn render_atom(&self) -> String {
+        let base = base_url(&self.request);
+        let self_url = absolute_url(&base, &self.request.uri().to_string());
+        let updated = self
+            .last_updated()
+            .to_rfc3339_opts(SecondsFormat::Secs, true);
+
+        let mut xml = String::from(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+        xml.push_str(r#"<feed xmlns="http://www.w3.org/2005/Atom" xml:lang=""#);
+        push_escaped(&mut xml, &self.locale.langid().to_string());
+        xml.push_str(r#"">"#);
+        push_element(&mut xml, "title", &self.localized(&self.title));
+        if let Some(subtitle) = &self.subtitle {
+            push_element(&mut xml, "subtitle", &self.localized(subtitle));
+        }
+        push_element(&mut xml, "id", &self_url);
+        push_link(&mut xml, Some("self"), &self_url);
+        push_link(&mut xml, None, &absolute_url(&base, &self.link.to_string()));
+        push_element(&mut xml, "updated", &updated);
+        if let Some(author) = &self.author {
+            xml.push_str("<author>");
+            push_element(&mut xml, "name", author);
+            xml.push_str("</author>");
+        }
+        for entry in &self.entries {
+            let link = absolute_url(&base, &entry.link.to_string());
+            xml.push_str("<entry>");
+            push_element(&mut xml, "title", &self.localized(&entry.title));
+            push_element(&mut xml, "id", entry.id.as_deref().unwrap_or(&link));
+            push_link(&mut xml, None, &link);
+            push_element(
+                &mut xml,
+                "updated",
+                &entry.updated.to_rfc3339_opts(SecondsFormat::Secs, true),
+            );
+            if let Some(published) = &entry.published {
+                push_element(
+                    &mut xml,
+                    "published",
+                    &published.to_rfc3339_opts(SecondsFormat::Secs, true),
+                );
+            }
+            if let Some(author) = &entry.author {
+                xml.push_str("<author>");
+                push_element(&mut xml, "name", author);
+                xml.push_str("</author>");
+            }
+            for category in &entry.categories {
+                xml.push_str(r#"<category term=""#);
+                push_escaped(&mut xml, category);
+                xml.push_str(r#""/>"#);
+            }
+            if let Some(summary) = &entry.summary {
+                push_element(&mut xml, "summary", &self.localized(summary));
+            }
+            if let Some(content) = &entry.content {
+                xml.push_str(r#"<content type="html">"#);
+                push_escaped(&mut xml, &content.0);
+                xml.push_str("</content>");
+            }
+            xml.push_str("</entry>");
+        }
+        xml.push_str("</feed>");
+        xml
+    }
+
+    fn render_rss(&self) -> String {
+        let base = base_url(&self.request);
+        let self_url = absolute_url(&base, &self.request.uri().to_string());
+
+        let mut xml = String::from(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+        xml.push_str(concat!(
+            r#"<rss version="2.0" xmlns:atom="http://www.w3.org/2005/Atom""#,
+            r#" xmlns:dc="http://purl.org/dc/elements/1.1/"><channel>"#,
+        ));
+        push_element(&mut xml, "title", &self.localized(&self.title));
+        push_element(
+            &mut xml,
+            "link",
+            &absolute_url(&base, &self.link.to_string()),
+        );
+        push_element(
+            &mut xml,
+            "description",
+            &self
+                .subtitle
+                .as_ref()
+                .map(|subtitle| self.localized(subtitle))
+                .unwrap_or_default(),
+        );
+        push_element(&mut xml, "language", &self.locale.langid().to_string());
+        push_element(&mut xml, "lastBuildDate", &self.last_updated().to_rfc2822());
+        xml.push_str(r#"<atom:link rel="self" type="application/rss+xml" href=""#);
+        push_escaped(&mut xml, &self_url);
+        xml.push_str(r#""/>"#);
+        for entry in &self.entries {
+            let link = absolute_url(&base, &entry.link.to_string());
+            xml.push_str("<item>");
+            push_element(&mut xml, "title", &self.localized(&entry.title));
+            push_element(&mut xml, "link", &link);
+            match &entry.id {
+                Some(id) => {
+                    xml.push_str(r#"<guid isPermaLink="false">"#);
+                    push_escaped(&mut xml, id);
+                    xml.push_str("</guid>");
+                }
+                None => push_element(&mut xml, "guid", &link),
+            }
+            push_element(
+                &mut xml,
+                "pubDate",
+                &entry.published.unwrap_or(entry.updated).to_rfc2822(),
+            );
+            if let Some(author) = &entry.author {
+                push_element(&mut xml, "dc:creator", author);
+            }
+            for category in &entry.categories {
+                push_element(&mut xml, "category", category);
+            }
+            if let Some(summary) = &entry.summary {
+                push_element(&mut xml, "description", &self.localized(summary));
+            } else if let Some(content) = &entry.content {
+                push_element(&mut xml, "description", &content.0);
+            }
+            xml.push_str("</item>");
+        }
+        xml.push_str("</channel></rss>");
+        xml
+    }
+
+    // Fecha de la última modificación del canal, de sus entradas o, si no hay, la actual.
+    fn last_updated(&self) -> DateTime<FixedOffset> {
+        self.updated
+            .or_else(|| self.entries.iter().map(|entry| entry.updated).max())
+            .unwrap_or_else(|| Utc::now().fixed_offset())
+    }
+
+    fn localized(&self, text: &L10n) -> String {
+        text.lookup(&self.locale).unwrap_or_default()
+    }
+}
+
+impl Responder for Feed {
+    type Body = BoxBody;
+
+    fn respond_to(self, _req: &HttpRequest) -> HttpResponse<Self::Body> {
+        HttpResponse::Ok()
+            .content_type(format!("{}; charset=utf-8", self.format.mime()))
+            .body(self.render())
+    }
+}
+
+// **< Helpers >************************************************************************************
+
+fn push_element(xml: &mut String, name: &str, text: &str) {
+    let _ = write!(xml, "<{name}>");
+    push_escaped(xml, text);
+    let _ = write!(xml, "</{name}>");
+}
+
+fn push_link(xml: &mut String, rel: Option<&str>, href: &str) {
+    xml.push_str("<link");
+    if let Some(rel) = rel {
+        let _ = write!(xml, r#" rel="{rel}""#);
+    }
+    xml.push_str(r#" href=""#);
+    push_escaped(xml, href);
+    xml.push_str(r#""/>"#);
+}
+
+fn push_escaped(xml: &mut String, text: &str) {
+    let _ = Escaper::new(xml).write_str(text);
+}
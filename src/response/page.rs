@@ -16,18 +16,31 @@
 mod error;
 pub use error::ErrorPage;
 
+mod fragment;
+pub use fragment::{Fragment, FRAGMENT_HEADER};
+
+mod stream;
+
+mod social;
+pub use social::{OgImage, OgType, OpenGraph, TwitterCard, TwitterCardType};
+
 pub use actix_web::Result as ResultPage;
 
 use crate::base::action;
 use crate::core::component::{AssetsOp, ChildOp, Context, ContextError, Contextual};
 use crate::core::theme::{DefaultRegion, Region, RegionRef, TemplateRef, ThemeRef};
-use crate::html::{html, Markup, DOCTYPE};
+use crate::html::{html, Markup, PreEscaped, DOCTYPE};
 use crate::html::{Assets, Favicon, JavaScript, StyleSheet};
-use crate::html::{Attr, AttrId};
+use crate::html::{Attr, AttrId, Link, LinkRel};
 use crate::html::{Classes, ClassesOp};
 use crate::locale::{CharacterDirection, L10n, LangId, LanguageIdentifier};
-use crate::service::HttpRequest;
-use crate::{builder_fn, AutoDefault};
+use crate::service::http::header::ContentType;
+use crate::service::{HttpRequest, HttpResponse};
+use crate::{builder_fn, AutoDefault, CowStr};
+
+use serde::Serialize;
+
+use std::collections::VecDeque;
 
 // **< ReservedRegion >*****************************************************************************
 
@@ -90,10 +103,14 @@ impl Region for ReservedRegion {
 pub struct Page {
     title       : Attr<L10n>,
     description : Attr<L10n>,
-    metadata    : Vec<(&'static str, &'static str)>,
-    properties  : Vec<(&'static str, &'static str)>,
+    metadata    : Vec<(CowStr, L10n)>,
+    properties  : Vec<(CowStr, L10n)>,
+    links       : Vec<Link>,
+    open_graph  : Option<OpenGraph>,
+    twitter_card: Option<TwitterCard>,
     body_id     : AttrId,
     body_classes: Classes,
+    fragments   : bool,
     context     : Context,
 }
 
@@ -109,8 +126,12 @@ impl Page {
             description : Attr::<L10n>::default(),
             metadata    : Vec::default(),
             properties  : Vec::default(),
+            links       : Vec::default(),
+            open_graph  : None,
+            twitter_card: None,
             body_id     : AttrId::default(),
             body_classes: Classes::default(),
+            fragments   : false,
             context     : Context::new(Some(request)),
         }
     }
@@ -132,16 +153,64 @@ impl Page {
     }
 
     /// Añade una entrada `<meta name="..." content="...">` al `<head>`.
+    ///
+    /// El contenido puede ser un texto construido en cada petición o un texto traducible
+    /// ([`L10n`]), que se resolverá con el idioma de la página.
     #[builder_fn]
-    pub fn with_metadata(mut self, name: &'static str, content: &'static str) -> Self {
-        self.metadata.push((name, content));
+    pub fn with_metadata(mut self, name: impl Into<CowStr>, content: impl Into<L10n>) -> Self {
+        self.metadata.push((name.into(), content.into()));
         self
     }
 
     /// Añade una entrada `<meta property="..." content="...">` al `<head>`.
+    ///
+    /// El contenido puede ser un texto construido en cada petición o un texto traducible
+    /// ([`L10n`]), que se resolverá con el idioma de la página.
+    #[builder_fn]
+    pub fn with_property(mut self, property: impl Into<CowStr>, content: impl Into<L10n>) -> Self {
+        self.properties.push((property.into(), content.into()));
+        self
+    }
+
+    /// Añade un elemento `<link>` al `<head>`.
+    ///
+    /// Sólo puede haber una URL canónica por página, así que un enlace [`LinkRel::Canonical`]
+    /// sustituye al anterior, si lo hubiera.
+    #[builder_fn]
+    pub fn with_link(mut self, link: Link) -> Self {
+        if *link.rel() == LinkRel::Canonical {
+            self.links.retain(|l| *l.rel() != LinkRel::Canonical);
+        }
+        self.links.push(link);
+        self
+    }
+
+    /// Establece los metadatos de Open Graph para compartir la página en redes sociales.
+    ///
+    /// Los valores no definidos en [`OpenGraph`] se completan con los de la página al renderizar
+    /// el `<head>`.
+    #[builder_fn]
+    pub fn with_open_graph(mut self, open_graph: OpenGraph) -> Self {
+        self.open_graph = Some(open_graph);
+        self
+    }
+
+    /// Establece los metadatos de tarjeta de Twitter/X para compartir la página.
+    ///
+    /// Los valores no definidos en [`TwitterCard`] se completan con los de la página al renderizar
+    /// el `<head>`.
+    #[builder_fn]
+    pub fn with_twitter_card(mut self, twitter_card: TwitterCard) -> Self {
+        self.twitter_card = Some(twitter_card);
+        self
+    }
+
+    /// Añade datos estructurados (JSON-LD) a la página a partir de cualquier valor serializable.
+    ///
+    /// Ver [`Context::add_structured_data()`].
     #[builder_fn]
-    pub fn with_property(mut self, property: &'static str, content: &'static str) -> Self {
-        self.properties.push((property, content));
+    pub fn with_structured_data(mut self, data: impl Serialize) -> Self {
+        self.context.add_structured_data(data);
         self
     }
 
@@ -159,6 +228,16 @@ impl Page {
         self
     }
 
+    /// Permite que [`Page::render()`] y [`Page::stream()`] devuelvan sólo la parte de la página
+    /// que solicite la petición (ver [`Page::render_fragment()`]).
+    ///
+    /// Por defecto está deshabilitado y siempre se renderiza el documento completo.
+    #[builder_fn]
+    pub fn with_fragments(mut self, enable: bool) -> Self {
+        self.fragments = enable;
+        self
+    }
+
     // **< Page GETTERS >***************************************************************************
 
     /// Devuelve el título traducido para el idioma de la página, si existe.
@@ -172,15 +251,30 @@ impl Page {
     }
 
     /// Devuelve la lista de metadatos `<meta name=...>`.
-    pub fn metadata(&self) -> &Vec<(&str, &str)> {
+    pub fn metadata(&self) -> &Vec<(CowStr, L10n)> {
         &self.metadata
     }
 
     /// Devuelve la lista de propiedades `<meta property=...>`.
-    pub fn properties(&self) -> &Vec<(&str, &str)> {
+    pub fn properties(&self) -> &Vec<(CowStr, L10n)> {
         &self.properties
     }
 
+    /// Devuelve la lista de elementos `<link>` del `<head>`.
+    pub fn links(&self) -> &Vec<Link> {
+        &self.links
+    }
+
+    /// Devuelve los metadatos de Open Graph de la página, si se han definido.
+    pub fn open_graph(&self) -> Option<&OpenGraph> {
+        self.open_graph.as_ref()
+    }
+
+    /// Devuelve los metadatos de tarjeta de Twitter/X de la página, si se han definido.
+    pub fn twitter_card(&self) -> Option<&TwitterCard> {
+        self.twitter_card.as_ref()
+    }
+
     /// Devuelve el identificador del elemento `<body>`.
     pub fn body_id(&self) -> &AttrId {
         &self.body_id
@@ -228,7 +322,19 @@ impl Page {
     ///    `lang` y `dir` en la etiqueta `<html>`.
     /// 8. Compone el documento HTML completo (`<!DOCTYPE html>`, `<html>`, `<head>`, `<body>`) y
     ///    devuelve un [`ResultPage`] con el [`Markup`] final.
+    ///
+    /// Si la página admite fragmentos ([`Page::with_fragments()`]) y la petición solicita sólo una
+    /// parte de la página, ya sea con la cabecera [`FRAGMENT_HEADER`] o con una petición de *htmx*
+    /// dirigida a un componente de la página, devuelve únicamente ese fragmento (ver
+    /// [`Page::render_fragment()`]).
     pub fn render(&mut self) -> ResultPage<Markup, ErrorPage> {
+        if let Some(fragment) = self.requested_fragment() {
+            return match fragment {
+                Some(fragment) => self.render_fragment(fragment),
+                None => self.fragment_not_found(),
+            };
+        }
+
         // Acciones específicas del tema antes de renderizar el <body>.
         self.context.theme().before_render_page_body(self);
 
@@ -252,13 +358,185 @@ impl Page {
         let head = self.context.theme().render_page_head(self);
 
         // Compone la página incluyendo los atributos de idioma y dirección del texto.
+        Ok(self.render_document(head, body))
+    }
+
+    /// Renderiza la página por fragmentos, enviando cada parte al cliente en cuanto está lista.
+    ///
+    /// Devuelve una respuesta [`HttpResponse`] con codificación *chunked* cuyo cuerpo se genera a
+    /// medida que el servidor lo va enviando. Resulta útil en páginas lentas, porque el navegador
+    /// recibe pronto el `<head>` y puede empezar a descargar hojas de estilo y *scripts* mientras
+    /// se renderiza el resto del documento.
+    ///
+    /// Mantiene el mismo orden de acciones que [`Page::render()`], salvo que el `<head>` se
+    /// renderiza antes que el `<body>`:
+    ///
+    /// 1. Ejecuta
+    ///    [`Theme::before_render_page_body()`](crate::core::theme::Theme::before_render_page_body)
+    ///    y despacha [`action::page::BeforeRenderBody`].
+    /// 2. Renderiza el `<head>` con
+    ///    [`Theme::render_page_head()`](crate::core::theme::Theme::render_page_head) y envía
+    ///    el primer fragmento hasta la apertura de `<body>`.
+    /// 3. Envía la región reservada [`ReservedRegion::PageTop`], después el cuerpo completo
+    ///    generado por
+    ///    [`Theme::render_page_body()`](crate::core::theme::Theme::render_page_body) y, por
+    ///    último, [`ReservedRegion::PageBottom`]. Si el tema lo habilita con
+    ///    [`Theme::stream_by_regions()`](crate::core::theme::Theme::stream_by_regions), envía en
+    ///    su lugar cada región devuelta por
+    ///    [`Template::stream_regions()`](crate::core::theme::Template::stream_regions).
+    /// 4. Envía uno a uno los componentes aplazados con
+    ///    [`Deferred`](crate::base::component::Deferred), que sustituyen a sus marcadores de
+    ///    posición.
+    /// 5. Ejecuta
+    ///    [`Theme::after_render_page_body()`](crate::core::theme::Theme::after_render_page_body)
+    ///    y despacha [`action::page::AfterRenderBody`].
+    /// 6. Envía al final del `<body>` los recursos añadidos al contexto después de enviar el
+    ///    `<head>`, y cierra el documento.
+    ///
+    /// # Ejemplo
+    ///
+    /// ```rust
+    /// # use pagetop::prelude::*;
+    /// async fn slow_page(request: HttpRequest) -> ResultPage<HttpResponse, ErrorPage> {
+    ///     Page::new(request)
+    ///         .with_title(L10n::n("Informe"))
+    ///         .with_child(Html::with(|_| html! { p { "Contenido lento..." } }))
+    ///         .stream()
+    /// }
+    /// ```
+    pub fn stream(mut self) -> ResultPage<HttpResponse, ErrorPage> {
+        // Los fragmentos se envían de una vez.
+        if self.requested_fragment().is_some() {
+            return Ok(HttpResponse::Ok()
+                .content_type(ContentType::html())
+                .body(self.render()?.into_string()));
+        }
+
+        self.context.enable_streaming();
+
+        // Acciones específicas del tema antes de renderizar el <body>.
+        self.context.theme().before_render_page_body(&mut self);
+
+        // Acciones de las extensiones antes de renderizar el <body>.
+        action::page::BeforeRenderBody::dispatch(&mut self);
+
+        // Renderiza el <head> por adelantado con los recursos conocidos hasta ahora.
+        let head = self.context.theme().render_page_head(&mut self);
+        self.context.mark_assets_rendered();
+
+        // Divide el documento en la apertura y el cierre del <body>.
+        let document = self
+            .render_document(head, PreEscaped(STREAM_MARK.to_owned()))
+            .into_string();
+        let (open, close) = document
+            .rsplit_once(STREAM_MARK)
+            .map(|(open, close)| (open.to_owned(), close.to_owned()))
+            .unwrap_or_default();
+
+        let mut steps = VecDeque::from([stream::Step::Markup(open)]);
+        let regions = match self.context.theme().stream_by_regions() {
+            true => self.context.template().stream_regions(),
+            false => None,
+        };
+        steps.extend(stream::body_steps(regions));
+        steps.push_back(stream::Step::Finish(close));
+
+        Ok(HttpResponse::Ok()
+            .content_type(ContentType::html())
+            .body(stream::PageStream::new(self, steps)))
+    }
+
+    /// Renderiza sólo una región o un componente de la página.
+    ///
+    /// Pensado para actualizaciones parciales (por ejemplo, con *htmx*), donde el navegador ya
+    /// muestra la página y sólo necesita el marcado de una parte. Ejecuta la misma preparación del
+    /// tema y de las extensiones que [`Page::render()`], pero en lugar del documento completo
+    /// devuelve:
+    ///
+    /// - Con [`Fragment::Region`], el contenedor de la región y sus componentes.
+    /// - Con [`Fragment::Child`], el componente de primer nivel con ese identificador en cualquier
+    ///   región, o un error [`ErrorPage::NotFound`] si no existe.
+    ///
+    /// Para localizar un componente éste debe tener un identificador explícito.
+    ///
+    /// Los recursos que se añadan al contexto durante el renderizado del fragmento (y que no
+    /// estuvieran ya registrados al preparar la página) se incluyen al final dentro de un
+    /// `<div hx-swap-oob="beforeend:head">`, para que *htmx* los incorpore al `<head>` del
+    /// documento.
+    ///
+    /// # Ejemplo
+    ///
+    /// ```rust
+    /// # use pagetop::prelude::*;
+    /// async fn news(request: HttpRequest) -> ResultPage<Markup, ErrorPage> {
+    ///     Page::new(request)
+    ///         .with_child(Block::new().with_id("news"))
+    ///         .render_fragment(Fragment::Child("news".to_string()))
+    /// }
+    /// ```
+    pub fn render_fragment(&mut self, fragment: Fragment) -> ResultPage<Markup, ErrorPage> {
+        // Acciones específicas del tema antes de renderizar el <body>.
+        self.context.theme().before_render_page_body(self);
+
+        // Acciones de las extensiones antes de renderizar el <body>.
+        action::page::BeforeRenderBody::dispatch(self);
+
+        // Los recursos registrados hasta aquí ya forman parte de la página completa.
+        self.context.mark_assets_rendered();
+
+        let markup = match fragment {
+            Fragment::Region(region) => region.render_ref(&mut self.context),
+            Fragment::Child(id) => match self.context.child_by_id(&id) {
+                Some(child) => child.render(&mut self.context),
+                None => return self.fragment_not_found(),
+            },
+        };
+
+        // Acciones específicas del tema después de renderizar el <body>.
+        self.context.theme().after_render_page_body(self);
+
+        // Acciones de las extensiones después de renderizar el <body>.
+        action::page::AfterRenderBody::dispatch(self);
+
+        let assets = self.context.render_pending_assets();
+        Ok(html! {
+            (markup)
+            @if !assets.is_empty() {
+                div hx-swap-oob="beforeend:head" { (assets) }
+            }
+        })
+    }
+
+    // Fragmento solicitado en la petición, si lo hay y la página admite fragmentos (ver
+    // `Fragment::from_request()`).
+    fn requested_fragment(&self) -> Option<Option<Fragment>> {
+        if !self.fragments {
+            return None;
+        }
+        let request = self.context.request()?;
+        Fragment::from_request(request, self.context.template(), |id| {
+            self.context.child_by_id(id).is_some()
+        })
+    }
+
+    // Respuesta para un fragmento inexistente. Sin petición asociada no hay página de error que
+    // devolver, y el fragmento queda vacío.
+    fn fragment_not_found(&self) -> ResultPage<Markup, ErrorPage> {
+        match self.context.request() {
+            Some(request) => Err(ErrorPage::NotFound(request.clone())),
+            None => Ok(html! {}),
+        }
+    }
+
+    // Compone el documento HTML completo a partir del `<head>` y el `<body>` ya renderizados.
+    fn render_document(&self, head: Markup, body: Markup) -> Markup {
         let lang = &self.context.langid().language;
         let dir = match self.context.langid().character_direction() {
             CharacterDirection::LTR => "ltr",
             CharacterDirection::RTL => "rtl",
             CharacterDirection::TTB => "auto",
         };
-        Ok(html! {
+        html! {
             (DOCTYPE)
             html lang=(lang) dir=(dir) {
                 head {
@@ -268,10 +546,13 @@ impl Page {
                     (body)
                 }
             }
-        })
+        }
     }
 }
 
+// Marca que separa la apertura y el cierre del <body> al renderizar la página por fragmentos.
+const STREAM_MARK: &str = "<!--pagetop:stream-->";
+
 /// Permite a [`Page`] actuar como proveedor de idioma usando el [`Context`] de la página.
 ///
 /// Resulta útil para usar [`Page`] directamente como fuente de traducción en [`L10n::lookup()`] o
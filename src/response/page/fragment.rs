@@ -0,0 +1,79 @@
+use crate::core::theme::{DefaultRegion, RegionRef, TemplateRef};
+use crate::service::HttpRequest;
+
+use super::ReservedRegion;
+
+/// Cabecera HTTP que solicita el renderizado parcial de una página.
+///
+/// Su valor es el nombre de una región (por ejemplo, `content`) o el identificador de un componente
+/// precedido de `#` (por ejemplo, `#latest-news`). Sólo se atiende en las páginas que admiten
+/// fragmentos ([`Page::with_fragments()`](super::Page::with_fragments)).
+pub const FRAGMENT_HEADER: &str = "PageTop-Fragment";
+
+/// Parte de una página que se puede renderizar de forma aislada con
+/// [`Page::render_fragment()`](super::Page::render_fragment).
+pub enum Fragment {
+    /// Una región completa, incluyendo su contenedor.
+    Region(RegionRef),
+    /// El componente de primer nivel de cualquier región con el identificador dado.
+    Child(String),
+}
+
+impl Fragment {
+    // Obtiene el fragmento solicitado en la petición, si lo hay.
+    //
+    // Devuelve `None` si la petición no solicita ningún fragmento y `Some(None)` si lo solicita
+    // explícitamente con `PageTop-Fragment` pero no corresponde a ninguna región conocida.
+    //
+    // Las peticiones de *htmx* (`HX-Request`) que no sean de navegación (`HX-Boosted`) se tratan
+    // como peticiones de fragmento si `HX-Target` coincide con el identificador de un componente
+    // de la página (`has_child`). En otro caso se renderiza el documento completo.
+    pub(super) fn from_request(
+        request: &HttpRequest,
+        template: TemplateRef,
+        has_child: impl FnOnce(&str) -> bool,
+    ) -> Option<Option<Fragment>> {
+        let header = |name: &str| {
+            request
+                .headers()
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+        };
+
+        if let Some(target) = header(FRAGMENT_HEADER) {
+            return Some(match target.strip_prefix('#') {
+                Some(id) => Some(Fragment::Child(id.to_owned())),
+                None => Self::region_by_name(template, target).map(Fragment::Region),
+            });
+        }
+
+        if header("HX-Request") == Some("true") && header("HX-Boosted").is_none() {
+            if let Some(id) = header("HX-Target").filter(|id| has_child(id)) {
+                return Some(Some(Fragment::Child(id.to_owned())));
+            }
+        }
+
+        None
+    }
+
+    // Busca por nombre entre las regiones de la plantilla y las regiones reservadas.
+    fn region_by_name(template: TemplateRef, name: &str) -> Option<RegionRef> {
+        template
+            .stream_regions()
+            .unwrap_or_else(|| {
+                vec![
+                    &DefaultRegion::Header,
+                    &DefaultRegion::Content,
+                    &DefaultRegion::Footer,
+                ]
+            })
+            .into_iter()
+            .chain([
+                &ReservedRegion::PageTop as RegionRef,
+                &ReservedRegion::PageBottom,
+            ])
+            .find(|region| region.name() == name)
+    }
+}
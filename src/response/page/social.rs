@@ -0,0 +1,406 @@
+use crate::html::{html, LinkRel, Markup};
+use crate::locale::{L10n, LangId};
+use crate::{global, AutoDefault, CowStr};
+
+use super::Page;
+
+// **< OgType >*************************************************************************************
+
+/// Tipo de objeto de Open Graph (`og:type`).
+#[derive(AutoDefault, Clone, Debug)]
+pub enum OgType {
+    /// Sitio o página genérica (`website`). Es el tipo por defecto.
+    #[default]
+    Website,
+    /// Artículo, noticia o entrada de blog (`article`).
+    Article,
+    /// Perfil de una persona (`profile`).
+    Profile,
+    /// Cualquier otro tipo del protocolo Open Graph.
+    Custom(CowStr),
+}
+
+impl OgType {
+    /// Devuelve el valor de la propiedad `og:type`.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Website => "website",
+            Self::Article => "article",
+            Self::Profile => "profile",
+            Self::Custom(kind) => kind,
+        }
+    }
+}
+
+// **< OgImage >************************************************************************************
+
+/// Imagen asociada a una página en Open Graph (`og:image`).
+///
+/// Indicar las dimensiones permite a las redes sociales mostrar la vista previa sin tener que
+/// descargar antes la imagen.
+#[rustfmt::skip]
+#[derive(Clone, Debug)]
+pub struct OgImage {
+    url   : CowStr,
+    mime  : Option<CowStr>,
+    width : Option<u32>,
+    height: Option<u32>,
+    alt   : Option<L10n>,
+}
+
+impl OgImage {
+    /// Crea una imagen a partir de su URL absoluta.
+    #[rustfmt::skip]
+    pub fn new(url: impl Into<CowStr>) -> Self {
+        OgImage {
+            url   : url.into(),
+            mime  : None,
+            width : None,
+            height: None,
+            alt   : None,
+        }
+    }
+
+    // **< OgImage BUILDER >************************************************************************
+
+    /// Establece el tipo MIME de la imagen (`og:image:type`).
+    pub fn with_type(mut self, mime: impl Into<CowStr>) -> Self {
+        self.mime = Some(mime.into());
+        self
+    }
+
+    /// Establece el ancho y el alto de la imagen en píxeles (`og:image:width` y
+    /// `og:image:height`).
+    pub fn with_size(mut self, width: u32, height: u32) -> Self {
+        self.width = Some(width);
+        self.height = Some(height);
+        self
+    }
+
+    /// Establece el texto alternativo de la imagen (`og:image:alt`).
+    pub fn with_alt(mut self, alt: impl Into<L10n>) -> Self {
+        self.alt = Some(alt.into());
+        self
+    }
+
+    // **< OgImage GETTERS >************************************************************************
+
+    /// Devuelve la URL de la imagen.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+}
+
+// **< OpenGraph >**********************************************************************************
+
+/// Metadatos de Open Graph para compartir una página en redes sociales.
+///
+/// Se añade a la página con [`Page::with_open_graph()`] y se renderiza en el `<head>` como
+/// propiedades `<meta property="og:...">`. Los valores que no se indiquen se toman de la propia
+/// página:
+///
+/// - `og:title` y `og:description`, del título y la descripción de la página.
+/// - `og:url`, de la URL canónica de la página ([`Link::canonical()`](crate::html::Link)), si la
+///   tiene.
+/// - `og:locale`, del idioma de la página (por ejemplo, `es_ES`).
+/// - `og:site_name`, del nombre de la aplicación (`app.name`).
+///
+/// # Ejemplo
+///
+/// ```rust
+/// # use pagetop::prelude::*;
+/// # let request = service::test::TestRequest::default().to_http_request();
+/// let page = Page::new(request)
+///     .with_title(L10n::n("Hola mundo"))
+///     .with_open_graph(
+///         OpenGraph::new()
+///             .with_type(OgType::Article)
+///             .with_url("https://example.com/blog/hola-mundo")
+///             .with_image(
+///                 OgImage::new("https://example.com/img/hola.png")
+///                     .with_size(1200, 630)
+///                     .with_alt("Saludo"),
+///             ),
+///     );
+/// ```
+#[rustfmt::skip]
+#[derive(AutoDefault, Clone, Debug)]
+pub struct OpenGraph {
+    kind       : OgType,
+    title      : Option<L10n>,
+    description: Option<L10n>,
+    url        : Option<CowStr>,
+    images     : Vec<OgImage>,
+    locale     : Option<CowStr>,
+    site_name  : Option<CowStr>,
+}
+
+impl OpenGraph {
+    /// Crea unos metadatos de Open Graph vacíos, que tomarán de la página sus valores por defecto.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // **< OpenGraph BUILDER >**********************************************************************
+
+    /// Establece el tipo de objeto (`og:type`).
+    pub fn with_type(mut self, kind: OgType) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    /// Establece el título (`og:title`) en lugar del título de la página.
+    pub fn with_title(mut self, title: impl Into<L10n>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Establece la descripción (`og:description`) en lugar de la descripción de la página.
+    pub fn with_description(mut self, description: impl Into<L10n>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Establece la URL absoluta de la página (`og:url`).
+    pub fn with_url(mut self, url: impl Into<CowStr>) -> Self {
+        self.url = Some(url.into());
+        self
+    }
+
+    /// Añade una imagen (`og:image`). Se pueden añadir varias, por orden de preferencia.
+    pub fn with_image(mut self, image: OgImage) -> Self {
+        self.images.push(image);
+        self
+    }
+
+    /// Establece el idioma (`og:locale`) en lugar del derivado del idioma de la página.
+    pub fn with_locale(mut self, locale: impl Into<CowStr>) -> Self {
+        self.locale = Some(locale.into());
+        self
+    }
+
+    /// Establece el nombre del sitio (`og:site_name`) en lugar del nombre de la aplicación.
+    pub fn with_site_name(mut self, site_name: impl Into<CowStr>) -> Self {
+        self.site_name = Some(site_name.into());
+        self
+    }
+
+    // **< OpenGraph GETTERS >**********************************************************************
+
+    /// Devuelve las imágenes añadidas.
+    pub fn images(&self) -> &[OgImage] {
+        &self.images
+    }
+
+    // **< OpenGraph RENDER >***********************************************************************
+
+    /// Renderiza las propiedades `og:*` completando los valores no definidos con los de la página.
+    pub fn render(&self, page: &Page) -> Markup {
+        let title = page_text(page, self.title.as_ref(), &page.title)
+            .unwrap_or_else(|| global::SETTINGS.app.name.clone());
+        let description = page_text(page, self.description.as_ref(), &page.description);
+        let url = self.url.as_deref().or_else(|| canonical_url(page));
+        let locale = match &self.locale {
+            Some(locale) => locale.to_string(),
+            None => og_locale(page),
+        };
+        let site_name = self
+            .site_name
+            .as_deref()
+            .unwrap_or(&global::SETTINGS.app.name);
+
+        html! {
+            meta property="og:type" content=(self.kind.as_str());
+            meta property="og:title" content=(title);
+            @if let Some(description) = description {
+                meta property="og:description" content=(description);
+            }
+            @if let Some(url) = url {
+                meta property="og:url" content=(url);
+            }
+            @for image in &self.images {
+                meta property="og:image" content=(image.url);
+                @if let Some(mime) = &image.mime {
+                    meta property="og:image:type" content=(mime);
+                }
+                @if let Some(width) = image.width {
+                    meta property="og:image:width" content=(width);
+                }
+                @if let Some(height) = image.height {
+                    meta property="og:image:height" content=(height);
+                }
+                @if let Some(alt) = image.alt.as_ref().and_then(|alt| alt.lookup(page)) {
+                    meta property="og:image:alt" content=(alt);
+                }
+            }
+            meta property="og:locale" content=(locale);
+            meta property="og:site_name" content=(site_name);
+        }
+    }
+}
+
+// **< TwitterCard >********************************************************************************
+
+/// Tipo de tarjeta de Twitter/X (`twitter:card`).
+#[derive(AutoDefault, Clone, Debug)]
+pub enum TwitterCardType {
+    /// Tarjeta con título, descripción y una imagen pequeña (`summary`). Es el tipo por defecto.
+    #[default]
+    Summary,
+    /// Tarjeta con una imagen destacada de gran tamaño (`summary_large_image`).
+    SummaryLargeImage,
+}
+
+impl TwitterCardType {
+    /// Devuelve el valor de la propiedad `twitter:card`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Summary => "summary",
+            Self::SummaryLargeImage => "summary_large_image",
+        }
+    }
+}
+
+/// Metadatos de tarjeta de Twitter/X para compartir una página.
+///
+/// Se añade a la página con [`Page::with_twitter_card()`] y se renderiza en el `<head>` como
+/// metadatos `<meta name="twitter:...">`. El título y la descripción se toman de la página si no se
+/// indican, y la imagen de la primera imagen de Open Graph ([`OpenGraph::with_image()`]), si la
+/// hay.
+///
+/// # Ejemplo
+///
+/// ```rust
+/// # use pagetop::prelude::*;
+/// let card = TwitterCard::new()
+///     .with_type(TwitterCardType::SummaryLargeImage)
+///     .with_site("@pagetop");
+/// ```
+#[rustfmt::skip]
+#[derive(AutoDefault, Clone, Debug)]
+pub struct TwitterCard {
+    kind       : TwitterCardType,
+    site       : Option<CowStr>,
+    creator    : Option<CowStr>,
+    title      : Option<L10n>,
+    description: Option<L10n>,
+    image      : Option<CowStr>,
+    image_alt  : Option<L10n>,
+}
+
+impl TwitterCard {
+    /// Crea una tarjeta vacía, que tomará de la página sus valores por defecto.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // **< TwitterCard BUILDER >********************************************************************
+
+    /// Establece el tipo de tarjeta (`twitter:card`).
+    pub fn with_type(mut self, kind: TwitterCardType) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    /// Establece la cuenta del sitio (`twitter:site`), por ejemplo `"@pagetop"`.
+    pub fn with_site(mut self, site: impl Into<CowStr>) -> Self {
+        self.site = Some(site.into());
+        self
+    }
+
+    /// Establece la cuenta del autor del contenido (`twitter:creator`).
+    pub fn with_creator(mut self, creator: impl Into<CowStr>) -> Self {
+        self.creator = Some(creator.into());
+        self
+    }
+
+    /// Establece el título (`twitter:title`) en lugar del título de la página.
+    pub fn with_title(mut self, title: impl Into<L10n>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Establece la descripción (`twitter:description`) en lugar de la descripción de la página.
+    pub fn with_description(mut self, description: impl Into<L10n>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Establece la URL absoluta de la imagen (`twitter:image`) y su texto alternativo
+    /// (`twitter:image:alt`).
+    pub fn with_image(mut self, url: impl Into<CowStr>, alt: impl Into<L10n>) -> Self {
+        self.image = Some(url.into());
+        self.image_alt = Some(alt.into());
+        self
+    }
+
+    // **< TwitterCard RENDER >*********************************************************************
+
+    /// Renderiza los metadatos `twitter:*` completando los valores no definidos con los de la
+    /// página.
+    pub fn render(&self, page: &Page) -> Markup {
+        let title = page_text(page, self.title.as_ref(), &page.title)
+            .unwrap_or_else(|| global::SETTINGS.app.name.clone());
+        let description = page_text(page, self.description.as_ref(), &page.description);
+        let og_image = page.open_graph.as_ref().and_then(|og| og.images.first());
+        let image = self
+            .image
+            .as_deref()
+            .or_else(|| og_image.map(|image| image.url()));
+        let image_alt = match &self.image {
+            Some(_) => self.image_alt.as_ref(),
+            None => og_image.and_then(|image| image.alt.as_ref()),
+        };
+
+        html! {
+            meta name="twitter:card" content=(self.kind.as_str());
+            @if let Some(site) = &self.site {
+                meta name="twitter:site" content=(site);
+            }
+            @if let Some(creator) = &self.creator {
+                meta name="twitter:creator" content=(creator);
+            }
+            meta name="twitter:title" content=(title);
+            @if let Some(description) = description {
+                meta name="twitter:description" content=(description);
+            }
+            @if let Some(image) = image {
+                meta name="twitter:image" content=(image);
+                @if let Some(alt) = image_alt.and_then(|alt| alt.lookup(page)) {
+                    meta name="twitter:image:alt" content=(alt);
+                }
+            }
+        }
+    }
+}
+
+// **< Helpers >************************************************************************************
+
+// Texto propio si está definido o, en otro caso, el texto equivalente de la página.
+fn page_text(
+    page: &Page,
+    own: Option<&L10n>,
+    fallback: &crate::html::Attr<L10n>,
+) -> Option<String> {
+    match own {
+        Some(text) => text.lookup(page),
+        None => fallback.lookup(page),
+    }
+}
+
+// URL canónica de la página, si se ha definido.
+fn canonical_url(page: &Page) -> Option<&str> {
+    page.links
+        .iter()
+        .find(|link| *link.rel() == LinkRel::Canonical)
+        .map(|link| link.href())
+}
+
+// Idioma de la página en el formato de Open Graph (`idioma_REGIÓN`, por ejemplo `es_ES`).
+fn og_locale(page: &Page) -> String {
+    let langid = page.langid();
+    match &langid.region {
+        Some(region) => format!("{}_{}", langid.language, region),
+        None => langid.language.to_string(),
+    }
+}
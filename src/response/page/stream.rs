@@ -0,0 +1,128 @@
+use crate::base::action;
+use crate::core::component::Contextual;
+use crate::core::theme::RegionRef;
+use crate::html::{html, PreEscaped};
+use crate::service::web::Bytes;
+
+use super::{Page, ReservedRegion};
+
+use actix_web::body::{BodySize, MessageBody};
+
+use std::collections::VecDeque;
+use std::convert::Infallible;
+use std::pin::Pin;
+use std::task::{Context as TaskContext, Poll};
+
+// Sustituye el marcador de un componente aplazado por el contenido de la plantilla precedente.
+const SWAP_DEFERRED: &str = "(function(s){\
+var t=s.previousElementSibling,e=document.getElementById(t.getAttribute('data-deferred'));\
+if(e){e.replaceWith(t.content);}t.remove();s.remove();\
+})(document.currentScript)";
+
+// Etapas pendientes en el envío de una página por fragmentos.
+pub(super) enum Step {
+    // Marcado ya renderizado, listo para enviarse.
+    Markup(String),
+    // Región que se renderiza y se envía en cuanto está lista.
+    Region(RegionRef),
+    // Cuerpo completo generado por el tema, cuando la plantilla no declara sus regiones.
+    Body,
+    // Siguiente componente aplazado por `Deferred`, si queda alguno.
+    Deferred,
+    // Acciones posteriores al <body>, recursos pendientes y cierre del documento.
+    Finish(String),
+}
+
+// Cuerpo de respuesta que renderiza una página etapa a etapa.
+//
+// Tras entregar cada fragmento devuelve una vez `Poll::Pending` (despertando inmediatamente la
+// tarea) para que el servidor vacíe su búfer de escritura antes de renderizar la siguiente etapa.
+// Así el cliente recibe el `<head>` y las primeras regiones sin esperar a las más lentas.
+#[rustfmt::skip]
+pub(super) struct PageStream {
+    page : Page,
+    steps: VecDeque<Step>,
+    flush: bool,
+}
+
+impl PageStream {
+    pub(super) fn new(page: Page, steps: VecDeque<Step>) -> Self {
+        PageStream {
+            page,
+            steps,
+            flush: false,
+        }
+    }
+
+    fn render_step(&mut self, step: Step) -> String {
+        let page = &mut self.page;
+        match step {
+            Step::Markup(markup) => markup,
+            Step::Region(region) => region.render_ref(&mut page.context).into_string(),
+            Step::Body => page.context.theme().render_page_body(page).into_string(),
+            Step::Deferred => match page.context.take_deferred() {
+                Some((id, child)) => {
+                    // Un componente aplazado puede aplazar a su vez otros componentes.
+                    self.steps.push_front(Step::Deferred);
+                    html! {
+                        template data-deferred=(id) { (child.render(&mut page.context)) }
+                        script { (PreEscaped(SWAP_DEFERRED)) }
+                    }
+                    .into_string()
+                }
+                None => String::new(),
+            },
+            Step::Finish(close) => {
+                // Acciones específicas del tema después de renderizar el <body>.
+                page.context.theme().after_render_page_body(page);
+
+                // Acciones de las extensiones después de renderizar el <body>.
+                action::page::AfterRenderBody::dispatch(page);
+
+                // Recursos añadidos después de enviar el <head>.
+                let mut markup = page.context.render_pending_assets().into_string();
+                markup.push_str(&close);
+                markup
+            }
+        }
+    }
+}
+
+impl MessageBody for PageStream {
+    type Error = Infallible;
+
+    fn size(&self) -> BodySize {
+        BodySize::Stream
+    }
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut TaskContext<'_>,
+    ) -> Poll<Option<Result<Bytes, Self::Error>>> {
+        let this = self.get_mut();
+        if this.flush {
+            this.flush = false;
+            cx.waker().wake_by_ref();
+            return Poll::Pending;
+        }
+        while let Some(step) = this.steps.pop_front() {
+            let chunk = this.render_step(step);
+            if !chunk.is_empty() {
+                this.flush = true;
+                return Poll::Ready(Some(Ok(Bytes::from(chunk))));
+            }
+        }
+        Poll::Ready(None)
+    }
+}
+
+// Etapas del cuerpo del documento entre la apertura de `<body>` y su cierre.
+pub(super) fn body_steps(regions: Option<Vec<RegionRef>>) -> impl Iterator<Item = Step> {
+    let body = match regions {
+        Some(regions) => regions.into_iter().map(Step::Region).collect(),
+        None => vec![Step::Body],
+    };
+    std::iter::once(Step::Region(&ReservedRegion::PageTop))
+        .chain(body)
+        .chain([Step::Region(&ReservedRegion::PageBottom), Step::Deferred])
+}
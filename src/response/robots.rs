@@ -0,0 +1,157 @@
+//! Instrucciones para los rastreadores web (`/robots.txt`).
+//!
+//! Cada extensión puede aportar sus reglas implementando
+//! [`Extension::robots()`](crate::core::extension::Extension::robots), que devuelve una lista de
+//! [`RobotsRule`]. PageTop agrupa las reglas de todas las extensiones habilitadas por agente de
+//! usuario y las sirve en `/robots.txt` siguiendo el
+//! [protocolo de exclusión de robots](https://www.rfc-editor.org/rfc/rfc9309).
+//!
+//! El ajuste [`global::SETTINGS.app.robots`](crate::global::App::robots) decide si se permite el
+//! rastreo. Por defecto sólo se permite en el modo de ejecución *"production"*; en cualquier otro
+//! modo `/robots.txt` impide el rastreo de todo el sitio, sin tener en cuenta las reglas de las
+//! extensiones.
+//!
+//! Si alguna extensión publica entradas en el [mapa del sitio](crate::response::sitemap), se añade
+//! también su URL absoluta con la directiva `Sitemap`.
+//!
+//! # Ejemplo
+//!
+//! ```rust
+//! # use pagetop::prelude::*;
+//! pub struct Admin;
+//!
+//! impl Extension for Admin {
+//!     fn robots(&self) -> Vec<RobotsRule> {
+//!         vec![
+//!             RobotsRule::new("*").with_disallow("/admin"),
+//!             RobotsRule::new("GPTBot").with_disallow("/"),
+//!         ]
+//!     }
+//! }
+//! ```
+
+use crate::core::extension::all::{has_sitemap_entries, robots_rules};
+use crate::response::base_url;
+use crate::service::http::Method;
+use crate::service::{add_route, web, HttpRequest, HttpResponse, RouteKind};
+use crate::{global, CowStr};
+
+use std::fmt::Write;
+
+// **< RobotsRule >*********************************************************************************
+
+/// Grupo de reglas de `/robots.txt` para un agente de usuario.
+#[rustfmt::skip]
+#[derive(Clone, Debug)]
+pub struct RobotsRule {
+    user_agent : CowStr,
+    allow      : Vec<CowStr>,
+    disallow   : Vec<CowStr>,
+    crawl_delay: Option<u32>,
+}
+
+impl RobotsRule {
+    /// Crea un grupo de reglas para el agente de usuario indicado (`"*"` para todos).
+    pub fn new(user_agent: impl Into<CowStr>) -> Self {
+        RobotsRule {
+            user_agent: user_agent.into(),
+            allow: Vec::new(),
+            disallow: Vec::new(),
+            crawl_delay: None,
+        }
+    }
+
+    // **< RobotsRule BUILDER >*********************************************************************
+
+    /// Permite rastrear las rutas que empiezan por `path` (`Allow`).
+    pub fn with_allow(mut self, path: impl Into<CowStr>) -> Self {
+        self.allow.push(path.into());
+        self
+    }
+
+    /// Impide rastrear las rutas que empiezan por `path` (`Disallow`).
+    pub fn with_disallow(mut self, path: impl Into<CowStr>) -> Self {
+        self.disallow.push(path.into());
+        self
+    }
+
+    /// Establece los segundos de espera entre peticiones (`Crawl-delay`). No es una directiva
+    /// estándar y algunos rastreadores la ignoran.
+    pub fn with_crawl_delay(mut self, seconds: u32) -> Self {
+        self.crawl_delay = Some(seconds);
+        self
+    }
+
+    // **< RobotsRule GETTERS >*********************************************************************
+
+    /// Devuelve el agente de usuario al que se aplican las reglas.
+    pub fn user_agent(&self) -> &str {
+        &self.user_agent
+    }
+}
+
+// **< Servicios >**********************************************************************************
+
+// Configura la ruta de `/robots.txt`.
+pub(crate) fn configure_service(scfg: &mut web::ServiceConfig) {
+    add_route(Method::GET, "/robots.txt", None, RouteKind::Handler);
+    scfg.route("/robots.txt", web::get().to(robots));
+}
+
+async fn robots(request: HttpRequest) -> HttpResponse {
+    let mut txt = String::new();
+
+    let app = &global::SETTINGS.app;
+    if app.robots.allows_crawling(&app.run_mode) {
+        let groups = merge_rules(robots_rules());
+        if groups.is_empty() {
+            txt.push_str("User-agent: *\nDisallow:\n");
+        }
+        for (i, rule) in groups.iter().enumerate() {
+            if i > 0 {
+                txt.push('\n');
+            }
+            let _ = writeln!(txt, "User-agent: {}", rule.user_agent);
+            for path in &rule.allow {
+                let _ = writeln!(txt, "Allow: {path}");
+            }
+            for path in &rule.disallow {
+                let _ = writeln!(txt, "Disallow: {path}");
+            }
+            if rule.allow.is_empty() && rule.disallow.is_empty() {
+                txt.push_str("Disallow:\n");
+            }
+            if let Some(seconds) = rule.crawl_delay {
+                let _ = writeln!(txt, "Crawl-delay: {seconds}");
+            }
+        }
+        if has_sitemap_entries() {
+            let _ = write!(txt, "\nSitemap: {}/sitemap.xml\n", base_url(&request));
+        }
+    } else {
+        txt.push_str("User-agent: *\nDisallow: /\n");
+    }
+
+    HttpResponse::Ok()
+        .content_type("text/plain; charset=utf-8")
+        .body(txt)
+}
+
+// Agrupa las reglas por agente de usuario, respetando el orden en que aparecen por primera vez.
+fn merge_rules(rules: Vec<RobotsRule>) -> Vec<RobotsRule> {
+    let mut groups: Vec<RobotsRule> = Vec::new();
+    for rule in rules {
+        match groups
+            .iter_mut()
+            .find(|group| group.user_agent.eq_ignore_ascii_case(&rule.user_agent))
+        {
+            Some(group) => {
+                group.allow.extend(rule.allow);
+                group.disallow.extend(rule.disallow);
+                group.crawl_delay = rule.crawl_delay.or(group.crawl_delay);
+            }
+            None => groups.push(rule),
+        }
+    }
+    groups
+}
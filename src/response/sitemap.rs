@@ -0,0 +1,280 @@
+//! Mapa del sitio en formato XML (`/sitemap.xml`).
+//!
+//! Cada extensión puede aportar las URL que quiera publicar implementando
+//! [`Extension::sitemap()`](crate::core::extension::Extension::sitemap), que devuelve una lista de
+//! entradas [`SitemapEntry`]. PageTop reúne las entradas de todas las extensiones habilitadas y las
+//! sirve en `/sitemap.xml` siguiendo el [protocolo Sitemaps](https://www.sitemaps.org/protocol.html).
+//!
+//! Un mapa del sitio no puede superar las 50.000 URL. Si hay más entradas, `/sitemap.xml` devuelve
+//! un índice de mapas que apunta a `/sitemap-1.xml`, `/sitemap-2.xml`, etc., cada uno con un máximo
+//! de 50.000 URL.
+//!
+//! Las rutas relativas de las entradas se convierten en URL absolutas usando el esquema y el
+//! servidor de la petición recibida.
+//!
+//! # Ejemplo
+//!
+//! ```rust
+//! # use pagetop::prelude::*;
+//! pub struct Blog;
+//!
+//! impl Extension for Blog {
+//!     fn sitemap(&self) -> Vec<SitemapEntry> {
+//!         vec![
+//!             SitemapEntry::new("/blog")
+//!                 .with_changefreq(ChangeFreq::Daily)
+//!                 .with_priority(0.8)
+//!                 .with_language_alternates(),
+//!             SitemapEntry::new("/blog/hello-world")
+//!                 .with_lastmod(Utc.with_ymd_and_hms(2025, 3, 14, 10, 0, 0).unwrap()),
+//!         ]
+//!     }
+//! }
+//! ```
+
+use crate::core::extension::all::sitemap_entries;
+use crate::datetime::{DateTime, FixedOffset, SecondsFormat};
+use crate::html::Escaper;
+use crate::locale::Locale;
+use crate::response::page::ErrorPage;
+use crate::response::{absolute_url, base_url};
+use crate::service::http::Method;
+use crate::service::{add_route, web, HttpRequest, HttpResponse, RouteKind};
+use crate::{global, AutoDefault, CowStr};
+
+use std::fmt::Write;
+
+/// Número máximo de URL en un mapa del sitio, según el protocolo Sitemaps.
+pub const MAX_SITEMAP_URLS: usize = 50_000;
+
+// Tipo de contenido de las respuestas XML.
+const XML_CONTENT_TYPE: &str = "application/xml; charset=utf-8";
+
+// **< ChangeFreq >*********************************************************************************
+
+/// Frecuencia con la que cambia previsiblemente una página (`<changefreq>`).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ChangeFreq {
+    /// Cambia en cada acceso.
+    Always,
+    /// Cambia cada hora.
+    Hourly,
+    /// Cambia cada día.
+    Daily,
+    /// Cambia cada semana.
+    Weekly,
+    /// Cambia cada mes.
+    Monthly,
+    /// Cambia cada año.
+    Yearly,
+    /// Página archivada que no cambiará.
+    Never,
+}
+
+impl ChangeFreq {
+    /// Devuelve el valor para el elemento `<changefreq>`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Always => "always",
+            Self::Hourly => "hourly",
+            Self::Daily => "daily",
+            Self::Weekly => "weekly",
+            Self::Monthly => "monthly",
+            Self::Yearly => "yearly",
+            Self::Never => "never",
+        }
+    }
+}
+
+// **< SitemapEntry >*******************************************************************************
+
+/// Entrada de una URL en el mapa del sitio.
+#[rustfmt::skip]
+#[derive(AutoDefault, Clone, Debug)]
+pub struct SitemapEntry {
+    loc       : CowStr,
+    lastmod   : Option<DateTime<FixedOffset>>,
+    changefreq: Option<ChangeFreq>,
+    priority  : Option<f32>,
+    alternates: Vec<(CowStr, CowStr)>,
+    languages : bool,
+}
+
+impl SitemapEntry {
+    /// Crea una entrada para la ruta (por ejemplo, `"/blog"`) o la URL absoluta indicada.
+    pub fn new(loc: impl Into<CowStr>) -> Self {
+        SitemapEntry {
+            loc: loc.into(),
+            ..Default::default()
+        }
+    }
+
+    // **< SitemapEntry BUILDER >*******************************************************************
+
+    /// Establece la fecha de la última modificación (`<lastmod>`).
+    pub fn with_lastmod(mut self, lastmod: impl Into<DateTime<FixedOffset>>) -> Self {
+        self.lastmod = Some(lastmod.into());
+        self
+    }
+
+    /// Establece la frecuencia de cambio prevista (`<changefreq>`).
+    pub fn with_changefreq(mut self, changefreq: ChangeFreq) -> Self {
+        self.changefreq = Some(changefreq);
+        self
+    }
+
+    /// Establece la prioridad relativa de la URL (`<priority>`), entre `0.0` y `1.0`.
+    pub fn with_priority(mut self, priority: f32) -> Self {
+        self.priority = Some(priority.clamp(0.0, 1.0));
+        self
+    }
+
+    /// Añade una versión de la página en otro idioma (`<xhtml:link rel="alternate">`).
+    pub fn with_alternate(mut self, hreflang: impl Into<CowStr>, loc: impl Into<CowStr>) -> Self {
+        self.alternates.push((hreflang.into(), loc.into()));
+        self
+    }
+
+    /// Añade como versiones alternativas la página en cada idioma soportado por PageTop.
+    ///
+    /// Cada alternativa añade a la URL el parámetro `?lang=...`, por lo que sólo se generan si la
+    /// negociación de idioma de la aplicación lo admite
+    /// ([`LangNegotiation::Full`](crate::global::LangNegotiation::Full)). La propia URL se publica
+    /// además como alternativa `x-default`.
+    pub fn with_language_alternates(mut self) -> Self {
+        self.languages = true;
+        self
+    }
+
+    // **< SitemapEntry GETTERS >*******************************************************************
+
+    /// Devuelve la ruta o URL de la entrada.
+    pub fn loc(&self) -> &str {
+        &self.loc
+    }
+
+    // **< SitemapEntry RENDER >********************************************************************
+
+    fn render_to(&self, xml: &mut String, base: &str) {
+        xml.push_str("<url><loc>");
+        push_escaped(xml, &absolute_url(base, &self.loc));
+        xml.push_str("</loc>");
+        if let Some(lastmod) = &self.lastmod {
+            xml.push_str("<lastmod>");
+            xml.push_str(&lastmod.to_rfc3339_opts(SecondsFormat::Secs, true));
+            xml.push_str("</lastmod>");
+        }
+        if let Some(changefreq) = &self.changefreq {
+            xml.push_str("<changefreq>");
+            xml.push_str(changefreq.as_str());
+            xml.push_str("</changefreq>");
+        }
+        if let Some(priority) = self.priority {
+            let _ = write!(xml, "<priority>{priority:.1}</priority>");
+        }
+        for (hreflang, loc) in self.alternates() {
+            xml.push_str(r#"<xhtml:link rel="alternate" hreflang=""#);
+            push_escaped(xml, &hreflang);
+            xml.push_str(r#"" href=""#);
+            push_escaped(xml, &absolute_url(base, &loc));
+            xml.push_str(r#""/>"#);
+        }
+        xml.push_str("</url>");
+    }
+
+    // Alternativas explícitas y, si procede, las de cada idioma soportado.
+    fn alternates(&self) -> Vec<(CowStr, CowStr)> {
+        let mut alternates = self.alternates.clone();
+        if self.languages
+            && matches!(
+                global::SETTINGS.app.lang_negotiation,
+                global::LangNegotiation::Full
+            )
+        {
+            for langid in Locale::supported_langids() {
+                let separator = if self.loc.contains('?') { '&' } else { '?' };
+                let loc = format!("{}{separator}lang={langid}", self.loc);
+                alternates.push((langid.to_string().into(), loc.into()));
+            }
+            alternates.push(("x-default".into(), self.loc.clone()));
+        }
+        alternates
+    }
+}
+
+// **< Servicios >**********************************************************************************
+
+// Configura las rutas del mapa del sitio.
+pub(crate) fn configure_service(scfg: &mut web::ServiceConfig) {
+    add_route(Method::GET, "/sitemap.xml", None, RouteKind::Handler);
+    add_route(
+        Method::GET,
+        r"/sitemap-{part:\d+}.xml",
+        None,
+        RouteKind::Handler,
+    );
+    scfg.route("/sitemap.xml", web::get().to(sitemap))
+        .route(r"/sitemap-{part:\d+}.xml", web::get().to(sitemap_part));
+}
+
+// Devuelve el mapa del sitio o, si hay demasiadas URL, el índice de mapas.
+async fn sitemap(request: HttpRequest) -> HttpResponse {
+    let entries = sitemap_entries();
+    let base = base_url(&request);
+
+    let xml = if entries.len() <= MAX_SITEMAP_URLS {
+        render_urlset(&entries, &base)
+    } else {
+        let mut xml = String::from(
+            r#"<?xml version="1.0" encoding="UTF-8"?><sitemapindex xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">"#,
+        );
+        for part in 1..=entries.len().div_ceil(MAX_SITEMAP_URLS) {
+            xml.push_str("<sitemap><loc>");
+            push_escaped(&mut xml, &format!("{base}/sitemap-{part}.xml"));
+            xml.push_str("</loc></sitemap>");
+        }
+        xml.push_str("</sitemapindex>");
+        xml
+    };
+
+    HttpResponse::Ok().content_type(XML_CONTENT_TYPE).body(xml)
+}
+
+// Devuelve una de las partes del mapa del sitio cuando se ha dividido.
+async fn sitemap_part(
+    request: HttpRequest,
+    part: web::Path<usize>,
+) -> Result<HttpResponse, ErrorPage> {
+    let entries = sitemap_entries();
+    let part = part.into_inner();
+
+    let chunk = if entries.len() > MAX_SITEMAP_URLS && part > 0 {
+        entries.chunks(MAX_SITEMAP_URLS).nth(part - 1)
+    } else {
+        None
+    };
+
+    match chunk {
+        Some(chunk) => Ok(HttpResponse::Ok()
+            .content_type(XML_CONTENT_TYPE)
+            .body(render_urlset(chunk, &base_url(&request)))),
+        None => Err(ErrorPage::NotFound(request)),
+    }
+}
+
+// **< Helpers >************************************************************************************
+
+fn render_urlset(entries: &[SitemapEntry], base: &str) -> String {
+    let mut xml = String::from(
+        r#"<?xml version="1.0" encoding="UTF-8"?><urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9" xmlns:xhtml="http://www.w3.org/1999/xhtml">"#,
+    );
+    for entry in entries {
+        entry.render_to(&mut xml, base);
+    }
+    xml.push_str("</urlset>");
+    xml
+}
+
+fn push_escaped(xml: &mut String, text: &str) {
+    let _ = Escaper::new(xml).write_str(text);
+}
@@ -0,0 +1,126 @@
+//! Documentos en rutas conocidas (`/.well-known/*`).
+//!
+//! El [RFC 8615](https://www.rfc-editor.org/rfc/rfc8615) reserva el prefijo `/.well-known/` para
+//! publicar metadatos del sitio en rutas acordadas, como `security.txt` o `change-password`. Cada
+//! extensión puede aportar sus documentos implementando
+//! [`Extension::well_known()`](crate::core::extension::Extension::well_known), que devuelve una
+//! lista de [`WellKnown`].
+//!
+//! Si dos extensiones publican un documento con el mismo nombre, prevalece el de la extensión
+//! habilitada en primer lugar. Las rutas sin documento devuelven un error 404 sin contenido.
+//!
+//! # Ejemplo
+//!
+//! ```rust
+//! # use pagetop::prelude::*;
+//! pub struct Security;
+//!
+//! impl Extension for Security {
+//!     fn well_known(&self) -> Vec<WellKnown> {
+//!         vec![
+//!             WellKnown::text(
+//!                 "security.txt",
+//!                 "Contact: mailto:security@example.com\nExpires: 2030-01-01T00:00:00Z\n",
+//!             ),
+//!             WellKnown::redirect("change-password", "/account/password"),
+//!         ]
+//!     }
+//! }
+//! ```
+
+use crate::core::extension::all::well_known_documents;
+use crate::response::redirect::Redirect;
+use crate::service::http::Method;
+use crate::service::{add_route, web, HttpResponse, RouteKind};
+use crate::CowStr;
+
+#[derive(Clone, Debug)]
+enum Content {
+    Document { content_type: CowStr, body: CowStr },
+    Redirect(CowStr),
+}
+
+// **< WellKnown >**********************************************************************************
+
+/// Documento publicado en `/.well-known/{name}`.
+#[derive(Clone, Debug)]
+pub struct WellKnown {
+    name: CowStr,
+    content: Content,
+}
+
+impl WellKnown {
+    /// Crea un documento con el nombre, tipo de contenido y cuerpo indicados.
+    pub fn new(
+        name: impl Into<CowStr>,
+        content_type: impl Into<CowStr>,
+        body: impl Into<CowStr>,
+    ) -> Self {
+        WellKnown {
+            name: name.into(),
+            content: Content::Document {
+                content_type: content_type.into(),
+                body: body.into(),
+            },
+        }
+    }
+
+    /// Crea un documento de texto plano, como `security.txt`.
+    pub fn text(name: impl Into<CowStr>, body: impl Into<CowStr>) -> Self {
+        Self::new(name, "text/plain; charset=utf-8", body)
+    }
+
+    /// Crea un documento JSON, como `assetlinks.json`.
+    pub fn json(name: impl Into<CowStr>, value: impl serde::Serialize) -> Self {
+        let body = serde_json::to_string(&value).unwrap_or_default();
+        Self::new(name, "application/json", body)
+    }
+
+    /// Crea una redirección temporal (302) a otra URL, como la que espera `change-password`.
+    pub fn redirect(name: impl Into<CowStr>, location: impl Into<CowStr>) -> Self {
+        WellKnown {
+            name: name.into(),
+            content: Content::Redirect(location.into()),
+        }
+    }
+
+    // **< WellKnown GETTERS >**********************************************************************
+
+    /// Devuelve el nombre del documento, la ruta relativa a `/.well-known/`.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    // **< WellKnown RESPONSE >*********************************************************************
+
+    fn response(&self) -> HttpResponse {
+        match &self.content {
+            Content::Document { content_type, body } => HttpResponse::Ok()
+                .content_type(content_type.as_ref())
+                .body(body.to_string()),
+            Content::Redirect(location) => Redirect::found(location),
+        }
+    }
+}
+
+// **< Servicios >**********************************************************************************
+
+// Configura las rutas de `/.well-known/`.
+pub(crate) fn configure_service(scfg: &mut web::ServiceConfig) {
+    add_route(
+        Method::GET,
+        "/.well-known/{name:.*}",
+        None,
+        RouteKind::Handler,
+    );
+    scfg.route("/.well-known/{name:.*}", web::get().to(well_known));
+}
+
+async fn well_known(name: web::Path<String>) -> HttpResponse {
+    let name = name.into_inner();
+    well_known_documents()
+        .iter()
+        .find(|document| document.name.trim_matches('/') == name.trim_matches('/'))
+        .map(WellKnown::response)
+        .unwrap_or_else(|| HttpResponse::NotFound().finish())
+}
@@ -3,6 +3,7 @@
 pub use actix_session::Session;
 pub use actix_web::body::BoxBody;
 pub use actix_web::dev::Server;
+pub use actix_web::dev::ServerHandle;
 pub use actix_web::dev::ServiceFactory as Factory;
 pub use actix_web::dev::ServiceRequest as Request;
 pub use actix_web::dev::ServiceResponse as Response;
@@ -12,9 +13,56 @@ pub use actix_web_files::Files as ActixFiles;
 
 pub use pagetop_statics::ResourceFiles;
 
+mod route;
+pub(crate) use route::configure_routes;
+pub use route::{url_path, Route, RouteError};
+
+mod inventory;
+#[doc(hidden)]
+pub use inventory::register_static_dir;
+pub(crate) use inventory::{
+    add_route, add_unlisted, format_routes, publish_routes, set_route_owner,
+};
+pub use inventory::{routes, unlisted_routes, RouteInfo, RouteKind};
+
+use crate::StaticResource;
+
+use parking_lot::RwLock;
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::LazyLock;
+
 #[doc(hidden)]
 pub use actix_web::test;
 
+// **< RECURSOS EMBEBIDOS >*************************************************************************
+
+// Contenido de los recursos embebidos publicados, indexados por su ruta URL.
+static STATIC_FILES: LazyLock<RwLock<BTreeMap<String, &'static [u8]>>> =
+    LazyLock::new(|| RwLock::new(BTreeMap::new()));
+
+/// Registra los recursos embebidos que publica [`static_files_service!`](crate::static_files_service)
+/// para poder exportarlos después con
+/// [`Application::export()`](crate::app::Application::export), y los añade al inventario de rutas.
+#[doc(hidden)]
+pub fn register_static_files(route: &str, files: &HashMap<&'static str, StaticResource>) {
+    add_route(http::Method::GET, route, None, RouteKind::EmbeddedFiles);
+    let route = route.trim_end_matches('/');
+    let mut registry = STATIC_FILES.write();
+    for (name, resource) in files {
+        registry.insert(format!("{route}/{name}"), resource.data);
+    }
+}
+
+// Devuelve las rutas URL y el contenido de todos los recursos embebidos publicados.
+pub(crate) fn static_files() -> Vec<(String, &'static [u8])> {
+    STATIC_FILES
+        .read()
+        .iter()
+        .map(|(path, data)| (path.clone(), *data))
+        .collect()
+}
+
 // **< static_files_service! >**********************************************************************
 
 /// Configura un servicio web para publicar archivos estáticos.
@@ -69,6 +117,7 @@ macro_rules! static_files_service {
             let mut serve_embedded: bool = true;
             if !::std::path::Path::new(&$path).as_os_str().is_empty() {
                 if let Ok(absolute) = $crate::util::resolve_absolute_dir($path) {
+                    $crate::service::register_static_dir($route);
                     $scfg.service($crate::service::ActixFiles::new($route, absolute));
                     serve_embedded = false;
                 }
@@ -78,10 +127,9 @@ macro_rules! static_files_service {
                     mod [<static_files_ $bundle>] {
                         include!(concat!(env!("OUT_DIR"), "/", stringify!($bundle), ".rs"));
                     }
-                    $scfg.service($crate::service::ResourceFiles::new(
-                        $route,
-                        [<static_files_ $bundle>]::$bundle(),
-                    ));
+                    let files = [<static_files_ $bundle>]::$bundle();
+                    $crate::service::register_static_files($route, &files);
+                    $scfg.service($crate::service::ResourceFiles::new($route, files));
                 }
             }
         });
@@ -98,10 +146,9 @@ macro_rules! static_files_service {
                 mod [<static_files_ $bundle>] {
                     include!(concat!(env!("OUT_DIR"), "/", stringify!($bundle), ".rs"));
                 }
-                $scfg.service($crate::service::ResourceFiles::new(
-                    $route,
-                    [<static_files_ $bundle>]::$bundle(),
-                ));
+                let files = [<static_files_ $bundle>]::$bundle();
+                $crate::service::register_static_files($route, &files);
+                $scfg.service($crate::service::ResourceFiles::new($route, files));
             }
         });
     }};
@@ -114,6 +161,7 @@ macro_rules! static_files_service {
         );
         let _ = span.in_scope(|| match $crate::util::resolve_absolute_dir($path) {
             Ok(absolute) => {
+                $crate::service::register_static_dir($route);
                 $scfg.service($crate::service::ActixFiles::new($route, absolute));
             }
             Err(e) => {
@@ -0,0 +1,177 @@
+use crate::service::http::Method;
+use crate::trace;
+
+use parking_lot::RwLock;
+
+use std::cell::RefCell;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::LazyLock;
+
+// Inventario de las rutas configuradas en el servicio web.
+static ROUTES: LazyLock<RwLock<Vec<RouteInfo>>> = LazyLock::new(|| RwLock::new(Vec::new()));
+
+// Extensiones que configuran rutas que no aparecen en el inventario.
+static UNLISTED: LazyLock<RwLock<Vec<&'static str>>> = LazyLock::new(|| RwLock::new(Vec::new()));
+
+// Indica si ya se ha mostrado el inventario en la traza de eventos.
+static LOGGED: AtomicBool = AtomicBool::new(false);
+
+// Cada *worker* del servidor configura su propio servicio web, por lo que el inventario se prepara
+// en el hilo que lo configura y se publica al terminar.
+thread_local! {
+    static PENDING: RefCell<(&'static str, Vec<RouteInfo>, Vec<&'static str>)> =
+        const { RefCell::new(("PageTop", Vec::new(), Vec::new())) };
+}
+
+// **< RouteKind >**********************************************************************************
+
+/// Tipo de servicio asociado a una entrada del inventario de rutas.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RouteKind {
+    /// Ruta atendida por un manejador.
+    Handler,
+    /// Archivos estáticos integrados en el binario y publicados bajo la ruta base.
+    EmbeddedFiles,
+    /// Archivos estáticos servidos desde el sistema de ficheros bajo la ruta base.
+    FileSystem,
+}
+
+// **< RouteInfo >**********************************************************************************
+
+/// Entrada del inventario de rutas del servicio web.
+///
+/// Registra el método HTTP, el patrón de la ruta, su nombre si es una [ruta con
+/// nombre](crate::service::Route) y la extensión que la configura. Ver [`routes()`].
+#[rustfmt::skip]
+#[derive(Clone, Debug)]
+pub struct RouteInfo {
+    method : Method,
+    pattern: String,
+    name   : Option<String>,
+    owner  : &'static str,
+    kind   : RouteKind,
+}
+
+impl RouteInfo {
+    /// Devuelve el método HTTP que atiende la ruta.
+    pub fn method(&self) -> &Method {
+        &self.method
+    }
+
+    /// Devuelve el patrón de la ruta, o la ruta base en el caso de los archivos estáticos.
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    /// Devuelve el nombre de la ruta, si es una ruta con nombre.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Devuelve el nombre corto de la extensión que configura la ruta, o `"PageTop"` para las
+    /// rutas propias de PageTop.
+    pub fn owner(&self) -> &'static str {
+        self.owner
+    }
+
+    /// Devuelve el tipo de servicio asociado a la ruta.
+    pub fn kind(&self) -> RouteKind {
+        self.kind
+    }
+}
+
+// **< routes() >***********************************************************************************
+
+/// Devuelve el inventario de las rutas configuradas en el servicio web, en el orden en que se
+/// configuran.
+///
+/// Incluye las rutas propias de PageTop (como `/robots.txt` o `/sitemap.xml`), las [rutas con
+/// nombre](crate::service::Route) de cada extensión y los archivos estáticos publicados con
+/// [`static_files_service!`](crate::static_files_service). Las rutas que una extensión añade
+/// directamente en [`configure_service()`](crate::core::extension::Extension::configure_service) no
+/// se pueden inspeccionar; para que aparezcan en el inventario hay que declararlas en
+/// [`routes()`](crate::core::extension::Extension::routes). Ver [`unlisted_routes()`].
+///
+/// El inventario se completa al configurar el servicio web, cuando la aplicación arranca con
+/// [`run()`](crate::app::Application::run) o se prepara con
+/// [`test()`](crate::app::Application::test). Con el nivel de trazas *"Debug"* se muestra también
+/// en la traza de eventos al arrancar.
+pub fn routes() -> Vec<RouteInfo> {
+    ROUTES.read().clone()
+}
+
+/// Devuelve los nombres cortos de las extensiones que indican con
+/// [`has_unlisted_routes()`](crate::core::extension::Extension::has_unlisted_routes) que configuran
+/// rutas que no aparecen en el inventario de [`routes()`].
+pub fn unlisted_routes() -> Vec<&'static str> {
+    UNLISTED.read().clone()
+}
+
+// Establece la extensión a la que se asignan las siguientes rutas del inventario.
+pub(crate) fn set_route_owner(owner: &'static str) {
+    PENDING.with_borrow_mut(|(current, _, _)| *current = owner);
+}
+
+// Anota la extensión actual entre las que tienen rutas fuera del inventario.
+pub(crate) fn add_unlisted() {
+    PENDING.with_borrow_mut(|(owner, _, unlisted)| unlisted.push(*owner));
+}
+
+// Añade una ruta al inventario en preparación.
+pub(crate) fn add_route(method: Method, pattern: &str, name: Option<&str>, kind: RouteKind) {
+    PENDING.with_borrow_mut(|(owner, routes, _)| {
+        routes.push(RouteInfo {
+            method,
+            pattern: pattern.to_owned(),
+            name: name.map(str::to_owned),
+            owner,
+            kind,
+        })
+    });
+}
+
+/// Añade al inventario de rutas los archivos estáticos que publica
+/// [`static_files_service!`](crate::static_files_service) desde el sistema de ficheros.
+#[doc(hidden)]
+pub fn register_static_dir(route: &str) {
+    add_route(Method::GET, route, None, RouteKind::FileSystem);
+}
+
+// Publica el inventario preparado y lo muestra en la traza de eventos la primera vez.
+pub(crate) fn publish_routes() {
+    let (routes, unlisted) = PENDING.with_borrow_mut(|(owner, routes, unlisted)| {
+        *owner = "PageTop";
+        (std::mem::take(routes), std::mem::take(unlisted))
+    });
+    if !LOGGED.swap(true, Ordering::Relaxed) {
+        trace::debug!(
+            "Registered routes:\n{}",
+            format_routes(&routes, &unlisted).trim_end()
+        );
+    }
+    *ROUTES.write() = routes;
+    *UNLISTED.write() = unlisted;
+}
+
+// Da formato al inventario de rutas, con una ruta por línea, y añade al final una línea por cada
+// extensión con rutas fuera del inventario.
+pub(crate) fn format_routes(routes: &[RouteInfo], unlisted: &[&str]) -> String {
+    let mut list = String::new();
+    for route in routes {
+        let _ = write!(list, "  {:<7} {}", route.method.as_str(), route.pattern);
+        match route.kind {
+            RouteKind::Handler => {}
+            RouteKind::EmbeddedFiles => list.push_str(" [embedded files]"),
+            RouteKind::FileSystem => list.push_str(" [file system]"),
+        }
+        if let Some(name) = &route.name {
+            let _ = write!(list, " \"{name}\"");
+        }
+        let _ = writeln!(list, " ({})", route.owner);
+    }
+    for owner in unlisted {
+        let _ = writeln!(list, "  {:<7} [unlisted routes] ({owner})", "*");
+    }
+    list
+}
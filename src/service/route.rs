@@ -0,0 +1,258 @@
+use crate::html::route::encode_segment;
+use crate::html::RoutePath;
+use crate::service::http::Method;
+use crate::service::{add_route, web, RouteKind};
+use crate::{trace, CowStr};
+
+use actix_web::{FromRequest, Handler, Responder};
+use parking_lot::RwLock;
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::LazyLock;
+
+// Patrones de las rutas con nombre, indexados por su nombre.
+static NAMED_ROUTES: LazyLock<RwLock<HashMap<String, String>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+// **< Route >**************************************************************************************
+
+/// Ruta web con nombre que declara una extensión.
+///
+/// Las extensiones devuelven sus rutas en
+/// [`Extension::routes()`](crate::core::extension::Extension::routes). PageTop las configura en el
+/// servicio web y guarda su patrón para generar después las URL a partir del nombre, con
+/// [`Context::url_for()`](crate::core::component::Context::url_for) o [`url_path()`], en lugar de
+/// repetir las rutas como literales.
+///
+/// El patrón sigue la sintaxis de Actix Web: los segmentos dinámicos se escriben entre llaves, como
+/// `{name}`, y admiten una expresión regular opcional, como `{id:\d+}`.
+///
+/// # Ejemplo
+///
+/// ```rust
+/// # use pagetop::prelude::*;
+/// pub struct Hello;
+///
+/// impl Extension for Hello {
+///     fn routes(&self) -> Vec<service::Route> {
+///         vec![service::Route::get("hello", "/hello/{name}").to(hello)]
+///     }
+/// }
+///
+/// async fn hello(request: HttpRequest, name: service::web::Path<String>) -> ResultPage<Markup, ErrorPage> {
+///     let name = name.into_inner();
+///     Page::new(request)
+///         .with_child(Html::with(move |cx| html! {
+///             h1 { "Hello " (name) "!" }
+///             a href=(cx.url_for("hello", &[("name", "World")])) { "Say hello to the world" }
+///         }))
+///         .render()
+/// }
+/// ```
+pub struct Route {
+    name: CowStr,
+    pattern: CowStr,
+    method: Method,
+    route: actix_web::Route,
+}
+
+impl Route {
+    /// Crea una ruta con nombre para el método HTTP y el patrón indicados.
+    pub fn new(method: Method, name: impl Into<CowStr>, pattern: impl Into<CowStr>) -> Self {
+        Route {
+            name: name.into(),
+            pattern: pattern.into(),
+            route: web::method(method.clone()),
+            method,
+        }
+    }
+
+    /// Crea una ruta con nombre para peticiones `GET`.
+    pub fn get(name: impl Into<CowStr>, pattern: impl Into<CowStr>) -> Self {
+        Self::new(Method::GET, name, pattern)
+    }
+
+    /// Crea una ruta con nombre para peticiones `POST`.
+    pub fn post(name: impl Into<CowStr>, pattern: impl Into<CowStr>) -> Self {
+        Self::new(Method::POST, name, pattern)
+    }
+
+    /// Crea una ruta con nombre para peticiones `PUT`.
+    pub fn put(name: impl Into<CowStr>, pattern: impl Into<CowStr>) -> Self {
+        Self::new(Method::PUT, name, pattern)
+    }
+
+    /// Crea una ruta con nombre para peticiones `PATCH`.
+    pub fn patch(name: impl Into<CowStr>, pattern: impl Into<CowStr>) -> Self {
+        Self::new(Method::PATCH, name, pattern)
+    }
+
+    /// Crea una ruta con nombre para peticiones `DELETE`.
+    pub fn delete(name: impl Into<CowStr>, pattern: impl Into<CowStr>) -> Self {
+        Self::new(Method::DELETE, name, pattern)
+    }
+
+    /// Asigna el manejador que atiende las peticiones de la ruta.
+    pub fn to<F, Args>(mut self, handler: F) -> Self
+    where
+        F: Handler<Args>,
+        Args: FromRequest + 'static,
+        F::Output: Responder + 'static,
+    {
+        self.route = self.route.to(handler);
+        self
+    }
+
+    // **< Route GETTERS >**************************************************************************
+
+    /// Devuelve el nombre de la ruta.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Devuelve el patrón de la ruta.
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    /// Devuelve el método HTTP de la ruta.
+    pub fn method(&self) -> &Method {
+        &self.method
+    }
+}
+
+// Configura las rutas en el servicio web y registra sus nombres. Las rutas con el mismo patrón se
+// agrupan en un único recurso para que cada método llegue a su manejador.
+pub(crate) fn configure_routes(scfg: &mut web::ServiceConfig, routes: Vec<Route>) {
+    let mut resources: Vec<(CowStr, Vec<actix_web::Route>)> = Vec::new();
+    for route in routes {
+        register_name(&route.name, &route.pattern);
+        add_route(
+            route.method.clone(),
+            &route.pattern,
+            Some(&route.name),
+            RouteKind::Handler,
+        );
+        match resources
+            .iter_mut()
+            .find(|(pattern, _)| *pattern == route.pattern)
+        {
+            Some((_, methods)) => methods.push(route.route),
+            None => resources.push((route.pattern, vec![route.route])),
+        }
+    }
+    for (pattern, methods) in resources {
+        let resource = methods
+            .into_iter()
+            .fold(web::resource(pattern.as_ref()), |resource, route| {
+                resource.route(route)
+            });
+        scfg.service(resource);
+    }
+}
+
+fn register_name(name: &str, pattern: &str) {
+    let mut routes = NAMED_ROUTES.write();
+    match routes.get(name) {
+        Some(registered) if registered != pattern => {
+            trace::warn!(
+                "Route name \"{name}\" is already used by \"{registered}\", ignoring \"{pattern}\""
+            );
+        }
+        Some(_) => {}
+        None => {
+            routes.insert(name.to_owned(), pattern.to_owned());
+        }
+    }
+}
+
+// **< RouteError >*********************************************************************************
+
+/// Errores al generar la URL de una ruta con nombre.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RouteError {
+    /// No hay ninguna ruta registrada con el nombre indicado.
+    UnknownRoute(String),
+    /// Falta el valor de un segmento dinámico del patrón de la ruta.
+    MissingParam { route: String, param: String },
+}
+
+impl fmt::Display for RouteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RouteError::UnknownRoute(name) => write!(f, "unknown route \"{name}\""),
+            RouteError::MissingParam { route, param } => {
+                write!(f, "missing parameter \"{param}\" for route \"{route}\"")
+            }
+        }
+    }
+}
+
+impl std::error::Error for RouteError {}
+
+// **< url_path() >*********************************************************************************
+
+/// Genera la ruta de una ruta con nombre sustituyendo los segmentos dinámicos de su patrón.
+///
+/// Cada segmento `{param}` del patrón se sustituye por el valor del parámetro con el mismo nombre,
+/// codificado como segmento de la ruta. Los parámetros que no aparecen en el patrón se añaden como
+/// parámetros de consulta, en el orden dado.
+///
+/// Devuelve [`RouteError::UnknownRoute`] si no hay ninguna ruta con ese nombre, o
+/// [`RouteError::MissingParam`] si falta algún segmento del patrón.
+pub fn url_path(name: &str, params: &[(&str, &str)]) -> Result<RoutePath, RouteError> {
+    let Some(pattern) = NAMED_ROUTES.read().get(name).cloned() else {
+        return Err(RouteError::UnknownRoute(name.to_owned()));
+    };
+
+    let mut path = String::with_capacity(pattern.len());
+    let mut used = Vec::new();
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '{' {
+            path.push(c);
+            continue;
+        }
+        // Lee el segmento hasta la llave de cierre, admitiendo llaves en la expresión regular.
+        let mut segment = String::new();
+        let mut depth = 1;
+        for c in chars.by_ref() {
+            match c {
+                '{' => depth += 1,
+                '}' => depth -= 1,
+                _ => {}
+            }
+            if depth == 0 {
+                break;
+            }
+            segment.push(c);
+        }
+        // Los segmentos finales del tipo `{tail}*` o con expresiones como `{path:.*}` pueden
+        // abarcar varios segmentos, por lo que mantienen las `/` del valor.
+        let tail = chars.next_if_eq(&'*').is_some();
+        let (param, regex) = segment.split_once(':').unwrap_or((&segment, ""));
+        let param = param.trim();
+        let keep_slash = tail || regex.contains(".*") || regex.contains(".+");
+        match params.iter().find(|(key, _)| *key == param) {
+            Some((_, value)) => {
+                encode_segment(&mut path, value, keep_slash);
+                used.push(param.to_owned());
+            }
+            None => {
+                return Err(RouteError::MissingParam {
+                    route: name.to_owned(),
+                    param: param.to_owned(),
+                })
+            }
+        }
+    }
+
+    let mut route = RoutePath::new(path);
+    for (key, value) in params {
+        if !used.iter().any(|param| param == key) {
+            route.alter_param(*key, *value);
+        }
+    }
+    Ok(route)
+}
@@ -13,6 +13,7 @@
 //! de ejecución y puede existir dentro de un árbol anidado de *spans* similares. Además, estos
 //! *spans* son estructurados, con la capacidad de registrar tipos de datos y mensajes de texto.
 
+use crate::config::DynamicSetting;
 use crate::global;
 use crate::global::{LogFormat, LogRolling};
 
@@ -68,12 +69,34 @@ pub(crate) static TRACING: LazyLock<WorkerGuard> = LazyLock::new(|| {
         .with_writer(non_blocking)
         .with_ansi(matches!(rolling, LogRolling::Stdout));
 
-    match global::SETTINGS.log.format {
-        LogFormat::Json => subscriber.json().init(),
-        LogFormat::Full => subscriber.init(),
-        LogFormat::Compact => subscriber.compact().init(),
-        LogFormat::Pretty => subscriber.pretty().init(),
+    // Instala el subscriber y devuelve la función que actualiza su filtro.
+    macro_rules! init_reloading {
+        ( $subscriber:expr ) => {{
+            let subscriber = $subscriber.with_filter_reloading();
+            let handle = subscriber.reload_handle();
+            subscriber.init();
+            Box::new(move |filter| handle.reload(filter).map_err(|e| e.to_string()))
+                as Box<dyn Fn(EnvFilter) -> Result<(), String> + Send + Sync>
+        }};
     }
+    let reload_filter = match global::SETTINGS.log.format {
+        LogFormat::Json => init_reloading!(subscriber.json()),
+        LogFormat::Full => init_reloading!(subscriber),
+        LogFormat::Compact => init_reloading!(subscriber.compact()),
+        LogFormat::Pretty => init_reloading!(subscriber.pretty()),
+    };
+
+    // Actualiza el filtro de las trazas al recargar la configuración.
+    LOG_TRACING.on_change(move |tracing| match EnvFilter::try_new(tracing) {
+        Ok(filter) => match reload_filter(filter) {
+            Ok(()) => info!("Tracing filter changed to \"{tracing}\""),
+            Err(e) => warn!("Failed to change tracing filter: {e}"),
+        },
+        Err(e) => warn!("Invalid tracing filter \"{tracing}\": {e}"),
+    });
 
     guard
 });
+
+// Filtro de las trazas, que se puede cambiar sin reiniciar la aplicación.
+static LOG_TRACING: DynamicSetting<String> = DynamicSetting::new("log.tracing");
@@ -2,8 +2,11 @@
 
 use crate::trace;
 
+use serde::Serialize;
+
 use std::borrow::Cow;
 use std::env;
+use std::fs;
 use std::io;
 use std::path::{Path, PathBuf};
 
@@ -198,3 +201,35 @@ pub fn resolve_absolute_dir<P: AsRef<Path>>(path: P) -> io::Result<PathBuf> {
         })
     }
 }
+
+/// Guarda `value` en formato JSON en la ruta indicada, sustituyendo el archivo de forma atómica.
+///
+/// Crea el directorio padre si no existe y escribe primero en un archivo temporal junto al destino
+/// (con el nombre completo del archivo seguido de `.tmp`) que después se renombra. Así, una
+/// interrupción a mitad de escritura nunca deja el archivo original a medias.
+///
+/// # Ejemplos
+///
+/// ```rust,no_run
+/// # use pagetop::prelude::*;
+/// let state = vec!["first", "second"];
+/// util::write_json_atomic("data/state.json", &state).unwrap();
+/// ```
+pub fn write_json_atomic<P: AsRef<Path>, T: Serialize + ?Sized>(
+    path: P,
+    value: &T,
+) -> io::Result<()> {
+    let path = path.as_ref();
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        fs::create_dir_all(parent)?;
+    }
+    let content = serde_json::to_string_pretty(value).map_err(io::Error::other)?;
+    let mut temporary = path.as_os_str().to_os_string();
+    temporary.push(".tmp");
+    let temporary = PathBuf::from(temporary);
+    fs::write(&temporary, content)?;
+    fs::rename(&temporary, path)
+}
@@ -0,0 +1,97 @@
+use pagetop::prelude::*;
+
+use std::sync::Mutex;
+
+static NOTIFIED: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+declare_hook! {
+    /// Avisa de que se ha guardado un contenido.
+    pub ContentSaved(title: &str, revisions: &mut u32);
+}
+
+declare_hook! {
+    /// Altera el título de un contenido.
+    pub AlterTitle(lang: &str) alters String;
+}
+
+declare_hook! {
+    /// Reúne los enlaces de un menú.
+    pub CollectLinks(menu: &str) collects String;
+}
+
+declare_hook! {
+    /// Busca la ruta de un alias.
+    pub ResolveAlias(alias: &str) finds String;
+}
+
+declare_hook! {
+    /// Gancho sin funciones registradas.
+    pub Unused(value: u8) collects u8;
+}
+
+struct Content;
+
+impl Extension for Content {
+    fn actions(&self) -> Vec<ActionBox> {
+        actions![
+            ContentSaved::new(|title, revisions| {
+                *revisions += 1;
+                NOTIFIED.lock().unwrap().push(format!("content {title}"));
+            }),
+            AlterTitle::new(|_, title| format!("{title}!")),
+            CollectLinks::new(|menu| format!("{menu}/content")),
+            ResolveAlias::new(|alias| (alias == "home").then(|| "/".to_string())),
+        ]
+    }
+}
+
+struct Blog;
+
+impl Extension for Blog {
+    fn dependencies(&self) -> Vec<ExtensionRef> {
+        vec![&Content]
+    }
+
+    fn actions(&self) -> Vec<ActionBox> {
+        actions![
+            ContentSaved::new(|title, revisions| {
+                *revisions += 1;
+                NOTIFIED.lock().unwrap().push(format!("blog {title}"));
+            })
+            .with_weight(-1),
+            AlterTitle::new(|lang, title| format!("[{lang}] {title}")).with_weight(-1),
+            CollectLinks::new(|menu| format!("{menu}/blog")).with_weight(10),
+            ResolveAlias::new(|alias| Some(format!("/blog/{alias}"))).with_weight(5),
+        ]
+    }
+}
+
+#[pagetop::test]
+async fn hooks_aggregate_results_by_weight() {
+    Application::prepare(&Blog);
+
+    // Todas las funciones se ejecutan, las de pesos más bajos antes.
+    let mut revisions = 0;
+    ContentSaved::dispatch("About", &mut revisions);
+    assert_eq!(revisions, 2);
+    assert_eq!(*NOTIFIED.lock().unwrap(), ["blog About", "content About"]);
+
+    // Cada función altera el valor que devuelve la anterior.
+    assert_eq!(AlterTitle::dispatch("es", "Hola".to_string()), "[es] Hola!");
+
+    // Se devuelven todos los resultados, en orden de peso.
+    assert_eq!(
+        CollectLinks::dispatch("main"),
+        ["main/content", "main/blog"]
+    );
+
+    // Gana el primer resultado, y las siguientes funciones ya no se ejecutan.
+    assert_eq!(ResolveAlias::dispatch("home").as_deref(), Some("/"));
+    assert_eq!(
+        ResolveAlias::dispatch("news").as_deref(),
+        Some("/blog/news")
+    );
+
+    // Sin funciones registradas se devuelve el resultado vacío.
+    assert!(Unused::dispatch(1).is_empty());
+}
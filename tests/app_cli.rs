@@ -0,0 +1,62 @@
+use pagetop::prelude::*;
+
+use std::sync::Mutex;
+
+static GREETED: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+struct Greeter;
+
+impl Extension for Greeter {
+    fn commands(&self) -> Vec<cli::Command> {
+        vec![cli::Command::new("greet")
+            .with_about("Greets someone")
+            .with_arg(cli::Arg::new("name").long("name").default_value("World"))
+            .to(greet)]
+    }
+}
+
+async fn greet(args: cli::ArgMatches) -> std::io::Result<()> {
+    let name = args.get_one::<String>("name").unwrap();
+    GREETED.lock().unwrap().push(name.clone());
+    Ok(())
+}
+
+#[pagetop::test]
+async fn cli_runs_builtin_and_extension_commands() {
+    // Subcomandos de las extensiones.
+    Application::prepare(&Greeter)
+        .cli_from(["app", "greet", "--name", "Ana"])
+        .await
+        .unwrap();
+    Application::prepare(&Greeter)
+        .cli_from(["app", "greet"])
+        .await
+        .unwrap();
+    assert_eq!(*GREETED.lock().unwrap(), vec!["Ana", "World"]);
+
+    // Subcomandos predefinidos que sólo muestran información.
+    for command in ["routes", "extensions", "config", "check"] {
+        Application::prepare(&Greeter)
+            .cli_from(["app", command])
+            .await
+            .unwrap_or_else(|e| panic!("command \"{command}\" failed: {e}"));
+    }
+
+    // La ayuda no es un error, pero sí los subcomandos desconocidos.
+    assert!(Application::prepare(&Greeter)
+        .cli_from(["app", "--help"])
+        .await
+        .is_ok());
+    let error = Application::prepare(&Greeter)
+        .cli_from(["app", "unknown"])
+        .await
+        .unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+
+    // Los ajustes `--set` requieren preparar la aplicación para la línea de comandos.
+    let error = Application::prepare(&Greeter)
+        .cli_from(["app", "--set", "app.name=Other", "config"])
+        .await
+        .unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+}
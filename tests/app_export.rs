@@ -0,0 +1,101 @@
+use pagetop::prelude::*;
+
+use std::fs;
+
+struct Site;
+
+impl Extension for Site {
+    fn configure_service(&self, scfg: &mut service::web::ServiceConfig) {
+        scfg.route("/", service::web::get().to(home))
+            .route("/about", service::web::get().to(about))
+            .route("/contact", service::web::get().to(contact))
+            .route("/news", service::web::get().to(news))
+            .route("/old", service::web::get().to(old))
+            .route("/team", service::web::get().to(team));
+    }
+
+    fn sitemap(&self) -> Vec<SitemapEntry> {
+        vec![SitemapEntry::new("/news")]
+    }
+}
+
+async fn home(request: HttpRequest) -> ResultPage<Markup, ErrorPage> {
+    Page::new(request)
+        .with_child(Html::with(|_| html! { a href="/team" { "Team" } }))
+        .render()
+}
+
+async fn about(request: HttpRequest) -> ResultPage<Markup, ErrorPage> {
+    Page::new(request)
+        .with_child(Html::with(|_| {
+            html! {
+                a href="/contact#form" { "Contact" }
+                a href="/about?lang=es-ES" { "Español" }
+                a href="/about?lang=../escaped" { "Escaped" }
+                a href="/search?q=rust" { "Search" }
+                a href="/old" { "Old" }
+            }
+        }))
+        .render()
+}
+
+async fn contact(request: HttpRequest) -> ResultPage<Markup, ErrorPage> {
+    Page::new(request).with_title(L10n::n("Contact")).render()
+}
+
+async fn news(request: HttpRequest) -> ResultPage<Markup, ErrorPage> {
+    Page::new(request).with_title(L10n::n("News")).render()
+}
+
+async fn team(request: HttpRequest) -> ResultPage<Markup, ErrorPage> {
+    Page::new(request).with_title(L10n::n("Team")).render()
+}
+
+async fn old() -> HttpResponse {
+    Redirect::found("/moved")
+}
+
+#[pagetop::test]
+async fn export_writes_routes_links_languages_and_resources() {
+    let output = std::env::temp_dir().join(format!("pagetop-export-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&output);
+
+    let files = Application::prepare(&Site)
+        .export(
+            SiteExport::new(&output)
+                .with_base_url("https://example.com")
+                .with_route("/about"),
+        )
+        .await
+        .unwrap();
+    assert!(files.iter().all(|file| file.starts_with(&output)));
+
+    // Página de inicio y enlaces que sólo se alcanzan desde ella.
+    assert!(output.join("index.html").is_file());
+    assert!(output.join("team/index.html").is_file());
+
+    // Rutas indicadas, enlaces seguidos y rutas del mapa del sitio.
+    let about = fs::read_to_string(output.join("about/index.html")).unwrap();
+    assert!(about.contains(r#"href="/es-ES/about""#));
+    assert!(output.join("contact/index.html").is_file());
+    assert!(output.join("news/index.html").is_file());
+    assert!(!output.join("search").exists());
+    assert!(!output.join("old").exists());
+    assert!(!output.parent().unwrap().join("escaped").exists());
+
+    // Variantes de idioma.
+    assert!(output.join("es-ES/about/index.html").is_file());
+    assert!(output.join("en-US/contact/index.html").is_file());
+
+    // Documentos generados con la URL base del sitio.
+    let sitemap = fs::read_to_string(output.join("sitemap.xml")).unwrap();
+    assert!(sitemap.contains("<loc>https://example.com/news</loc>"));
+    assert!(output.join("robots.txt").is_file());
+
+    // Página de error y recursos embebidos.
+    let not_found = fs::read_to_string(output.join("404.html")).unwrap();
+    assert!(not_found.contains("<html"));
+    assert!(output.join("css/basic.css").is_file());
+
+    fs::remove_dir_all(&output).unwrap();
+}
@@ -0,0 +1,70 @@
+use pagetop::prelude::*;
+
+use serde::Deserialize;
+
+use pagetop::config::IssueLevel;
+
+use std::{env, fs};
+
+include_config!(SETTINGS: Settings => [
+    "shop.currency" => "EUR",
+    "shop.page_size" => 20,
+    "shop.tagline" => "",
+]);
+
+#[derive(Debug, Deserialize)]
+pub struct Settings {
+    pub shop: Shop,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Shop {
+    pub currency: String,
+    pub page_size: u8,
+    pub tagline: String,
+}
+
+#[pagetop::test]
+async fn yaml_and_json_files_follow_the_same_precedence() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(
+        dir.path().join("common.yaml"),
+        "shop:\n  currency: USD\n  page_size: 10\n  tagline: Common\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.path().join("default.json"),
+        r#"{ "shop": { "page_size": 30 } }"#,
+    )
+    .unwrap();
+    fs::write(
+        dir.path().join("local.default.yml"),
+        "shop:\n  tagline: Local\n",
+    )
+    .unwrap();
+    // La misma capa en dos formatos es ambigua y no se carga ninguno.
+    fs::write(
+        dir.path().join("local.toml"),
+        "[shop]\ncurrency = \"GBP\"\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.path().join("local.json"),
+        r#"{ "shop": { "currency": "JPY" } }"#,
+    )
+    .unwrap();
+    env::set_var("CONFIG_DIR", dir.path());
+
+    assert_eq!(SETTINGS.shop.currency, "USD");
+    assert_eq!(SETTINGS.shop.page_size, 30);
+    assert_eq!(SETTINGS.shop.tagline, "Local");
+
+    let issues = pagetop::config::issues();
+    let issue = issues
+        .iter()
+        .find(|issue| issue.message().contains("\"local\""))
+        .unwrap_or_else(|| panic!("no issue for ambiguous layer: {issues:#?}"));
+    assert_eq!(issue.level(), IssueLevel::Error);
+    assert!(issue.message().contains("local.toml"));
+    assert!(issue.message().contains("local.json"));
+}
@@ -0,0 +1,71 @@
+use pagetop::prelude::*;
+
+use std::{env, fs};
+
+#[pagetop::test]
+async fn environment_overrides_config_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let secret = dir.path().join("app_name");
+    fs::write(&secret, "Secret App\n").unwrap();
+
+    env::set_var("PAGETOP__SERVER__BIND_PORT", "9123");
+    env::set_var("PAGETOP__SERVER__BIND_ADDRESS", "0.0.0.0");
+    env::set_var("PAGETOP__APP__NAME_FILE", &secret);
+    env::set_var("PAGETOP__APP__DESCRIPTION_FILE", dir.path().join("missing"));
+    env::set_var("PAGETOP__JOBS__STORE_PATH", dir.path().join("jobs.json"));
+    env::set_var(
+        "PAGETOP__EXTENSIONS__STATE_PATH",
+        dir.path().join("extensions.json"),
+    );
+
+    assert_eq!(global::SETTINGS.server.bind_port, 9123);
+    assert_eq!(global::SETTINGS.server.bind_address, "0.0.0.0");
+    assert_eq!(global::SETTINGS.app.name, "Secret App");
+
+    // Las rutas de archivos se asignan tal cual, aunque los archivos aún no existan.
+    let jobs = dir.path().join("jobs.json");
+    assert_eq!(global::SETTINGS.jobs.store_path, jobs.to_str().unwrap());
+    let extensions = dir.path().join("extensions.json");
+    assert_eq!(
+        global::SETTINGS.extensions.state_path,
+        extensions.to_str().unwrap()
+    );
+
+    // Los ajustes que no se sobrescriben mantienen su valor por defecto.
+    assert_eq!(global::SETTINGS.server.session_lifetime, 604_800);
+
+    // Los archivos que no se pueden leer se informan como errores de configuración.
+    assert!(pagetop::config::issues().iter().any(|issue| {
+        issue.level() == pagetop::config::IssueLevel::Error
+            && issue.message().contains("PAGETOP__APP__DESCRIPTION_FILE")
+    }));
+}
+
+#[pagetop::test]
+async fn secret_settings_match_whole_words() {
+    for key in [
+        "database.password",
+        "mail.smtp_password",
+        "session.secret_key",
+        "service.api_key",
+        "auth.token",
+    ] {
+        assert!(pagetop::config::is_secret(key), "{key} should be masked");
+    }
+    for key in [
+        "app.keywords",
+        "editor.hotkeys",
+        "game.monkey_mode",
+        "app.tokenizer",
+    ] {
+        assert!(
+            !pagetop::config::is_secret(key),
+            "{key} should not be masked"
+        );
+    }
+
+    // Los ajustes leídos de un archivo se ocultan siempre, se llamen como se llamen.
+    env::set_var("PAGETOP__EDITOR__THEME_FILE", "missing");
+    assert!(pagetop::config::is_secret("editor.theme"));
+    env::remove_var("PAGETOP__EDITOR__THEME_FILE");
+}
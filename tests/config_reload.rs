@@ -0,0 +1,65 @@
+use pagetop::prelude::*;
+
+use serde::Deserialize;
+
+use pagetop::config::DynamicSetting;
+
+use std::sync::{Arc, Mutex};
+use std::{env, fs};
+
+include_config!(SETTINGS: Settings => [
+    "shop.greeting" => "Welcome",
+    "shop.page_size" => 20,
+]);
+
+#[derive(Debug, Deserialize)]
+pub struct Settings {
+    pub shop: Shop,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Shop {
+    pub greeting: String,
+    pub page_size: u8,
+}
+
+static GREETING: DynamicSetting<String> = DynamicSetting::new("shop.greeting");
+static PAGE_SIZE: DynamicSetting<u8> = DynamicSetting::new("shop.page_size");
+
+#[pagetop::test]
+async fn dynamic_settings_change_on_reload() {
+    let dir = tempfile::tempdir().unwrap();
+    let common = dir.path().join("common.toml");
+    fs::write(&common, "[shop]\ngreeting = \"Hello\"\n").unwrap();
+    env::set_var("CONFIG_DIR", dir.path());
+
+    assert_eq!(SETTINGS.shop.greeting, "Hello");
+    assert_eq!(GREETING.get(), "Hello");
+    // Sin valor en los archivos se usa el valor por defecto.
+    assert_eq!(PAGE_SIZE.get(), 20);
+
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let notified = Arc::clone(&received);
+    GREETING.on_change(move |greeting| notified.lock().unwrap().push(greeting.clone()));
+
+    // Los ajustes dinámicos cambian y se notifican, los estáticos se mantienen.
+    fs::write(&common, "[shop]\ngreeting = \"Hi\"\npage_size = 50\n").unwrap();
+    let mut changed = pagetop::config::reload().unwrap();
+    changed.sort();
+    assert_eq!(changed, ["shop.greeting", "shop.page_size"]);
+    assert_eq!(GREETING.get(), "Hi");
+    assert_eq!(PAGE_SIZE.get(), 50);
+    assert_eq!(SETTINGS.shop.greeting, "Hello");
+    assert_eq!(*received.lock().unwrap(), ["Hi"]);
+
+    // Sin cambios no se notifica nada.
+    assert!(pagetop::config::reload().unwrap().is_empty());
+    assert_eq!(received.lock().unwrap().len(), 1);
+
+    // Una recarga con valores no válidos se descarta.
+    fs::write(&common, "[shop]\ngreeting = \"Hey\"\npage_size = 1000\n").unwrap();
+    assert!(pagetop::config::reload().is_err());
+    assert_eq!(GREETING.get(), "Hi");
+    assert_eq!(PAGE_SIZE.get(), 50);
+    assert_eq!(received.lock().unwrap().len(), 1);
+}
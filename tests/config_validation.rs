@@ -0,0 +1,90 @@
+use pagetop::prelude::*;
+
+use serde::Deserialize;
+
+use pagetop::config::IssueLevel;
+
+use std::{env, fs};
+
+include_config!(SETTINGS: Settings => [
+    "shop.currency" => "EUR",
+    "shop.page_size" => 20,
+]);
+
+#[derive(Debug, Deserialize)]
+pub struct Settings {
+    pub shop: Shop,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Shop {
+    pub currency: String,
+    pub page_size: u8,
+}
+
+#[pagetop::test]
+async fn invalid_settings_are_reported_all_at_once() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(
+        dir.path().join("common.toml"),
+        r#"
+[server]
+bind_port = "http"
+bind_prot = 8080
+
+[log]
+rolling = "Weekly"
+
+[shop]
+page_size = 1000
+"#,
+    )
+    .unwrap();
+    fs::write(dir.path().join("local.toml"), "[shop\npage_size = ").unwrap();
+    env::set_var("CONFIG_DIR", dir.path());
+
+    // Los valores no válidos toman su valor por defecto sin detener la ejecución.
+    assert_eq!(global::SETTINGS.server.bind_port, 8080);
+    assert_eq!(global::SETTINGS.log.rolling, global::LogRolling::Stdout);
+    assert_eq!(SETTINGS.shop.page_size, 20);
+    assert_eq!(SETTINGS.shop.currency, "EUR");
+
+    let issues = pagetop::config::issues();
+    let find = |key: &str| {
+        issues
+            .iter()
+            .find(|issue| issue.key() == Some(key))
+            .unwrap_or_else(|| panic!("no issue for {key}: {issues:#?}"))
+    };
+
+    // Errores con la clave, el archivo y el tipo o los valores esperados.
+    let issue = find("server.bind_port");
+    assert_eq!(issue.level(), IssueLevel::Error);
+    assert!(issue.origin().unwrap().ends_with("common.toml"));
+    assert!(issue.message().contains("expected an integer"));
+
+    let issue = find("log.rolling");
+    assert_eq!(issue.level(), IssueLevel::Error);
+    assert!(issue.message().contains("`Weekly`"));
+    assert!(issue.message().contains("`Minutely`"));
+
+    assert_eq!(find("shop.page_size").level(), IssueLevel::Error);
+
+    // Avisos de claves desconocidas que parecen erratas.
+    let issue = find("server.bind_prot");
+    assert_eq!(issue.level(), IssueLevel::Warning);
+    assert!(issue.message().contains("`server.bind_port`"));
+
+    // Los archivos que no se pueden interpretar se descartan sin perder los demás.
+    assert!(issues.iter().any(|issue| {
+        issue.level() == IssueLevel::Error && issue.message().contains("local.toml")
+    }));
+
+    // La aplicación no arranca y devuelve todos los errores.
+    let error = Application::try_new().err().unwrap();
+    assert_eq!(error.title(), "Invalid configuration");
+    assert!(error
+        .problems()
+        .iter()
+        .any(|problem| problem.starts_with("server.bind_port")));
+}
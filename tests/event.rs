@@ -0,0 +1,105 @@
+use pagetop::prelude::*;
+
+use std::sync::Mutex;
+use std::time::Duration;
+
+static DELIVERED: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+fn deliver(text: String) {
+    DELIVERED.lock().unwrap().push(text);
+}
+
+fn take_delivered() -> Vec<String> {
+    std::mem::take(&mut *DELIVERED.lock().unwrap())
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct UserRegistered {
+    name: String,
+}
+
+impl Event for UserRegistered {}
+
+#[derive(Clone, Debug, PartialEq)]
+struct ContentSaved(u32);
+
+impl Event for ContentSaved {}
+
+struct Accounts;
+
+impl Extension for Accounts {
+    fn subscribers(&self) -> Vec<Subscriber> {
+        vec![
+            Subscriber::on(|user: &UserRegistered| deliver(format!("accounts {}", user.name))),
+            Subscriber::on_async(welcome).with_weight(-5),
+        ]
+    }
+}
+
+async fn welcome(user: UserRegistered) {
+    service::rt::time::sleep(Duration::from_millis(10)).await;
+    deliver(format!("welcome {}", user.name));
+}
+
+struct Audit;
+
+impl Extension for Audit {
+    fn dependencies(&self) -> Vec<ExtensionRef> {
+        vec![&Accounts]
+    }
+
+    fn subscribers(&self) -> Vec<Subscriber> {
+        vec![
+            Subscriber::on(|user: &UserRegistered| deliver(format!("audit {}", user.name)))
+                .with_weight(-10),
+            Subscriber::on(|user: &UserRegistered| deliver(format!("last {}", user.name))),
+        ]
+    }
+}
+
+#[pagetop::test]
+async fn events_reach_subscribers_by_weight() {
+    Application::prepare(&Audit);
+    let registered = EventRecorder::<UserRegistered>::new();
+
+    // Se espera a cada suscriptor en orden de peso, y con el mismo peso en orden de carga.
+    event::publish_and_wait(UserRegistered {
+        name: "ana".to_string(),
+    })
+    .await;
+    assert_eq!(
+        take_delivered(),
+        ["audit ana", "welcome ana", "accounts ana", "last ana"]
+    );
+
+    // Sin esperar, los suscriptores asíncronos terminan después.
+    event::publish(UserRegistered {
+        name: "bob".to_string(),
+    });
+    assert_eq!(take_delivered(), ["audit bob", "accounts bob", "last bob"]);
+    service::rt::time::sleep(Duration::from_millis(50)).await;
+    assert_eq!(take_delivered(), ["welcome bob"]);
+
+    // El registro de eventos sólo guarda los de su tipo.
+    registered.assert_published(|user| user.name == "bob");
+    assert_eq!(registered.count(), 2);
+    assert_eq!(registered.last().unwrap().name, "bob");
+
+    let saved = EventRecorder::<ContentSaved>::new();
+    saved.assert_none();
+    event::publish(ContentSaved(7));
+    assert_eq!(saved.events(), [ContentSaved(7)]);
+    assert!(take_delivered().is_empty());
+    saved.clear();
+    saved.assert_none();
+
+    // Al descartar el registro deja de guardar eventos.
+    drop(registered);
+    let again = EventRecorder::<UserRegistered>::new();
+    event::publish_and_wait(UserRegistered {
+        name: "eva".to_string(),
+    })
+    .await;
+    assert_eq!(again.count(), 1);
+    assert_eq!(take_delivered().len(), 4);
+}
@@ -0,0 +1,81 @@
+use pagetop::prelude::*;
+
+use std::sync::Mutex;
+use std::{env, fs};
+
+static INITIALIZED: Mutex<Vec<&str>> = Mutex::new(Vec::new());
+
+struct Blog;
+
+impl Extension for Blog {
+    fn dependencies(&self) -> Vec<ExtensionRef> {
+        vec![&Storage]
+    }
+
+    fn optional_extensions(&self) -> Vec<ExtensionRef> {
+        vec![&Comments, &Search]
+    }
+
+    fn initialize(&self) {
+        INITIALIZED.lock().unwrap().push("Blog");
+    }
+}
+
+struct Storage;
+
+impl Extension for Storage {
+    fn initialize(&self) {
+        INITIALIZED.lock().unwrap().push("Storage");
+    }
+}
+
+struct Comments;
+
+impl Extension for Comments {
+    fn dependencies(&self) -> Vec<ExtensionRef> {
+        vec![&Storage]
+    }
+
+    fn initialize(&self) {
+        INITIALIZED.lock().unwrap().push("Comments");
+    }
+}
+
+struct Search;
+
+impl Extension for Search {
+    fn initialize(&self) {
+        INITIALIZED.lock().unwrap().push("Search");
+    }
+}
+
+#[pagetop::test]
+async fn extensions_are_enabled_and_disabled_from_config() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(
+        dir.path().join("common.toml"),
+        "[extensions]\nenabled = [\"comments\"]\ndisabled = [\"Welcome\"]\n",
+    )
+    .unwrap();
+    env::set_var("CONFIG_DIR", dir.path());
+
+    let app = service::test::init_service(Application::prepare(&Blog).test()).await;
+
+    // Sólo se habilitan las extensiones opcionales indicadas, después de sus dependencias.
+    assert_eq!(
+        *INITIALIZED.lock().unwrap(),
+        ["Storage", "Blog", "Comments"]
+    );
+    assert!(pagetop::config::issues()
+        .iter()
+        .all(|issue| issue.level() != pagetop::config::IssueLevel::Error));
+
+    // La página de bienvenida se deshabilita aunque `app.welcome` esté activado.
+    assert!(global::SETTINGS.app.welcome);
+    assert!(!service::routes()
+        .iter()
+        .any(|route| route.name() == Some("welcome")));
+    let request = service::test::TestRequest::get().uri("/").to_request();
+    let response = service::test::call_service(&app, request).await;
+    assert_eq!(response.status(), service::http::StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,128 @@
+use pagetop::prelude::*;
+
+fn names(extensions: &[ExtensionRef]) -> Vec<&'static str> {
+    extensions.iter().map(|&e| e.short_name()).collect()
+}
+
+struct Storage;
+
+impl Extension for Storage {
+    fn version(&self) -> Option<&'static str> {
+        Some("1.4.0")
+    }
+}
+
+struct Search;
+
+impl Extension for Search {}
+
+struct Blog;
+
+impl Extension for Blog {
+    fn requirements(&self) -> Vec<Dependency> {
+        vec![
+            Dependency::required(&Storage).with_version("^1.2"),
+            Dependency::optional(&Search),
+        ]
+    }
+}
+
+#[pagetop::test]
+async fn load_order_follows_required_and_optional_dependencies() {
+    struct Site;
+
+    impl Extension for Site {
+        fn dependencies(&self) -> Vec<ExtensionRef> {
+            vec![&Blog, &Search]
+        }
+    }
+
+    let graph = ExtensionGraph::resolve(Some(&Site));
+    assert!(graph.problems().is_empty(), "{:?}", graph.problems());
+
+    let order = names(graph.load_order());
+    let position = |name: &str| order.iter().position(|n| *n == name).unwrap();
+    assert_eq!(order[0], "Basic");
+    assert!(position("Storage") < position("Blog"));
+    assert!(position("Search") < position("Blog"));
+    assert!(position("Blog") < position("Site"));
+
+    // Las dependencias opcionales no se habilitan por sí solas.
+    let graph = ExtensionGraph::resolve(Some(&Blog));
+    assert!(graph.problems().is_empty(), "{:?}", graph.problems());
+    assert!(!names(graph.load_order()).contains(&"Search"));
+}
+
+#[pagetop::test]
+async fn cycles_are_detected() {
+    struct First;
+    struct Second;
+
+    impl Extension for First {
+        fn dependencies(&self) -> Vec<ExtensionRef> {
+            vec![&Second]
+        }
+    }
+
+    impl Extension for Second {
+        fn dependencies(&self) -> Vec<ExtensionRef> {
+            vec![&First]
+        }
+    }
+
+    let graph = ExtensionGraph::resolve(Some(&First));
+    assert_eq!(
+        graph.problems(),
+        ["Dependency cycle: First -> Second -> First"]
+    );
+}
+
+#[pagetop::test]
+async fn dropping_a_required_extension_is_a_conflict() {
+    struct Legacy;
+
+    impl Extension for Legacy {
+        fn drop_extensions(&self) -> Vec<ExtensionRef> {
+            vec![&Storage, &Search]
+        }
+    }
+
+    struct Site;
+
+    impl Extension for Site {
+        fn dependencies(&self) -> Vec<ExtensionRef> {
+            vec![&Blog, &Legacy]
+        }
+    }
+
+    let graph = ExtensionGraph::resolve(Some(&Site));
+    assert_eq!(
+        graph.problems(),
+        ["Extension \"Legacy\" drops \"Storage\", which is required by \"Blog\""]
+    );
+    assert_eq!(names(graph.dropped()), ["Search"]);
+}
+
+#[pagetop::test]
+async fn version_requirements_are_checked() {
+    struct Unversioned;
+
+    impl Extension for Unversioned {}
+
+    struct Site;
+
+    impl Extension for Site {
+        fn requirements(&self) -> Vec<Dependency> {
+            vec![
+                Dependency::required(&Storage).with_version("^2"),
+                Dependency::required(&Unversioned).with_version("1"),
+            ]
+        }
+    }
+
+    let graph = ExtensionGraph::resolve(Some(&Site));
+    let problems = graph.problems();
+    assert_eq!(problems.len(), 2, "{problems:?}");
+    assert!(problems[0].contains("requires \"Storage\" ^2, found 1.4.0"));
+    assert!(problems[1].contains("\"Unversioned\" does not declare its version"));
+}
@@ -0,0 +1,110 @@
+use pagetop::prelude::*;
+
+use std::sync::Mutex;
+use std::{env, fs};
+
+static CALLS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+fn record(call: impl Into<String>) -> std::io::Result<()> {
+    CALLS.lock().unwrap().push(call.into());
+    Ok(())
+}
+
+struct Site;
+
+impl Extension for Site {
+    fn dependencies(&self) -> Vec<ExtensionRef> {
+        vec![&Blog, &Forum]
+    }
+
+    fn drop_extensions(&self) -> Vec<ExtensionRef> {
+        vec![&Legacy]
+    }
+}
+
+struct Blog;
+
+impl Extension for Blog {
+    fn version(&self) -> Option<&'static str> {
+        Some("1.1.0")
+    }
+
+    fn install(&self) -> std::io::Result<()> {
+        record("install Blog")
+    }
+
+    fn upgrade(&self, from_version: &str) -> std::io::Result<()> {
+        record(format!("upgrade Blog from {from_version}"))
+    }
+}
+
+struct Forum;
+
+impl Extension for Forum {
+    fn version(&self) -> Option<&'static str> {
+        Some("0.2.0")
+    }
+
+    fn install(&self) -> std::io::Result<()> {
+        record("install Forum")
+    }
+
+    fn initialize(&self) {
+        record("initialize Forum").unwrap();
+    }
+}
+
+struct Legacy;
+
+impl Extension for Legacy {
+    fn uninstall(&self) -> std::io::Result<()> {
+        record("uninstall Legacy")
+    }
+}
+
+#[pagetop::test]
+async fn install_upgrade_and_uninstall_run_once() {
+    let dir = tempfile::tempdir().unwrap();
+    let state_path = dir.path().join("data").join("extensions.json");
+    fs::create_dir_all(state_path.parent().unwrap()).unwrap();
+    fs::write(
+        &state_path,
+        r#"{ "extensions": { "Blog": { "version": "1.0.0" }, "Legacy": { "version": null } } }"#,
+    )
+    .unwrap();
+    fs::write(
+        dir.path().join("common.toml"),
+        format!(
+            "[extensions]\nstate_path = {:?}\n",
+            state_path.display().to_string()
+        ),
+    )
+    .unwrap();
+    env::set_var("CONFIG_DIR", dir.path());
+
+    // Primero se desinstalan las extensiones descartadas y después se instalan o actualizan las
+    // habilitadas en orden de carga.
+    Application::prepare(&Site);
+    assert_eq!(
+        *CALLS.lock().unwrap(),
+        [
+            "uninstall Legacy",
+            "install Forum",
+            "upgrade Blog from 1.0.0",
+            "initialize Forum",
+        ]
+    );
+
+    let state: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&state_path).unwrap()).unwrap();
+    let extensions = &state["extensions"];
+    assert_eq!(extensions["Blog"]["version"], "1.1.0");
+    assert_eq!(extensions["Forum"]["version"], "0.2.0");
+    assert!(extensions["Site"]["version"].is_null());
+    assert!(extensions.get("Legacy").is_none());
+
+    // En el siguiente arranque sólo se inicializan.
+    CALLS.lock().unwrap().clear();
+    Application::prepare(&Site);
+    assert_eq!(*CALLS.lock().unwrap(), ["initialize Forum"]);
+}
@@ -0,0 +1,77 @@
+use pagetop::prelude::*;
+
+#[pagetop::test]
+async fn route_path_encodes_params_fragment_and_unsafe_path_chars() {
+    let route = RoutePath::new("/blog/año 2025")
+        .with_param("q", "a&b=c d")
+        .with_param("tag", "C++")
+        .with_flag("debug")
+        .with_fragment("top");
+    assert_eq!(
+        route.to_string(),
+        "/blog/a%C3%B1o%202025?q=a%26b%3Dc%20d&tag=C%2B%2B&debug#top"
+    );
+
+    // Las rutas ya codificadas no se codifican de nuevo.
+    assert_eq!(RoutePath::new("/a%20b").to_string(), "/a%20b");
+}
+
+#[pagetop::test]
+async fn route_path_parses_urls_and_requests() {
+    let route = RoutePath::parse("/search?q=caf%C3%A9+au+lait&page=3&page=4&debug#results");
+    assert_eq!(route.path(), "/search");
+    assert_eq!(
+        route.params().collect::<Vec<_>>(),
+        vec![("q", "café au lait"), ("page", "4"), ("debug", "")]
+    );
+    assert_eq!(route.fragment(), Some("results"));
+    assert_eq!(
+        route.to_string(),
+        "/search?q=caf%C3%A9%20au%20lait&page=4&debug#results"
+    );
+
+    let request = service::test::TestRequest::get()
+        .uri("/products/list?color=red&size=m%26l")
+        .to_http_request();
+    let route = RoutePath::from(&request);
+    assert_eq!(route.path(), "/products/list");
+    assert_eq!(route.param("size"), Some("m&l"));
+}
+
+#[pagetop::test]
+async fn route_path_round_trips_encoded_paths() {
+    let url = "/docs/my%20file/a%2Fb?q=a%20b";
+
+    // Se decodifica al leer, salvo lo que cambiaría los segmentos, y se codifica al mostrarla.
+    let route = RoutePath::parse(url);
+    assert_eq!(route.path(), "/docs/my file/a%2Fb");
+    assert_eq!(route.param("q"), Some("a b"));
+    assert_eq!(route.to_string(), url);
+
+    // Las peticiones siguen la misma regla que `parse()`.
+    let request = service::test::TestRequest::get().uri(url).to_http_request();
+    let route = RoutePath::from(&request);
+    assert_eq!(route.path(), "/docs/my file/a%2Fb");
+    assert_eq!(route.param("q"), Some("a b"));
+    assert_eq!(route.to_string(), url);
+}
+
+#[pagetop::test]
+async fn route_path_merges_and_removes_params() {
+    let current = RoutePath::parse("/products?color=red&page=5");
+
+    // Paginación: cambia la página conservando los filtros.
+    let next = current.clone().with_param("page", "6");
+    assert_eq!(next.to_string(), "/products?color=red&page=6");
+
+    // Filtros: combina nuevos filtros y vuelve a la primera página.
+    let filtered = current
+        .clone()
+        .with_params([("size", "m"), ("color", "blue")])
+        .without_param("page");
+    assert_eq!(filtered.to_string(), "/products?color=blue&size=m");
+
+    let mut cleared = current;
+    cleared.clear_params();
+    assert_eq!(cleared.to_string(), "/products");
+}
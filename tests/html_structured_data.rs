@@ -0,0 +1,70 @@
+use pagetop::prelude::*;
+
+use serde_json::json;
+
+#[pagetop::test]
+async fn structured_data_renders_single_entity_with_context() {
+    let mut data = StructuredData::default();
+    data.add(json!({ "@type": "Organization", "name": "PageTop" }));
+
+    assert_eq!(
+        data.render().into_string(),
+        r#"<script type="application/ld+json">{"@context":"https://schema.org","@type":"Organization","name":"PageTop"}</script>"#
+    );
+}
+
+#[pagetop::test]
+async fn structured_data_merges_entities_by_id_into_a_graph() {
+    let mut data = StructuredData::default();
+    data.add(json!({ "@id": "#article", "@type": "Article", "headline": "Draft" }));
+    data.add(json!({ "@type": "BreadcrumbList", "itemListElement": [] }));
+    data.add(json!({ "@id": "#article", "headline": "Hello", "author": "Ana" }));
+
+    assert_eq!(data.len(), 2);
+    assert_eq!(
+        data.entities()[0],
+        json!({ "@id": "#article", "@type": "Article", "headline": "Hello", "author": "Ana" })
+    );
+
+    let markup = data.render().into_string();
+    assert!(markup.contains(r#"{"@context":"https://schema.org","@graph":[{"#));
+    assert_eq!(markup.matches("application/ld+json").count(), 1);
+}
+
+#[pagetop::test]
+async fn structured_data_escapes_script_breaking_characters() {
+    let mut data = StructuredData::default();
+    data.add(json!({ "@type": "Thing", "name": "</script><b>Tom & Jerry</b>" }));
+
+    let markup = data.render().into_string();
+    assert!(markup.contains(r#"\u003c/script\u003e\u003cb\u003eTom \u0026 Jerry"#));
+    assert_eq!(markup.matches("</script>").count(), 1);
+}
+
+#[pagetop::test]
+async fn structured_data_ignores_non_object_values() {
+    let mut data = StructuredData::default();
+    data.add("just a string");
+
+    assert!(data.is_empty());
+    assert!(data.render().is_empty());
+}
+
+#[pagetop::test]
+async fn structured_data_from_components_is_rendered_in_page_head() {
+    let req = service::test::TestRequest::default().to_http_request();
+    let markup = Page::new(req)
+        .with_structured_data(json!({ "@id": "#org", "@type": "Organization", "name": "PageTop" }))
+        .with_child(Html::with(|cx| {
+            cx.add_structured_data(json!({ "@id": "#org", "url": "https://pagetop.cillero.es" }));
+            html! { p { "Content" } }
+        }))
+        .render()
+        .unwrap()
+        .into_string();
+
+    let (head, _) = markup.split_once("</head>").unwrap();
+    assert!(head.contains(
+        r##"<script type="application/ld+json">{"@context":"https://schema.org","@id":"#org","@type":"Organization","name":"PageTop","url":"https://pagetop.cillero.es"}</script>"##
+    ));
+}
@@ -0,0 +1,106 @@
+use pagetop::prelude::*;
+
+use serde::{Deserialize, Serialize};
+
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Mutex;
+use std::{env, fs};
+
+static RECORDED: Mutex<Vec<String>> = Mutex::new(Vec::new());
+static FLAKY_RUNS: AtomicU32 = AtomicU32::new(0);
+static BROKEN_RUNS: AtomicU32 = AtomicU32::new(0);
+
+#[derive(Deserialize, Serialize)]
+struct Record {
+    text: String,
+}
+
+impl Job for Record {
+    async fn run(self) -> std::io::Result<()> {
+        RECORDED.lock().unwrap().push(self.text);
+        Ok(())
+    }
+}
+
+// Falla las dos primeras veces.
+#[derive(Deserialize, Serialize)]
+struct Flaky;
+
+impl Job for Flaky {
+    async fn run(self) -> std::io::Result<()> {
+        match FLAKY_RUNS.fetch_add(1, Ordering::SeqCst) {
+            0 | 1 => Err(std::io::Error::other("not yet")),
+            _ => Ok(()),
+        }
+    }
+}
+
+// Falla siempre, con un solo reintento.
+#[derive(Deserialize, Serialize)]
+struct Broken;
+
+impl Job for Broken {
+    fn max_retries(&self) -> u32 {
+        1
+    }
+
+    async fn run(self) -> std::io::Result<()> {
+        BROKEN_RUNS.fetch_add(1, Ordering::SeqCst);
+        Err(std::io::Error::other("always broken"))
+    }
+}
+
+struct Worker;
+
+impl Extension for Worker {
+    fn jobs(&self) -> Vec<JobType> {
+        vec![JobType::of::<Record>()]
+    }
+}
+
+#[pagetop::test]
+async fn jobs_run_retry_and_resume() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = dir.path().join("data").join("jobs.json");
+    fs::write(
+        dir.path().join("common.toml"),
+        format!(
+            "[jobs]\nworkers = 2\nretry_delay = 10\nstore_path = \"{}\"\n",
+            store.display()
+        ),
+    )
+    .unwrap();
+    env::set_var("CONFIG_DIR", dir.path());
+
+    // Un trabajo pendiente de una ejecución anterior.
+    fs::create_dir_all(store.parent().unwrap()).unwrap();
+    fs::write(
+        &store,
+        r#"{"jobs": [{"id": 7, "job": "Record", "payload": {"text": "resumed"},
+            "attempt": 0, "max_retries": 3}]}"#,
+    )
+    .unwrap();
+
+    Application::prepare(&Worker);
+    let id = job::enqueue(Record {
+        text: "enqueued".to_string(),
+    })
+    .unwrap();
+    assert_eq!(id, 8);
+    job::enqueue(Flaky).unwrap();
+    job::enqueue(Broken).unwrap();
+
+    // Se espera a que terminen todos, también los reintentos.
+    assert_eq!(job::drain().await, 0);
+    let mut recorded = RECORDED.lock().unwrap().clone();
+    recorded.sort();
+    assert_eq!(recorded, ["enqueued", "resumed"]);
+    assert_eq!(FLAKY_RUNS.load(Ordering::SeqCst), 3);
+    assert_eq!(BROKEN_RUNS.load(Ordering::SeqCst), 2);
+
+    // No quedan trabajos pendientes ni se aceptan otros nuevos.
+    let stored: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&store).unwrap()).unwrap();
+    assert_eq!(stored["jobs"], serde_json::json!([]));
+    assert!(job::enqueue(Flaky).is_err());
+}
@@ -0,0 +1,37 @@
+use pagetop::prelude::*;
+
+use serde::{Deserialize, Serialize};
+
+use std::env;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+static FINISHED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Deserialize, Serialize)]
+struct Slow;
+
+impl Job for Slow {
+    async fn run(self) -> std::io::Result<()> {
+        service::rt::time::sleep(Duration::from_millis(200)).await;
+        FINISHED.store(true, Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+#[pagetop::test]
+async fn run_drains_pending_jobs_on_shutdown() {
+    env::set_var("PAGETOP__SERVER__BIND_ADDRESS", "127.0.0.1");
+    env::set_var("PAGETOP__SERVER__BIND_PORT", "0");
+
+    let server = Application::new().run().unwrap();
+    job::enqueue(Slow).unwrap();
+
+    // El servidor se detiene enseguida, pero espera a que termine el trabajo pendiente.
+    let handle = server.handle();
+    service::rt::spawn(async move { handle.stop(true).await });
+    server.await.unwrap();
+
+    assert!(FINISHED.load(Ordering::SeqCst));
+    assert!(job::enqueue(Slow).is_err());
+}
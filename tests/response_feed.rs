@@ -0,0 +1,106 @@
+use pagetop::prelude::*;
+
+struct Blog;
+
+impl Extension for Blog {
+    fn configure_service(&self, scfg: &mut service::web::ServiceConfig) {
+        scfg.route("/blog/feed.rss", service::web::get().to(blog_feed));
+    }
+
+    fn feeds(&self) -> Vec<Link> {
+        vec![FeedFormat::Rss.link("/blog/feed.rss").with_title("Blog")]
+    }
+}
+
+fn sample_feed(request: HttpRequest, format: FeedFormat) -> Feed {
+    Feed::new(request, format)
+        .with_title(L10n::n("Blog & news"))
+        .with_subtitle(L10n::n("Latest posts"))
+        .with_link("/blog")
+        .with_entry(
+            FeedEntry::new(
+                L10n::n("Hello <world>"),
+                "/blog/hello",
+                Utc.with_ymd_and_hms(2025, 3, 14, 10, 0, 0).unwrap(),
+            )
+            .with_summary(L10n::n("First post."))
+            .with_content(html! { p { "Body" } })
+            .with_author("Ana")
+            .with_category("rust"),
+        )
+        .with_entry(FeedEntry::new(
+            L10n::n("Older"),
+            RoutePath::new("/blog").with_param("page", "2"),
+            Utc.with_ymd_and_hms(2025, 1, 1, 8, 30, 0).unwrap(),
+        ))
+}
+
+async fn blog_feed(request: HttpRequest) -> Feed {
+    sample_feed(request, FeedFormat::Rss)
+}
+
+#[pagetop::test]
+async fn feed_renders_atom_document() {
+    let req = service::test::TestRequest::get()
+        .uri("/blog/feed.atom")
+        .to_http_request();
+    let xml = sample_feed(req, FeedFormat::Atom).render();
+
+    assert!(xml.starts_with(
+        r#"<?xml version="1.0" encoding="UTF-8"?><feed xmlns="http://www.w3.org/2005/Atom""#
+    ));
+    assert!(xml.contains("<title>Blog &amp; news</title><subtitle>Latest posts</subtitle>"));
+    assert!(xml.contains(
+        r#"<id>http://localhost:8080/blog/feed.atom</id><link rel="self" href="http://localhost:8080/blog/feed.atom"/>"#
+    ));
+    assert!(xml.contains("<updated>2025-03-14T10:00:00Z</updated><entry>"));
+    assert!(xml.contains(concat!(
+        "<entry><title>Hello &lt;world&gt;</title>",
+        "<id>http://localhost:8080/blog/hello</id>",
+        r#"<link href="http://localhost:8080/blog/hello"/>"#,
+        "<updated>2025-03-14T10:00:00Z</updated>",
+        "<author><name>Ana</name></author>",
+        r#"<category term="rust"/>"#,
+        "<summary>First post.</summary>",
+        r#"<content type="html">&lt;p&gt;Body&lt;/p&gt;</content></entry>"#,
+    )));
+    assert!(xml.contains(r#"<link href="http://localhost:8080/blog?page=2"/>"#));
+}
+
+#[pagetop::test]
+async fn feed_is_served_as_rss_and_announced_in_page_head() {
+    let app = service::test::init_service(Application::prepare(&Blog).test()).await;
+
+    let req = service::test::TestRequest::get()
+        .uri("/blog/feed.rss")
+        .to_request();
+    let resp = service::test::call_service(&app, req).await;
+    assert_eq!(
+        resp.headers().get("Content-Type").unwrap(),
+        "application/rss+xml; charset=utf-8"
+    );
+    let body = service::test::read_body(resp).await;
+    let xml = String::from_utf8(body.to_vec()).unwrap();
+    assert!(xml.contains(concat!(
+        "<channel><title>Blog &amp; news</title>",
+        "<link>http://localhost:8080/blog</link>",
+        "<description>Latest posts</description>",
+    )));
+    assert!(xml.contains("<lastBuildDate>Fri, 14 Mar 2025 10:00:00 +0000</lastBuildDate>"));
+    assert!(xml.contains(concat!(
+        "<item><title>Hello &lt;world&gt;</title>",
+        "<link>http://localhost:8080/blog/hello</link>",
+        "<guid>http://localhost:8080/blog/hello</guid>",
+        "<pubDate>Fri, 14 Mar 2025 10:00:00 +0000</pubDate>",
+        "<dc:creator>Ana</dc:creator>",
+        "<category>rust</category>",
+        "<description>First post.</description></item>",
+    )));
+
+    let req = service::test::TestRequest::default().to_http_request();
+    let markup = Page::new(req).render().unwrap().into_string();
+    let (head, _) = markup.split_once("</head>").unwrap();
+    assert!(head.contains(
+        r#"<link rel="alternate" href="/blog/feed.rss" type="application/rss+xml" title="Blog">"#
+    ));
+}
@@ -0,0 +1,379 @@
+use pagetop::prelude::*;
+
+fn sample_page(request: HttpRequest) -> Page {
+    Page::new(request)
+        .with_title(L10n::n("Streaming"))
+        .with_body_id("sample")
+        .with_child_in(
+            &DefaultRegion::Header,
+            Html::with(|_| html! { h1 { "Header" } }),
+        )
+        .with_child(Html::with(|_| html! { p { "Content" } }))
+        .with_child_in(
+            &DefaultRegion::Footer,
+            Html::with(|_| html! { small { "Footer" } }),
+        )
+}
+
+async fn rendered_page(request: HttpRequest) -> ResultPage<Markup, ErrorPage> {
+    sample_page(request).render()
+}
+
+async fn streamed_page(request: HttpRequest) -> ResultPage<HttpResponse, ErrorPage> {
+    sample_page(request).stream()
+}
+
+struct Wrapper;
+
+impl Extension for Wrapper {
+    fn theme(&self) -> Option<ThemeRef> {
+        Some(&Self)
+    }
+}
+
+impl Theme for Wrapper {
+    // Los temas hijos no heredan el envío por regiones del tema básico.
+    fn parent(&self) -> Option<ThemeRef> {
+        Some(&pagetop::base::theme::Basic)
+    }
+
+    fn render_page_body(&self, page: &mut Page) -> Markup {
+        html! { main.wrapper { (page.template().render(page.context())) } }
+    }
+}
+
+async fn streamed_wrapped_page(request: HttpRequest) -> ResultPage<HttpResponse, ErrorPage> {
+    sample_page(request).with_theme(&Wrapper).stream()
+}
+
+async fn streamed_page_with_late_asset(
+    request: HttpRequest,
+) -> ResultPage<HttpResponse, ErrorPage> {
+    Page::new(request)
+        .with_structured_data(serde_json::json!({ "@id": "#org", "name": "PageTop" }))
+        .with_child(Html::with(|cx| {
+            cx.alter_assets(AssetsOp::AddStyleSheet(StyleSheet::from("/css/late.css")));
+            cx.add_structured_data(serde_json::json!({ "@id": "#org", "url": "/about" }));
+            html! { p { "Late" } }
+        }))
+        .stream()
+}
+
+fn deferred_page(request: HttpRequest) -> Page {
+    Page::new(request)
+        .with_child(
+            Deferred::new()
+                .with_id("slow")
+                .with_placeholder(Html::with(|_| html! { p { "Loading" } }))
+                .with_child(Html::with(|_| html! { p { "Slow data" } })),
+        )
+        .with_child_in(
+            &DefaultRegion::Footer,
+            Html::with(|_| html! { small { "Footer" } }),
+        )
+}
+
+async fn rendered_deferred_page(request: HttpRequest) -> ResultPage<Markup, ErrorPage> {
+    deferred_page(request).render()
+}
+
+async fn streamed_deferred_page(request: HttpRequest) -> ResultPage<HttpResponse, ErrorPage> {
+    deferred_page(request).stream()
+}
+
+fn fragment_page(request: HttpRequest) -> Page {
+    sample_page(request)
+        .with_fragments(true)
+        .with_child(Block::new().with_id("news").with_child(Html::with(|cx| {
+            cx.alter_assets(AssetsOp::AddJavaScript(JavaScript::defer("/js/news.js")));
+            html! { p { "News" } }
+        })))
+}
+
+async fn rendered_fragment_page(request: HttpRequest) -> ResultPage<Markup, ErrorPage> {
+    fragment_page(request).render()
+}
+
+async fn read_body(uri: &str) -> String {
+    read_body_with_headers(uri, &[]).await
+}
+
+async fn read_body_with_headers(uri: &str, headers: &[(&'static str, &'static str)]) -> String {
+    Application::new();
+    let app = service::test::init_service(
+        service::App::new()
+            .route("/render", service::web::get().to(rendered_page))
+            .route("/stream", service::web::get().to(streamed_page))
+            .route("/wrapped", service::web::get().to(streamed_wrapped_page))
+            .route(
+                "/late",
+                service::web::get().to(streamed_page_with_late_asset),
+            )
+            .route(
+                "/deferred/render",
+                service::web::get().to(rendered_deferred_page),
+            )
+            .route(
+                "/deferred/stream",
+                service::web::get().to(streamed_deferred_page),
+            )
+            .route("/fragment", service::web::get().to(rendered_fragment_page)),
+    )
+    .await;
+
+    let mut req = service::test::TestRequest::get().uri(uri);
+    for header in headers {
+        req = req.insert_header(*header);
+    }
+    let req = req.to_request();
+    let body = service::test::call_and_read_body(&app, req).await;
+    String::from_utf8(body.to_vec()).unwrap()
+}
+
+#[pagetop::test]
+async fn page_stream_matches_full_render() {
+    let rendered = read_body("/render").await;
+    let streamed = read_body("/stream").await;
+
+    assert_eq!(streamed, rendered);
+}
+
+#[pagetop::test]
+async fn page_stream_keeps_regions_in_order() {
+    let streamed = read_body("/stream").await;
+
+    let head = streamed.find("</head>").unwrap();
+    let header = streamed.find("<h1>Header</h1>").unwrap();
+    let content = streamed.find("<p>Content</p>").unwrap();
+    let footer = streamed.find("<small>Footer</small>").unwrap();
+
+    assert!(streamed.contains(r#"<body id="sample">"#));
+    assert!(head < header && header < content && content < footer);
+    assert!(streamed.ends_with("</body></html>"));
+}
+
+#[pagetop::test]
+async fn page_stream_keeps_the_body_rendered_by_the_theme() {
+    let streamed = read_body("/wrapped").await;
+
+    let (_, body) = streamed.split_once(r#"<main class="wrapper">"#).unwrap();
+    assert!(body.find("<h1>Header</h1>").unwrap() < body.find("</main>").unwrap());
+    assert!(body.find("<small>Footer</small>").unwrap() < body.find("</main>").unwrap());
+}
+
+#[pagetop::test]
+async fn page_stream_appends_late_assets_before_closing_body() {
+    let streamed = read_body("/late").await;
+
+    let (head, body) = streamed.split_once("</head>").unwrap();
+    assert!(!head.contains("/css/late.css"));
+    assert!(body.contains(r#"<link rel="stylesheet" href="/css/late.css"#));
+    assert!(body.find("/css/late.css").unwrap() > body.find("<p>Late</p>").unwrap());
+
+    // Las entidades ya enviadas que reciben propiedades nuevas se envían otra vez completas.
+    assert!(head.contains(r##"{"@context":"https://schema.org","@id":"#org","name":"PageTop"}"##));
+    assert!(body.contains(
+        r##"{"@context":"https://schema.org","@id":"#org","name":"PageTop","url":"/about"}"##
+    ));
+}
+
+#[pagetop::test]
+async fn page_stream_sends_deferred_content_after_the_body() {
+    let streamed = read_body("/deferred/stream").await;
+
+    let placeholder = streamed
+        .find(r#"<div id="slow" class="deferred deferred--pending" aria-busy="true"><p>Loading</p></div>"#)
+        .unwrap();
+    let footer = streamed.find("<small>Footer</small>").unwrap();
+    let template = streamed
+        .find(r#"<template data-deferred="slow"><div id="slow" class="deferred"><p>Slow data</p></div></template><script>"#)
+        .unwrap();
+
+    assert!(placeholder < footer && footer < template);
+    assert!(streamed.ends_with("</body></html>"));
+}
+
+#[pagetop::test]
+async fn page_render_keeps_deferred_content_inline() {
+    let rendered = read_body("/deferred/render").await;
+
+    assert!(rendered.contains(r#"<div id="slow" class="deferred"><p>Slow data</p></div>"#));
+    assert!(!rendered.contains("Loading"));
+    assert!(!rendered.contains("<template"));
+}
+
+struct Banner;
+
+impl Region for Banner {
+    fn name(&self) -> &'static str {
+        "header"
+    }
+
+    fn label(&self) -> L10n {
+        L10n::n("Banner")
+    }
+
+    fn render(&'static self, cx: &mut Context) -> Markup
+    where
+        Self: Sized,
+    {
+        html! { header.banner { (cx.render_region(self)) } }
+    }
+}
+
+#[pagetop::test]
+async fn page_render_fragment_uses_the_region_override() {
+    let req = service::test::TestRequest::default().to_http_request();
+    let markup = sample_page(req)
+        .render_fragment(Fragment::Region(&Banner))
+        .unwrap()
+        .into_string();
+
+    assert_eq!(markup, r#"<header class="banner"><h1>Header</h1></header>"#);
+}
+
+#[pagetop::test]
+async fn page_render_fragment_returns_only_the_region() {
+    let req = service::test::TestRequest::default().to_http_request();
+    let markup = sample_page(req)
+        .render_fragment(Fragment::Region(&DefaultRegion::Header))
+        .unwrap()
+        .into_string();
+
+    assert_eq!(
+        markup,
+        r#"<div class="region region-header" role="region"><h1>Header</h1></div>"#
+    );
+}
+
+#[pagetop::test]
+async fn page_render_fragment_adds_new_assets_out_of_band() {
+    let req = service::test::TestRequest::default().to_http_request();
+    let markup = fragment_page(req)
+        .render_fragment(Fragment::Child("news".to_string()))
+        .unwrap()
+        .into_string();
+
+    assert!(markup.starts_with(r#"<div id="news" class="block">"#));
+    assert!(markup.contains(r#"<div hx-swap-oob="beforeend:head"><script src="/js/news.js"#));
+    assert!(!markup.contains("basic.css"));
+    assert!(!markup.contains("<html"));
+}
+
+#[pagetop::test]
+async fn page_render_fragment_fails_for_unknown_child() {
+    let req = service::test::TestRequest::default().to_http_request();
+    let result = fragment_page(req).render_fragment(Fragment::Child("missing".to_string()));
+
+    assert!(matches!(result, Err(ErrorPage::NotFound(_))));
+}
+
+#[pagetop::test]
+async fn page_render_honors_fragment_header() {
+    let region = read_body_with_headers("/fragment", &[(FRAGMENT_HEADER, "footer")]).await;
+    assert!(region.starts_with(r#"<div class="region region-footer""#));
+    assert!(region.contains("<small>Footer</small>"));
+
+    let child = read_body_with_headers("/fragment", &[(FRAGMENT_HEADER, "#news")]).await;
+    assert!(child.starts_with(r#"<div id="news" class="block">"#));
+    assert!(!child.contains("<h1>Header</h1>"));
+
+    // Sin habilitar los fragmentos se devuelve el documento completo.
+    let full = read_body_with_headers("/render", &[(FRAGMENT_HEADER, "footer")]).await;
+    assert!(full.starts_with("<!DOCTYPE html>"));
+}
+
+#[pagetop::test]
+async fn page_render_honors_htmx_target() {
+    let child = read_body_with_headers(
+        "/fragment",
+        &[("HX-Request", "true"), ("HX-Target", "news")],
+    )
+    .await;
+    assert!(child.starts_with(r#"<div id="news" class="block">"#));
+
+    // Un destino que no es un componente de la página devuelve el documento completo.
+    let full = read_body_with_headers(
+        "/fragment",
+        &[("HX-Request", "true"), ("HX-Target", "main")],
+    )
+    .await;
+    assert!(full.starts_with("<!DOCTYPE html>"));
+}
+
+#[pagetop::test]
+async fn page_head_renders_owned_metadata_and_links() {
+    let req = service::test::TestRequest::default().to_http_request();
+    let slug = String::from("hello-world");
+    let markup = Page::new(req)
+        .with_metadata("author", format!("Author of {slug}"))
+        .with_metadata("keywords", L10n::n("rust, web"))
+        .with_property("og:url", format!("https://example.com/{slug}"))
+        .with_link(Link::canonical("https://example.com/old"))
+        .with_link(Link::canonical(format!("https://example.com/{slug}")))
+        .with_link(Link::alternate("https://example.com/es/hello-world").with_hreflang("es"))
+        .with_link(Link::preconnect("https://cdn.example.com").with_crossorigin(true))
+        .with_link(Link::manifest("/manifest.webmanifest"))
+        .render()
+        .unwrap()
+        .into_string();
+
+    let (head, _) = markup.split_once("</head>").unwrap();
+    assert!(head.contains(r#"<meta name="author" content="Author of hello-world">"#));
+    assert!(head.contains(r#"<meta name="keywords" content="rust, web">"#));
+    assert!(head.contains(r#"<meta property="og:url" content="https://example.com/hello-world">"#));
+    assert!(head.contains(r#"<link rel="canonical" href="https://example.com/hello-world">"#));
+    assert!(!head.contains("https://example.com/old"));
+    assert!(head.contains(
+        r#"<link rel="alternate" href="https://example.com/es/hello-world" hreflang="es">"#
+    ));
+    assert!(head.contains(r#"<link rel="preconnect" href="https://cdn.example.com" crossorigin>"#));
+    assert!(head.contains(r#"<link rel="manifest" href="/manifest.webmanifest">"#));
+}
+
+#[pagetop::test]
+async fn page_head_renders_social_metadata_with_page_defaults() {
+    let req = service::test::TestRequest::default().to_http_request();
+    let markup = Page::new(req)
+        .with_langid(&Locale::resolve("es-ES"))
+        .with_title(L10n::n("Hello"))
+        .with_description(L10n::n("A greeting"))
+        .with_link(Link::canonical("https://example.com/hello"))
+        .with_open_graph(
+            OpenGraph::new().with_type(OgType::Article).with_image(
+                OgImage::new("https://example.com/hello.png")
+                    .with_size(1200, 630)
+                    .with_alt("Waving hand"),
+            ),
+        )
+        .with_twitter_card(
+            TwitterCard::new()
+                .with_type(TwitterCardType::SummaryLargeImage)
+                .with_site("@pagetop"),
+        )
+        .render()
+        .unwrap()
+        .into_string();
+
+    let (head, _) = markup.split_once("</head>").unwrap();
+    for tag in [
+        r#"<meta property="og:type" content="article">"#,
+        r#"<meta property="og:title" content="Hello">"#,
+        r#"<meta property="og:description" content="A greeting">"#,
+        r#"<meta property="og:url" content="https://example.com/hello">"#,
+        r#"<meta property="og:image" content="https://example.com/hello.png">"#,
+        r#"<meta property="og:image:width" content="1200">"#,
+        r#"<meta property="og:image:height" content="630">"#,
+        r#"<meta property="og:image:alt" content="Waving hand">"#,
+        r#"<meta property="og:locale" content="es_ES">"#,
+        r#"<meta name="twitter:card" content="summary_large_image">"#,
+        r#"<meta name="twitter:site" content="@pagetop">"#,
+        r#"<meta name="twitter:title" content="Hello">"#,
+        r#"<meta name="twitter:description" content="A greeting">"#,
+        r#"<meta name="twitter:image" content="https://example.com/hello.png">"#,
+        r#"<meta name="twitter:image:alt" content="Waving hand">"#,
+    ] {
+        assert!(head.contains(tag), "missing {tag}");
+    }
+    assert!(head.contains(r#"<meta property="og:site_name" content=""#));
+}
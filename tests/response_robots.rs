@@ -0,0 +1,86 @@
+use pagetop::prelude::*;
+
+struct Crawling;
+
+impl Extension for Crawling {
+    fn robots(&self) -> Vec<RobotsRule> {
+        vec![
+            RobotsRule::new("*").with_disallow("/admin"),
+            RobotsRule::new("GPTBot").with_disallow("/"),
+            RobotsRule::new("*").with_allow("/admin/public"),
+        ]
+    }
+
+    fn sitemap(&self) -> Vec<SitemapEntry> {
+        vec![SitemapEntry::new("/")]
+    }
+
+    fn well_known(&self) -> Vec<WellKnown> {
+        vec![
+            WellKnown::text("security.txt", "Contact: mailto:security@example.com\n"),
+            WellKnown::redirect("change-password", "/account/password"),
+        ]
+    }
+
+    fn configure_service(&self, scfg: &mut service::web::ServiceConfig) {
+        scfg.route(
+            "/.well-known/openid-configuration",
+            service::web::get().to(|| async { "{}" }),
+        );
+    }
+}
+
+#[pagetop::test]
+async fn robots_policy_depends_on_run_mode() {
+    assert!(global::RobotsPolicy::Auto.allows_crawling("production"));
+    assert!(!global::RobotsPolicy::Auto.allows_crawling("default"));
+    assert!(global::RobotsPolicy::Allow.allows_crawling("staging"));
+    assert!(!global::RobotsPolicy::Disallow.allows_crawling("production"));
+}
+
+#[pagetop::test]
+async fn robots_and_well_known_documents_come_from_extensions() {
+    // Debe definirse antes de cargar la configuración global.
+    std::env::set_var("PAGETOP_RUN_MODE", "production");
+
+    let app = service::test::init_service(Application::prepare(&Crawling).test()).await;
+    macro_rules! get {
+        ($uri:expr) => {{
+            let req = service::test::TestRequest::get().uri($uri).to_request();
+            service::test::call_service(&app, req).await
+        }};
+    }
+
+    let resp = get!("/robots.txt");
+    assert!(resp.status().is_success());
+    let body = service::test::read_body(resp).await;
+    assert_eq!(
+        String::from_utf8(body.to_vec()).unwrap(),
+        "User-agent: *\n\
+         Allow: /admin/public\n\
+         Disallow: /admin\n\
+         \n\
+         User-agent: GPTBot\n\
+         Disallow: /\n\
+         \n\
+         Sitemap: http://localhost:8080/sitemap.xml\n"
+    );
+
+    let resp = get!("/.well-known/security.txt");
+    assert!(resp.status().is_success());
+    let body = service::test::read_body(resp).await;
+    assert_eq!(&body[..], b"Contact: mailto:security@example.com\n");
+
+    let resp = get!("/.well-known/change-password");
+    assert_eq!(resp.status(), service::http::StatusCode::FOUND);
+    assert_eq!(resp.headers().get("Location").unwrap(), "/account/password");
+
+    // Las rutas que configura la extensión en `/.well-known/` también responden.
+    let resp = get!("/.well-known/openid-configuration");
+    assert!(resp.status().is_success());
+    let body = service::test::read_body(resp).await;
+    assert_eq!(&body[..], b"{}");
+
+    let resp = get!("/.well-known/appspecific/com.chrome.devtools.json");
+    assert_eq!(resp.status(), service::http::StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,63 @@
+use pagetop::prelude::*;
+
+struct ManyPages;
+
+impl Extension for ManyPages {
+    fn sitemap(&self) -> Vec<SitemapEntry> {
+        let mut entries: Vec<SitemapEntry> = (0..MAX_SITEMAP_URLS)
+            .map(|i| SitemapEntry::new(format!("/page/{i}")))
+            .collect();
+        entries.push(
+            SitemapEntry::new("/blog?tag=a&b")
+                .with_lastmod(Utc.with_ymd_and_hms(2025, 3, 14, 10, 0, 0).unwrap())
+                .with_changefreq(ChangeFreq::Weekly)
+                .with_priority(1.5)
+                .with_language_alternates(),
+        );
+        entries
+    }
+}
+
+#[pagetop::test]
+async fn sitemap_is_split_into_an_index_when_too_large() {
+    let app = service::test::init_service(Application::prepare(&ManyPages).test()).await;
+    macro_rules! get {
+        ($uri:expr) => {{
+            let req = service::test::TestRequest::get().uri($uri).to_request();
+            let resp = service::test::call_service(&app, req).await;
+            let status = resp.status();
+            let body = service::test::read_body(resp).await;
+            (status, String::from_utf8(body.to_vec()).unwrap())
+        }};
+    }
+
+    let (status, index) = get!("/sitemap.xml");
+    assert!(status.is_success());
+    assert!(index.contains("<sitemapindex"));
+    assert!(index.contains("<loc>http://localhost:8080/sitemap-1.xml</loc>"));
+    assert!(index.contains("<loc>http://localhost:8080/sitemap-2.xml</loc>"));
+    assert!(!index.contains("sitemap-3.xml"));
+
+    let (status, first) = get!("/sitemap-1.xml");
+    assert!(status.is_success());
+    assert_eq!(first.matches("<url>").count(), MAX_SITEMAP_URLS);
+
+    let (status, second) = get!("/sitemap-2.xml");
+    assert!(status.is_success());
+    assert_eq!(second.matches("<url>").count(), 1);
+    assert!(second.contains(
+        "<url><loc>http://localhost:8080/blog?tag=a&amp;b</loc>\
+         <lastmod>2025-03-14T10:00:00Z</lastmod>\
+         <changefreq>weekly</changefreq>\
+         <priority>1.0</priority>"
+    ));
+    assert!(second.contains(
+        r#"<xhtml:link rel="alternate" hreflang="x-default" href="http://localhost:8080/blog?tag=a&amp;b"/>"#
+    ));
+    assert!(second.contains(
+        r#"<xhtml:link rel="alternate" hreflang="en-US" href="http://localhost:8080/blog?tag=a&amp;b&amp;lang=en-US"/>"#
+    ));
+
+    let (status, _) = get!("/sitemap-3.xml");
+    assert_eq!(status, service::http::StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,158 @@
+use pagetop::prelude::*;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+static PINGS: AtomicUsize = AtomicUsize::new(0);
+
+struct Monitor;
+
+impl Extension for Monitor {
+    fn scheduled_tasks(&self) -> Vec<ScheduledTask> {
+        vec![
+            ScheduledTask::new("ping", Schedule::every(Duration::from_secs(60))).to(ping),
+            // Las tareas con el nombre de otra ya declarada se descartan.
+            ScheduledTask::new("ping", Schedule::cron("@daily").unwrap()).to(fail),
+            ScheduledTask::new("fail", Schedule::cron("*/5 * * * *").unwrap()).to(fail),
+        ]
+    }
+}
+
+async fn ping() -> std::io::Result<()> {
+    PINGS.fetch_add(1, Ordering::SeqCst);
+    Ok(())
+}
+
+async fn fail() -> std::io::Result<()> {
+    Err(std::io::Error::other("task failed"))
+}
+
+fn local(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> DateTime<Local> {
+    Local
+        .with_ymd_and_hms(year, month, day, hour, minute, 0)
+        .unwrap()
+}
+
+fn next(expression: &str, after: DateTime<Local>) -> DateTime<Local> {
+    Schedule::cron(expression)
+        .unwrap()
+        .next_after(after)
+        .unwrap()
+}
+
+#[pagetop::test]
+async fn schedule_cron_expressions() {
+    // Lunes 5 de enero de 2026, después de las 10:07.
+    let after = local(2026, 1, 5, 10, 7) + chrono::Duration::seconds(30);
+    assert_eq!(next("*/15 * * * *", after), local(2026, 1, 5, 10, 15));
+    assert_eq!(next("7 * * * *", after), local(2026, 1, 5, 11, 7));
+    assert_eq!(next("0,30 9-17 * * *", after), local(2026, 1, 5, 10, 30));
+    assert_eq!(next("@hourly", after), local(2026, 1, 5, 11, 0));
+    assert_eq!(next("@monthly", after), local(2026, 2, 1, 0, 0));
+
+    // Días de la semana y meses por nombre, con el domingo como 0 o 7.
+    let friday = local(2026, 1, 9, 10, 0);
+    assert_eq!(next("0 9 * * mon-fri", friday), local(2026, 1, 12, 9, 0));
+    assert_eq!(next("0 9 * * 7", friday), local(2026, 1, 11, 9, 0));
+    assert_eq!(next("0 0 1 MAR *", friday), local(2026, 3, 1, 0, 0));
+
+    // Basta con que coincida el día del mes o el de la semana.
+    assert_eq!(next("0 0 13 * fri", after), local(2026, 1, 9, 0, 0));
+    assert_eq!(next("0 0 29 feb *", after), local(2028, 2, 29, 0, 0));
+
+    // Un campo que admite todos los días, aunque no sea `*`, no amplía los días del otro.
+    assert_eq!(next("0 0 */1 * MON", after), local(2026, 1, 12, 0, 0));
+    assert_eq!(next("0 0 1-31 * 0-6", after), local(2026, 1, 6, 0, 0));
+    assert_eq!(next("0 0 13 * */1", after), local(2026, 1, 13, 0, 0));
+
+    // Siempre es posterior al instante indicado.
+    assert_eq!(
+        next("0 9 * * *", local(2026, 1, 5, 9, 0)),
+        local(2026, 1, 6, 9, 0)
+    );
+
+    // Las fracciones de segundo no retrasan el siguiente minuto.
+    let now = local(2026, 1, 5, 8, 0) + chrono::Duration::milliseconds(3);
+    assert_eq!(next("* * * * *", now), local(2026, 1, 5, 8, 1));
+    let now = local(2026, 1, 5, 7, 59) + chrono::Duration::milliseconds(30_500);
+    assert_eq!(next("*/5 * * * *", now), local(2026, 1, 5, 8, 0));
+
+    assert!(Schedule::cron("0 0 31 feb *")
+        .unwrap()
+        .next_after(after)
+        .is_none());
+}
+
+#[pagetop::test]
+async fn schedule_intervals_and_display() {
+    let after = local(2026, 1, 5, 10, 0);
+    let every = Schedule::every(Duration::from_secs(90));
+    assert_eq!(
+        every.next_after(after),
+        Some(after + chrono::Duration::seconds(90))
+    );
+    assert_eq!(every.to_string(), "every 90s");
+    assert_eq!(
+        Schedule::every(Duration::from_secs(300)).to_string(),
+        "every 5m"
+    );
+    assert_eq!(
+        Schedule::every(Duration::from_millis(1500)).to_string(),
+        "every 1500ms"
+    );
+    assert_eq!(
+        Schedule::cron(" 0 8 * * mon ").unwrap().to_string(),
+        "0 8 * * mon"
+    );
+}
+
+#[pagetop::test]
+async fn schedule_rejects_invalid_cron_expressions() {
+    for expression in [
+        "",
+        "* * * *",
+        "* * * * * *",
+        "60 * * * *",
+        "* 24 * * *",
+        "* * 0 * *",
+        "*/0 * * * *",
+        "30-10 * * * *",
+        "* * * foo *",
+        "* * * * 8",
+        "@sometimes",
+    ] {
+        let error = Schedule::cron(expression).unwrap_err();
+        assert!(
+            error.to_string().contains(&format!("\"{expression}\"")),
+            "unexpected error for \"{expression}\": {error}"
+        );
+    }
+}
+
+#[pagetop::test]
+async fn schedule_tasks_run_from_cli() {
+    Application::prepare(&Monitor)
+        .cli_from(["app", "task"])
+        .await
+        .unwrap();
+    assert_eq!(PINGS.load(Ordering::SeqCst), 0);
+
+    // Se ejecuta la primera tarea declarada con el nombre indicado.
+    Application::prepare(&Monitor)
+        .cli_from(["app", "task", "ping"])
+        .await
+        .unwrap();
+    assert_eq!(PINGS.load(Ordering::SeqCst), 1);
+
+    // Los errores de la tarea se devuelven, igual que las tareas desconocidas.
+    let error = Application::prepare(&Monitor)
+        .cli_from(["app", "task", "fail"])
+        .await
+        .unwrap_err();
+    assert_eq!(error.to_string(), "task failed");
+    let error = Application::prepare(&Monitor)
+        .cli_from(["app", "task", "unknown"])
+        .await
+        .unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::NotFound);
+}
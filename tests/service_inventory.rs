@@ -0,0 +1,71 @@
+use pagetop::prelude::*;
+
+struct Shop;
+
+impl Extension for Shop {
+    fn dependencies(&self) -> Vec<ExtensionRef> {
+        vec![&pagetop::base::extension::RouteInventory]
+    }
+
+    fn routes(&self) -> Vec<service::Route> {
+        vec![
+            service::Route::get("product", "/products/{id}").to(product),
+            service::Route::delete("product.delete", "/products/{id}").to(product),
+        ]
+    }
+
+    fn configure_service(&self, scfg: &mut service::web::ServiceConfig) {
+        scfg.route("/cart", service::web::get().to(product));
+    }
+
+    fn has_unlisted_routes(&self) -> bool {
+        true
+    }
+}
+
+async fn product() -> HttpResponse {
+    HttpResponse::Ok().finish()
+}
+
+#[pagetop::test]
+async fn route_inventory_lists_routes_owners_and_static_files() {
+    let app = service::test::init_service(Application::prepare(&Shop).test()).await;
+
+    let routes = service::routes();
+    let find = |method: &str, pattern: &str| {
+        routes
+            .iter()
+            .find(|route| route.method().as_str() == method && route.pattern() == pattern)
+            .unwrap_or_else(|| panic!("route {method} {pattern} not found"))
+    };
+
+    // Rutas con nombre de las extensiones.
+    let route = find("GET", "/products/{id}");
+    assert_eq!(route.name(), Some("product"));
+    assert_eq!(route.owner(), "Shop");
+    assert_eq!(route.kind(), service::RouteKind::Handler);
+    assert_eq!(find("DELETE", "/products/{id}").owner(), "Shop");
+    assert_eq!(find("GET", "/dev/routes").owner(), "RouteInventory");
+
+    // Rutas propias de PageTop y archivos estáticos.
+    let route = find("GET", "/robots.txt");
+    assert_eq!(route.name(), None);
+    assert_eq!(route.owner(), "PageTop");
+    assert!(routes.iter().any(|route| {
+        route.pattern() == "/" && route.kind() == service::RouteKind::EmbeddedFiles
+    }));
+
+    // Extensiones con rutas que no aparecen en el inventario.
+    assert_eq!(service::unlisted_routes(), ["Shop"]);
+    assert!(!routes.iter().any(|route| route.pattern() == "/cart"));
+
+    // Página de desarrollo con el inventario.
+    let req = service::test::TestRequest::get()
+        .uri("/dev/routes")
+        .to_request();
+    let body = service::test::call_and_read_body(&app, req).await;
+    let html = String::from_utf8_lossy(&body);
+    assert!(html.contains("<code>/products/{id}</code>"));
+    assert!(html.contains("product.delete"));
+    assert!(html.contains("<li>Shop</li>"));
+}
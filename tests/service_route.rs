@@ -0,0 +1,76 @@
+use pagetop::prelude::*;
+
+struct Articles;
+
+impl Extension for Articles {
+    fn routes(&self) -> Vec<service::Route> {
+        vec![
+            service::Route::get("article", r"/articles/{id:\d+}/{slug}").to(show_article),
+            service::Route::post("article.update", r"/articles/{id:\d+}/{slug}").to(update_article),
+            service::Route::get("files", "/files/{path}*").to(show_article),
+        ]
+    }
+}
+
+async fn show_article(request: HttpRequest) -> HttpResponse {
+    let cx = Context::new(Some(request));
+    HttpResponse::Ok().body(
+        cx.url_for("article", &[("slug", "hello"), ("id", "7"), ("page", "2")])
+            .to_string(),
+    )
+}
+
+async fn update_article() -> HttpResponse {
+    HttpResponse::Ok().body("updated")
+}
+
+#[pagetop::test]
+async fn named_routes_are_served_and_reversed() {
+    let app = service::test::init_service(Application::prepare(&Articles).test()).await;
+
+    // Las rutas con el mismo patrón atienden cada método con su manejador.
+    let req = service::test::TestRequest::get()
+        .uri("/articles/1/first")
+        .to_request();
+    let body = service::test::call_and_read_body(&app, req).await;
+    assert_eq!(&body[..], b"/articles/7/hello?page=2");
+
+    let req = service::test::TestRequest::post()
+        .uri("/articles/1/first")
+        .to_request();
+    let body = service::test::call_and_read_body(&app, req).await;
+    assert_eq!(&body[..], b"updated");
+
+    // Propaga el idioma de la petición igual que `Context::route()`.
+    let req = service::test::TestRequest::get()
+        .uri("/articles/1/first?lang=es-ES")
+        .to_request();
+    let body = service::test::call_and_read_body(&app, req).await;
+    assert_eq!(&body[..], b"/articles/7/hello?page=2&lang=es-ES");
+
+    // Segmentos finales y errores.
+    assert_eq!(
+        service::url_path("files", &[("path", "docs/intro.md")])
+            .unwrap()
+            .to_string(),
+        "/files/docs/intro.md"
+    );
+    assert_eq!(
+        service::url_path("article", &[("id", "7")]).unwrap_err(),
+        service::RouteError::MissingParam {
+            route: "article".to_owned(),
+            param: "slug".to_owned(),
+        }
+    );
+    assert_eq!(
+        service::url_path("missing", &[]).unwrap_err(),
+        service::RouteError::UnknownRoute("missing".to_owned())
+    );
+}
+
+#[pagetop::test]
+#[should_panic(expected = "unknown route \"nowhere\"")]
+async fn url_for_panics_on_unknown_routes_in_debug_builds() {
+    let cx = Context::new(None);
+    let _ = cx.url_for("nowhere", &[]);
+}
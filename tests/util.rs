@@ -366,3 +366,38 @@ mod windows {
         Ok(())
     }
 }
+
+// **< Testing write_json_atomic() >****************************************************************
+
+#[pagetop::test]
+async fn write_json_atomic_creates_parent_and_replaces_file() -> io::Result<()> {
+    let root = TempDir::new()?;
+    let path = root.path().join("data").join("state.json");
+
+    util::write_json_atomic(&path, &vec!["first"])?;
+    util::write_json_atomic(&path, &vec!["second"])?;
+
+    let stored: Vec<String> = serde_json::from_str(&fs::read_to_string(&path)?)?;
+    assert_eq!(stored, vec!["second"]);
+    // El temporal añade `.tmp` al nombre completo y no queda tras renombrarlo.
+    assert!(!root.path().join("data").join("state.json.tmp").exists());
+    assert!(!root.path().join("data").join("state.tmp").exists());
+    Ok(())
+}
+
+#[pagetop::test]
+async fn write_json_atomic_keeps_files_sharing_the_stem_apart() -> io::Result<()> {
+    let root = TempDir::new()?;
+    let json = root.path().join("state.json");
+    let toml = root.path().join("state.toml");
+    fs::write(&toml, "kept = true")?;
+    // Un archivo que se llamara como el temporal antiguo no debe verse afectado.
+    fs::write(root.path().join("state.tmp"), "other")?;
+
+    util::write_json_atomic(&json, &true)?;
+
+    assert_eq!(fs::read_to_string(&json)?, "true");
+    assert_eq!(fs::read_to_string(&toml)?, "kept = true");
+    assert_eq!(fs::read_to_string(root.path().join("state.tmp"))?, "other");
+    Ok(())
+}